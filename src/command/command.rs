@@ -1,9 +1,11 @@
+use crate::command::operation_log::{Operation, Step};
 use crate::models::connection::Connection;
 use crate::models::connection_manager::{ConnectionManager, ConnectionManagerError};
 
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::{Arc, Mutex};
+use tracing::error;
 use uuid::Uuid;
 
 // AppState (refactor to another file?)
@@ -18,6 +20,7 @@ pub enum CommandError {
     DuplicateConnection,
     ConnectionNotDeleted,
     ConnectionNotFoundError,
+    InvalidTerminal(String),
     Other(String), // General error category for simplification
 }
 
@@ -31,6 +34,7 @@ impl fmt::Display for CommandError {
             CommandError::ConnectionNotFoundError => {
                 write!(f, "Connection not found")
             }
+            CommandError::InvalidTerminal(ref cause) => write!(f, "{}", cause),
             CommandError::Other(ref cause) => write!(f, "{}", cause),
         }
     }
@@ -42,10 +46,15 @@ pub trait Command {
     fn execute(&mut self) -> Result<(), CommandError>;
     fn undo(&mut self) -> Result<(), CommandError>;
     fn redo(&mut self) -> Result<(), CommandError>;
+
+    // What the command changed when last executed or redone, for the operation log
+    fn operations(&self) -> Vec<Operation> {
+        Vec::new()
+    }
 }
 
 pub struct AddConnectionCommand {
-    connection_manager: ConnectionManager,
+    connection_manager: Arc<Mutex<ConnectionManager>>,
     source: HashMap<String, String>,
     destination: HashMap<String, String>,
     connection: Option<Connection>,
@@ -53,7 +62,7 @@ pub struct AddConnectionCommand {
 
 impl AddConnectionCommand {
     pub fn new(
-        connection_manager: ConnectionManager,
+        connection_manager: Arc<Mutex<ConnectionManager>>,
         source: HashMap<String, String>,
         destination: HashMap<String, String>,
     ) -> Self {
@@ -64,41 +73,63 @@ impl AddConnectionCommand {
             connection: None,
         }
     }
+
+    // Adds `connection` as it is, keeping its UUID and wire attributes
+    pub fn with_connection(
+        connection_manager: Arc<Mutex<ConnectionManager>>,
+        connection: Connection,
+    ) -> Self {
+        AddConnectionCommand {
+            connection_manager,
+            source: HashMap::new(),
+            destination: HashMap::new(),
+            connection: Some(connection),
+        }
+    }
+
+    // The connection as it was added, once executed
+    pub fn connection(&self) -> Option<&Connection> {
+        self.connection.as_ref()
+    }
 }
 
 impl Command for AddConnectionCommand {
     fn execute(&mut self) -> Result<(), CommandError> {
-        let src_component = self.source.get("component").unwrap();
-        let src_terminal_block = self.source.get("terminal_block").unwrap();
-        let src_terminal = self.source.get("terminal").unwrap();
-        let dst_component = self.destination.get("component").unwrap();
-        let dst_terminal_block = self.destination.get("terminal_block").unwrap();
-        let dst_terminal = self.destination.get("terminal").unwrap();
-
-        match self.connection_manager.add_connection(
-            src_component.to_string(),
-            src_terminal_block.to_string(),
-            src_terminal.to_string(),
-            dst_component.to_string(),
-            dst_terminal_block.to_string(),
-            dst_terminal.to_string(),
-        ) {
+        // Redo adds the same wire again, keeping its UUID
+        let connection = match self.connection.clone() {
+            Some(connection) => connection,
+            None => {
+                let src_component = self.source.get("component").unwrap();
+                let src_terminal_block = self.source.get("terminal_block").unwrap();
+                let src_terminal = self.source.get("terminal").unwrap();
+                let dst_component = self.destination.get("component").unwrap();
+                let dst_terminal_block = self.destination.get("terminal_block").unwrap();
+                let dst_terminal = self.destination.get("terminal").unwrap();
+                Connection::new(
+                    src_component.to_string(),
+                    src_terminal_block.to_string(),
+                    src_terminal.to_string(),
+                    dst_component.to_string(),
+                    dst_terminal_block.to_string(),
+                    dst_terminal.to_string(),
+                )
+            }
+        };
+
+        let mut mgr = self.connection_manager.lock().unwrap();
+        match mgr.insert_connection(connection) {
             Ok(connection) => {
                 self.connection = Some(connection);
                 Ok(())
             }
-            Err(e) => match e {
-                ConnectionManagerError::DuplicateConnection => {
-                    Err(CommandError::DuplicateConnection)
-                }
-                _ => Err(CommandError::Other(e.to_string())),
-            },
+            Err(e) => Err(insert_error(e)),
         }
     }
 
     fn undo(&mut self) -> Result<(), CommandError> {
         if let Some(ref connection) = self.connection {
-            match self.connection_manager.delete_connection(connection) {
+            let mut mgr = self.connection_manager.lock().unwrap();
+            match mgr.delete_connection(connection) {
                 Ok(_) => {
                     // Notify the event system about the connection removed
                     Ok(())
@@ -116,12 +147,79 @@ impl Command for AddConnectionCommand {
     fn redo(&mut self) -> Result<(), CommandError> {
         self.execute()
     }
+
+    fn operations(&self) -> Vec<Operation> {
+        self.connection
+            .iter()
+            .map(|connection| Operation::Add {
+                connection: connection.clone(),
+            })
+            .collect()
+    }
 }
 
-struct DeleteConnectionCommand {
-    connection_uuids: Vec<Uuid>,
-    deleted_connections: Vec<Connection>,
-    connection_manager: Arc<Mutex<ConnectionManager>>,
+// Adds several connections as one undo step, e.g. the wires of an expanded range or an import.
+// Each wire is added by its own `AddConnectionCommand` inside a `CompositeCommand`, so if one of
+// them cannot be added, the ones already added are removed again.
+pub struct AddConnectionsCommand {
+    composite: CompositeCommand,
+}
+
+impl AddConnectionsCommand {
+    pub fn new(
+        connection_manager: Arc<Mutex<ConnectionManager>>,
+        connections: Vec<Connection>,
+    ) -> Self {
+        Self {
+            composite: CompositeCommand::new(
+                connections
+                    .into_iter()
+                    .map(|connection| {
+                        Box::new(AddConnectionCommand::with_connection(
+                            connection_manager.clone(),
+                            connection,
+                        )) as Box<dyn Command>
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    // The connections as they were added (with their wire numbers, if numbered automatically)
+    pub fn added(&self) -> Vec<Connection> {
+        self.composite
+            .operations()
+            .into_iter()
+            .filter_map(|operation| match operation {
+                Operation::Add { connection } => Some(connection),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Command for AddConnectionsCommand {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        self.composite.execute()
+    }
+
+    fn undo(&mut self) -> Result<(), CommandError> {
+        self.composite.undo()
+    }
+
+    fn redo(&mut self) -> Result<(), CommandError> {
+        self.composite.redo()
+    }
+
+    fn operations(&self) -> Vec<Operation> {
+        self.composite.operations()
+    }
+}
+
+// Deletes several connections as one undo step: all of them or, if one cannot be deleted, none.
+// Each wire is deleted by its own child command inside a `CompositeCommand`.
+pub struct DeleteConnectionCommand {
+    composite: CompositeCommand,
 }
 
 impl DeleteConnectionCommand {
@@ -130,36 +228,91 @@ impl DeleteConnectionCommand {
         connection_manager: Arc<Mutex<ConnectionManager>>,
     ) -> Self {
         Self {
-            connection_uuids,
-            deleted_connections: Vec::new(),
-            connection_manager,
+            composite: CompositeCommand::new(
+                connection_uuids
+                    .into_iter()
+                    .map(|uuid| {
+                        Box::new(DeleteOneConnection::new(uuid, connection_manager.clone()))
+                            as Box<dyn Command>
+                    })
+                    .collect(),
+            ),
         }
     }
 }
 
 impl Command for DeleteConnectionCommand {
     fn execute(&mut self) -> Result<(), CommandError> {
-        let mut mgr = self.connection_manager.lock().unwrap();
-        for uuid in &self.connection_uuids {
-            if let Some(conn) = mgr.connections.iter().find(|c| c.uuid == *uuid).cloned() {
-                mgr.delete_connection(&conn);
-                self.deleted_connections.push(conn);
-            }
+        self.composite.execute()
+    }
+
+    fn undo(&mut self) -> Result<(), CommandError> {
+        self.composite.undo()
+    }
+
+    fn redo(&mut self) -> Result<(), CommandError> {
+        self.composite.redo()
+    }
+
+    fn operations(&self) -> Vec<Operation> {
+        self.composite.operations()
+    }
+}
+
+// Deletes one connection, remembering where it was in the list and which cable core carried it
+struct DeleteOneConnection {
+    connection_uuid: Uuid,
+    connection_manager: Arc<Mutex<ConnectionManager>>,
+    // The connection with the index it had in the list, once deleted
+    deleted: Option<(usize, Connection)>,
+    // (cable tag, core) the connection was assigned to, so undo can assign it again
+    freed_core: Option<(String, String)>,
+}
+
+impl DeleteOneConnection {
+    fn new(connection_uuid: Uuid, connection_manager: Arc<Mutex<ConnectionManager>>) -> Self {
+        Self {
+            connection_uuid,
+            connection_manager,
+            deleted: None,
+            freed_core: None,
         }
+    }
+}
+
+impl Command for DeleteOneConnection {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        let mut mgr = self.connection_manager.lock().unwrap();
+        let index = mgr
+            .connections
+            .iter()
+            .position(|c| c.uuid == self.connection_uuid)
+            .ok_or(CommandError::ConnectionNotFoundError)?;
+        let connection = mgr.connections[index].clone();
+        let assignment = mgr.core_assignment(self.connection_uuid);
+        mgr.delete_connection(&connection).map_err(delete_error)?;
+        self.deleted = Some((index, connection));
+        self.freed_core = assignment;
         Ok(())
     }
 
+    // Puts the connection back where it was, with its UUID, attributes and cable core
     fn undo(&mut self) -> Result<(), CommandError> {
+        let Some((index, connection)) = self.deleted.clone() else {
+            return Err(CommandError::Other("No deletion to undo".to_string()));
+        };
         let mut mgr = self.connection_manager.lock().unwrap();
-        for conn in &self.deleted_connections {
-            let _ = mgr.add_connection(
-                conn.src_component.clone(),
-                conn.src_terminal_block.clone(),
-                conn.src_terminal.clone(),
-                conn.dst_component.clone(),
-                conn.dst_terminal_block.clone(),
-                conn.dst_terminal.clone(),
-            );
+        let connection = mgr
+            .insert_connection_at(index, connection)
+            .map_err(insert_error)?;
+        if let Some((cable, core)) = &self.freed_core {
+            if let Err(e) = mgr.assign_core(cable, core, self.connection_uuid) {
+                // Delete it again, so the deletion stays in effect as a whole
+                if let Err(delete_error) = mgr.delete_connection(&connection) {
+                    error!("Failed to delete connection again: {}", delete_error);
+                }
+                return Err(insert_error(e));
+            }
         }
         Ok(())
     }
@@ -167,29 +320,60 @@ impl Command for DeleteConnectionCommand {
     fn redo(&mut self) -> Result<(), CommandError> {
         self.execute()
     }
+
+    fn operations(&self) -> Vec<Operation> {
+        self.deleted
+            .iter()
+            .map(|(index, connection)| Operation::Delete {
+                index: *index,
+                connection: connection.clone(),
+                core: self.freed_core.clone(),
+            })
+            .collect()
+    }
 }
 
-struct EditConnectionCommand {
+// Changes the terminals of a connection in place. `new_values` holds the fields to change, keyed
+// by field name (`src_component`, ..., `dst_terminal`); the UUID and wire attributes are kept.
+pub struct EditConnectionCommand {
     connection_manager: Arc<Mutex<ConnectionManager>>,
-    old_connection_uuid: Uuid,
+    connection_uuid: Uuid,
     new_values: HashMap<String, String>,
     old_connection: Option<Connection>,
-    new_connection_uuid: Option<Uuid>,
+    new_connection: Option<Connection>,
 }
 
 impl EditConnectionCommand {
     pub fn new(
         connection_manager: Arc<Mutex<ConnectionManager>>,
-        old_connection_uuid: Uuid,
+        connection_uuid: Uuid,
         new_values: HashMap<String, String>,
     ) -> Self {
         Self {
             connection_manager,
-            old_connection_uuid,
+            connection_uuid,
             new_values,
             old_connection: None,
-            new_connection_uuid: None,
+            new_connection: None,
+        }
+    }
+
+    fn edited(&self, connection: &Connection) -> Connection {
+        let mut edited = connection.clone();
+        let fields = [
+            ("src_component", &mut edited.src_component),
+            ("src_terminal_block", &mut edited.src_terminal_block),
+            ("src_terminal", &mut edited.src_terminal),
+            ("dst_component", &mut edited.dst_component),
+            ("dst_terminal_block", &mut edited.dst_terminal_block),
+            ("dst_terminal", &mut edited.dst_terminal),
+        ];
+        for (name, field) in fields {
+            if let Some(value) = self.new_values.get(name) {
+                *field = value.clone();
+            }
         }
+        edited
     }
 }
 
@@ -197,79 +381,311 @@ impl Command for EditConnectionCommand {
     fn execute(&mut self) -> Result<(), CommandError> {
         let mut cm = self.connection_manager.lock().unwrap();
 
-        // Find and store the old connection for undo
-        if let Some(index) = cm
-            .connections
-            .iter()
-            .position(|conn| conn.uuid == self.old_connection_uuid)
-        {
-            self.old_connection = Some(cm.connections[index].clone());
-
-            // Remove the old connection
-            cm.connections.remove(index);
-
-            // Create and add the new connection
-            let new_connection = Connection::new(
-                self.new_values
-                    .get("src_component")
-                    .cloned()
-                    .unwrap_or_default(),
-                self.new_values
-                    .get("src_terminal_block")
-                    .cloned()
-                    .unwrap_or_default(),
-                self.new_values
-                    .get("src_terminal")
-                    .cloned()
-                    .unwrap_or_default(),
-                self.new_values
-                    .get("dst_component")
-                    .cloned()
-                    .unwrap_or_default(),
-                self.new_values
-                    .get("dst_terminal_block")
-                    .cloned()
-                    .unwrap_or_default(),
-                self.new_values
-                    .get("dst_terminal")
-                    .cloned()
-                    .unwrap_or_default(),
-            );
-
-            // Track the UUID of the newly created connection
-            self.new_connection_uuid = Some(new_connection.uuid);
-
-            // Add the new connection
-            cm.connections.push(new_connection);
-        } else {
-            return Err(CommandError::ConnectionNotFoundError);
+        let new_connection = match self.new_connection.clone() {
+            Some(connection) => connection,
+            None => {
+                let old_connection = cm
+                    .find_connection(self.connection_uuid)
+                    .ok_or(CommandError::ConnectionNotFoundError)?;
+                self.edited(old_connection)
+            }
+        };
+
+        // Duplicates and catalog violations leave the list untouched
+        let old_connection = cm
+            .replace_connection(new_connection.clone())
+            .map_err(insert_error)?;
+        self.old_connection = Some(old_connection);
+        self.new_connection = Some(new_connection);
+        Ok(())
+    }
+
+    fn undo(&mut self) -> Result<(), CommandError> {
+        let Some(old_connection) = self.old_connection.clone() else {
+            return Err(CommandError::Other("No edit to undo".to_string()));
+        };
+        let mut cm = self.connection_manager.lock().unwrap();
+        cm.replace_connection(old_connection)
+            .map_err(insert_error)?;
+        Ok(())
+    }
+
+    fn redo(&mut self) -> Result<(), CommandError> {
+        self.execute()
+    }
+
+    fn operations(&self) -> Vec<Operation> {
+        match (&self.old_connection, &self.new_connection) {
+            (Some(before), Some(after)) => vec![Operation::Edit {
+                before: Box::new(before.clone()),
+                after: Box::new(after.clone()),
+            }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+// Numbers wires with the configured wire numbering, as one undo step. Undo and redo restore the
+// numbers that were recorded rather than numbering again.
+pub struct NumberWiresCommand {
+    connection_manager: Arc<Mutex<ConnectionManager>>,
+    // Throw away existing numbers first (`ConnectionManager::renumber_wires`) instead of only
+    // numbering wires without one (`ConnectionManager::number_new_wires`)
+    renumber: bool,
+    // (before, after) of every connection whose number changed
+    changes: Vec<(Connection, Connection)>,
+}
+
+impl NumberWiresCommand {
+    pub fn renumber(connection_manager: Arc<Mutex<ConnectionManager>>) -> Self {
+        Self {
+            connection_manager,
+            renumber: true,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn number_new(connection_manager: Arc<Mutex<ConnectionManager>>) -> Self {
+        Self {
+            connection_manager,
+            renumber: false,
+            changes: Vec::new(),
         }
+    }
+
+    fn set_numbers(&self, numbers: Vec<(Uuid, Option<String>)>) -> Result<(), CommandError> {
+        let mut cm = self.connection_manager.lock().unwrap();
+        cm.set_wire_numbers(&numbers).map_err(insert_error)
+    }
+}
 
+impl Command for NumberWiresCommand {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        let mut cm = self.connection_manager.lock().unwrap();
+        let before = cm.connections.clone();
+        let result = if self.renumber {
+            cm.renumber_wires()
+        } else {
+            cm.number_new_wires()
+        };
+        if let Err(e) = result {
+            // Numbering happens in memory before saving, so put the old numbers back
+            cm.connections = before;
+            return Err(CommandError::Other(e.to_string()));
+        }
+        self.changes = before
+            .into_iter()
+            .zip(&cm.connections)
+            .filter(|(before, after)| before.wire_number != after.wire_number)
+            .map(|(before, after)| (before, after.clone()))
+            .collect();
         Ok(())
     }
 
     fn undo(&mut self) -> Result<(), CommandError> {
-        if let Some(new_connection_uuid) = self.new_connection_uuid {
-            let mut cm = self.connection_manager.lock().unwrap();
+        self.set_numbers(
+            self.changes
+                .iter()
+                .map(|(before, _)| (before.uuid, before.wire_number.clone()))
+                .collect(),
+        )
+    }
 
-            // Find and remove the newly added connection using its UUID
-            if let Some(index) = cm
-                .connections
+    fn redo(&mut self) -> Result<(), CommandError> {
+        self.set_numbers(
+            self.changes
                 .iter()
-                .position(|conn| conn.uuid == new_connection_uuid)
-            {
-                cm.connections.remove(index);
-            }
+                .map(|(_, after)| (after.uuid, after.wire_number.clone()))
+                .collect(),
+        )
+    }
+
+    fn operations(&self) -> Vec<Operation> {
+        self.changes
+            .iter()
+            .map(|(before, after)| Operation::Edit {
+                before: Box::new(before.clone()),
+                after: Box::new(after.clone()),
+            })
+            .collect()
+    }
+}
+
+// Runs several commands as one undo step, e.g. deleting a selection or importing a file. The
+// children run in order; if one fails, those already applied are undone again, so the composite
+// takes effect completely or not at all. Undo and redo likewise apply to all children. Nothing
+// here depends on the UI, so the same commands can be driven from the command line.
+#[derive(Default)]
+pub struct CompositeCommand {
+    commands: Vec<Box<dyn Command>>,
+}
+
+impl CompositeCommand {
+    pub fn new(commands: Vec<Box<dyn Command>>) -> Self {
+        Self { commands }
+    }
+
+    pub fn push(&mut self, command: Box<dyn Command>) {
+        self.commands.push(command);
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl Command for CompositeCommand {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        apply_all(self.commands.iter_mut(), |c| c.execute(), |c| c.undo())
+    }
 
-            // Re-add the original (old) connection if it exists
-            if let Some(old_connection) = &self.old_connection {
-                cm.connections.push(old_connection.clone());
+    // Undoes the children in reverse order; if one fails, those already undone are redone
+    fn undo(&mut self) -> Result<(), CommandError> {
+        apply_all(self.commands.iter_mut().rev(), |c| c.undo(), |c| c.redo())
+    }
+
+    fn redo(&mut self) -> Result<(), CommandError> {
+        apply_all(self.commands.iter_mut(), |c| c.redo(), |c| c.undo())
+    }
+
+    fn operations(&self) -> Vec<Operation> {
+        self.commands.iter().flat_map(|c| c.operations()).collect()
+    }
+}
+
+// Replays one logged operation, e.g. for undo steps restored from the operation log. The
+// connections keep the UUIDs and attributes they were logged with.
+pub struct OperationCommand {
+    connection_manager: Arc<Mutex<ConnectionManager>>,
+    operation: Operation,
+}
+
+impl OperationCommand {
+    pub fn new(connection_manager: Arc<Mutex<ConnectionManager>>, operation: Operation) -> Self {
+        Self {
+            connection_manager,
+            operation,
+        }
+    }
+
+    // One undo step made of the operations of a logged step
+    pub fn step(
+        connection_manager: &Arc<Mutex<ConnectionManager>>,
+        operations: Step,
+    ) -> CompositeCommand {
+        CompositeCommand::new(
+            operations
+                .into_iter()
+                .map(|operation| {
+                    Box::new(Self::new(connection_manager.clone(), operation)) as Box<dyn Command>
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Command for OperationCommand {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        let mut mgr = self.connection_manager.lock().unwrap();
+        match &self.operation {
+            Operation::Add { connection } => mgr
+                .insert_connection(connection.clone())
+                .map(|_| ())
+                .map_err(insert_error),
+            Operation::Delete { connection, .. } => delete_by_uuid(&mut mgr, connection.uuid),
+            Operation::Edit { after, .. } => mgr
+                .replace_connection(after.as_ref().clone())
+                .map(|_| ())
+                .map_err(insert_error),
+        }
+    }
+
+    fn undo(&mut self) -> Result<(), CommandError> {
+        let mut mgr = self.connection_manager.lock().unwrap();
+        match &self.operation {
+            Operation::Add { connection } => delete_by_uuid(&mut mgr, connection.uuid),
+            Operation::Delete {
+                index,
+                connection,
+                core,
+            } => {
+                let connection = mgr
+                    .insert_connection_at(*index, connection.clone())
+                    .map_err(insert_error)?;
+                if let Some((cable, core)) = core {
+                    if let Err(e) = mgr.assign_core(cable, core, connection.uuid) {
+                        if let Err(delete_error) = mgr.delete_connection(&connection) {
+                            error!("Failed to delete connection again: {}", delete_error);
+                        }
+                        return Err(insert_error(e));
+                    }
+                }
+                Ok(())
             }
+            Operation::Edit { before, .. } => mgr
+                .replace_connection(before.as_ref().clone())
+                .map(|_| ())
+                .map_err(insert_error),
         }
-        Ok(())
     }
 
     fn redo(&mut self) -> Result<(), CommandError> {
         self.execute()
     }
+
+    fn operations(&self) -> Vec<Operation> {
+        vec![self.operation.clone()]
+    }
+}
+
+fn delete_by_uuid(mgr: &mut ConnectionManager, uuid: Uuid) -> Result<(), CommandError> {
+    let connection = mgr
+        .find_connection(uuid)
+        .cloned()
+        .ok_or(CommandError::ConnectionNotFoundError)?;
+    mgr.delete_connection(&connection).map_err(delete_error)
+}
+
+// Applies `apply` to each command in turn. On the first error, `revert` is applied to the
+// commands already done, last first, and the error is returned.
+fn apply_all<'a>(
+    commands: impl Iterator<Item = &'a mut Box<dyn Command>>,
+    apply: fn(&mut dyn Command) -> Result<(), CommandError>,
+    revert: fn(&mut dyn Command) -> Result<(), CommandError>,
+) -> Result<(), CommandError> {
+    let mut done: Vec<&'a mut Box<dyn Command>> = Vec::new();
+    for command in commands {
+        if let Err(e) = apply(command.as_mut()) {
+            for command in done.into_iter().rev() {
+                if let Err(revert_error) = revert(command.as_mut()) {
+                    error!("Failed to roll back command: {}", revert_error);
+                }
+            }
+            return Err(e);
+        }
+        done.push(command);
+    }
+    Ok(())
+}
+
+fn delete_error(e: ConnectionManagerError) -> CommandError {
+    match e {
+        ConnectionManagerError::MalformedData => CommandError::ConnectionNotDeleted,
+        _ => CommandError::Other(e.to_string()),
+    }
+}
+
+fn insert_error(e: ConnectionManagerError) -> CommandError {
+    match e {
+        ConnectionManagerError::DuplicateConnection => CommandError::DuplicateConnection,
+        ConnectionManagerError::ConnectionNotFoundError => CommandError::ConnectionNotFoundError,
+        ConnectionManagerError::CatalogViolation(violation) => {
+            CommandError::InvalidTerminal(violation.to_string())
+        }
+        _ => CommandError::Other(e.to_string()),
+    }
 }
@@ -1,35 +1,117 @@
-use crate::command::command::{Command, CommandError};
+use std::sync::{Arc, Mutex};
+use tracing::{error, warn};
 
+use crate::command::command::{Command, CommandError, OperationCommand};
+use crate::command::operation_log::{Action, OperationLog, OperationLogError, Step};
+use crate::models::connection_manager::ConnectionManager;
+
+#[derive(Default)]
 pub struct CommandManager {
     undo_stack: Vec<Box<dyn Command>>,
     redo_stack: Vec<Box<dyn Command>>,
+    // Where executed, undone and redone steps are recorded, if anywhere, together with the
+    // revision of the project they leave behind
+    log: Option<(OperationLog, Arc<Mutex<ConnectionManager>>)>,
 }
 
 impl CommandManager {
-    pub fn execute_command(&mut self, mut command: Box<dyn Command>) {
-        if command.execute().is_ok() {
-            self.undo_stack.push(command);
-            self.redo_stack.clear(); // Clear redo stack on new command execution
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Starts with empty stacks and records every step made to `connection_manager` in `log`
+    pub fn with_log(log: OperationLog, connection_manager: &Arc<Mutex<ConnectionManager>>) -> Self {
+        Self {
+            log: Some((log, connection_manager.clone())),
+            ..Self::default()
+        }
+    }
+
+    // Rebuilds the undo and redo stacks from the steps recorded in `log`, e.g. when a project is
+    // reopened, and keeps recording to it. Changes made without a command, such as cable and
+    // bridge edits or edits in other programs, are not logged, so when the project no longer
+    // matches the revision of the last step the stacks are dropped and a reset is logged instead
+    // of replaying steps onto the wrong wires.
+    pub fn restore(
+        log: OperationLog,
+        connection_manager: &Arc<Mutex<ConnectionManager>>,
+    ) -> Result<Self, OperationLogError> {
+        let Some(logged) = log.revision()? else {
+            return Ok(Self::with_log(log, connection_manager));
+        };
+        let (undo_steps, redo_steps) = log.stacks()?;
+        let mut command_manager = Self::with_log(log, connection_manager);
+        if logged != connection_manager.lock().unwrap().revision() {
+            warn!("The project was changed outside of the undo history, which is cleared");
+            command_manager.record(Action::Reset, Vec::new());
+            return Ok(command_manager);
         }
+        let step = |operations| -> Box<dyn Command> {
+            Box::new(OperationCommand::step(connection_manager, operations))
+        };
+        command_manager.undo_stack = undo_steps.into_iter().map(step).collect();
+        command_manager.redo_stack = redo_steps.into_iter().map(step).collect();
+        Ok(command_manager)
     }
 
+    // Failed commands are not recorded, so there is nothing to undo for them
+    pub fn execute_command(&mut self, mut command: Box<dyn Command>) -> Result<(), CommandError> {
+        command.execute()?;
+        self.record(Action::Execute, command.operations());
+        self.undo_stack.push(command);
+        self.redo_stack.clear(); // Clear redo stack on new command execution
+        Ok(())
+    }
+
+    // A command that fails to undo stays on the undo stack, so it can be tried again
     pub fn undo(&mut self) -> Result<(), CommandError> {
-        if let Some(mut command) = self.undo_stack.pop() {
-            command.undo()?;
-            self.redo_stack.push(command);
-            Ok(())
-        } else {
-            Err(CommandError::Other("No command to undo".into()))
+        let Some(mut command) = self.undo_stack.pop() else {
+            return Err(CommandError::Other("No command to undo".into()));
+        };
+        // Taken before undoing, while the command still knows what it changed
+        let operations = command.operations();
+        if let Err(e) = command.undo() {
+            self.undo_stack.push(command);
+            return Err(e);
         }
+        self.record(Action::Undo, operations);
+        self.redo_stack.push(command);
+        Ok(())
     }
 
     pub fn redo(&mut self) -> Result<(), CommandError> {
-        if let Some(mut command) = self.redo_stack.pop() {
-            command.redo()?;
-            self.undo_stack.push(command);
-            Ok(())
-        } else {
-            Err(CommandError::Other("No command to redo".into()))
+        let Some(mut command) = self.redo_stack.pop() else {
+            return Err(CommandError::Other("No command to redo".into()));
+        };
+        if let Err(e) = command.redo() {
+            self.redo_stack.push(command);
+            return Err(e);
+        }
+        self.record(Action::Redo, command.operations());
+        self.undo_stack.push(command);
+        Ok(())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    // The change itself has already been made and saved, so a log that cannot be written is
+    // reported but does not fail the command
+    fn record(&self, action: Action, operations: Step) {
+        if let Some((log, connection_manager)) = &self.log {
+            let revision = connection_manager.lock().unwrap().revision();
+            if let Err(e) = log.append(action, operations, revision) {
+                error!(
+                    "Failed to write undo history to {}: {}",
+                    log.path().display(),
+                    e
+                );
+            }
         }
     }
 }
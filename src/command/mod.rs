@@ -1,2 +1,3 @@
 pub mod command;
 pub mod command_manager;
+pub mod operation_log;
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::connection::Connection;
+use crate::models::designation::DesignationFormatter;
+use crate::utils::timestamp;
+
+#[derive(Debug, Error)]
+pub enum OperationLogError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Malformed history entry on line {line}: {source}")]
+    MalformedEntry {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+// One change to one wire. Undo steps are recorded as the list of operations they made, so a
+// step can be replayed or reverted from the log alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    Add {
+        connection: Connection,
+    },
+    // `index` is the position the connection had in the list when it was deleted, `core` the
+    // (cable tag, core) that carried it, if any
+    Delete {
+        index: usize,
+        connection: Connection,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        core: Option<(String, String)>,
+    },
+    // Boxed to keep the enum small
+    Edit {
+        before: Box<Connection>,
+        after: Box<Connection>,
+    },
+}
+
+// The operations of one undo step, in the order they were made
+pub type Step = Vec<Operation>;
+
+impl Operation {
+    // The wire this operation changed
+    pub fn uuid(&self) -> Uuid {
+        match self {
+            Operation::Add { connection } | Operation::Delete { connection, .. } => connection.uuid,
+            Operation::Edit { after, .. } => after.uuid,
+        }
+    }
+
+    // e.g. `added <uuid> (K1-X1-3 to K2-X2-4)`, with the ends written by `formatter`
+    pub fn describe(&self, formatter: &DesignationFormatter) -> String {
+        let wire = |connection: &Connection| {
+            let (source, destination) = connection.to_tuple(formatter);
+            format!("{} to {}", source, destination)
+        };
+        match self {
+            Operation::Add { connection } => {
+                format!("added {} ({})", connection.uuid, wire(connection))
+            }
+            Operation::Delete { connection, .. } => {
+                format!("deleted {} ({})", connection.uuid, wire(connection))
+            }
+            Operation::Edit { before, after } => format!(
+                "edited {} ({} -> {})",
+                after.uuid,
+                wire(before),
+                wire(after)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Execute,
+    Undo,
+    Redo,
+    // The project was changed outside of the history (an import, renumbering, an edit in
+    // another program), so the earlier steps can no longer be undone or redone
+    Reset,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Execute => write!(f, "execute"),
+            Action::Undo => write!(f, "undo"),
+            Action::Redo => write!(f, "redo"),
+            Action::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+// One line of the log: an undo step that was executed, undone or redone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    // RFC 3339 UTC, e.g. `2024-03-01T14:05:09Z`
+    pub timestamp: String,
+    pub author: String,
+    pub action: Action,
+    pub operations: Step,
+    // `ConnectionManager::revision` of the project after the action; empty in logs written
+    // before revisions were recorded
+    #[serde(default)]
+    pub revision: String,
+}
+
+impl LogEntry {
+    pub fn touches(&self, uuid: Uuid) -> bool {
+        self.operations
+            .iter()
+            .any(|operation| operation.uuid() == uuid)
+    }
+
+    // One line of the audit trail, e.g. `2024-03-01T14:05:09Z alice undo: deleted ...`
+    pub fn describe(&self, formatter: &DesignationFormatter) -> String {
+        let mut line = format!("{} {} {}", self.timestamp, self.author, self.action);
+        for (i, operation) in self.operations.iter().enumerate() {
+            let separator = if i == 0 { ":" } else { "," };
+            line.push_str(&format!("{} {}", separator, operation.describe(formatter)));
+        }
+        line
+    }
+}
+
+// Append-only JSON Lines file with the undo history of a project, kept next to the project file.
+// Entries are never rewritten, so the file doubles as an audit trail of who changed which wire
+// and when.
+#[derive(Debug, Clone)]
+pub struct OperationLog {
+    path: PathBuf,
+    author: String,
+}
+
+impl OperationLog {
+    pub fn new(path: PathBuf, author: String) -> Self {
+        Self { path, author }
+    }
+
+    // `connections.json` → `connections.history.jsonl`
+    pub fn path_for_project(project_path: &Path) -> PathBuf {
+        project_path.with_extension("history.jsonl")
+    }
+
+    // The project's author, else the user logged in to the system
+    pub fn default_author(project_author: &str) -> String {
+        if !project_author.trim().is_empty() {
+            return project_author.trim().to_string();
+        }
+        ["USER", "USERNAME"]
+            .into_iter()
+            .find_map(|name| std::env::var(name).ok().filter(|user| !user.is_empty()))
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(
+        &self,
+        action: Action,
+        operations: Step,
+        revision: String,
+    ) -> Result<LogEntry, OperationLogError> {
+        let entry = LogEntry {
+            timestamp: timestamp::to_rfc3339(timestamp::unix_now()),
+            author: self.author.clone(),
+            action,
+            operations,
+            revision,
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+
+    // Every entry in the order written; a missing file is an empty history
+    pub fn entries(&self) -> Result<Vec<LogEntry>, OperationLogError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|source| OperationLogError::MalformedEntry {
+                    line: i + 1,
+                    source,
+                })
+            })
+            .collect()
+    }
+
+    // The entries that changed the wire with `uuid`
+    pub fn history_of(&self, uuid: Uuid) -> Result<Vec<LogEntry>, OperationLogError> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.touches(uuid))
+            .collect())
+    }
+
+    // The revision the project had after the last entry, if there is one
+    pub fn revision(&self) -> Result<Option<String>, OperationLogError> {
+        Ok(self.entries()?.pop().map(|entry| entry.revision))
+    }
+
+    // The steps on the undo and redo stacks after replaying the log, each bottom first
+    pub fn stacks(&self) -> Result<(Vec<Step>, Vec<Step>), OperationLogError> {
+        let mut undo_stack = Vec::new();
+        let mut redo_stack = Vec::new();
+        for entry in self.entries()? {
+            match entry.action {
+                Action::Execute => {
+                    undo_stack.push(entry.operations);
+                    redo_stack.clear();
+                }
+                Action::Undo => {
+                    if let Some(step) = undo_stack.pop() {
+                        redo_stack.push(step);
+                    }
+                }
+                Action::Redo => {
+                    if let Some(step) = redo_stack.pop() {
+                        undo_stack.push(step);
+                    }
+                }
+                Action::Reset => {
+                    undo_stack.clear();
+                    redo_stack.clear();
+                }
+            }
+        }
+        Ok((undo_stack, redo_stack))
+    }
+}
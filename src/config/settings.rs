@@ -13,6 +13,78 @@ pub struct Settings {
     csv_save_location: String,
     default_csv_delimiter: String,
     file_path: PathBuf,
+    // Optional component catalog (JSON or CSV) and how violations are handled ("warn"/"reject")
+    #[serde(default)]
+    catalog_file: String,
+    #[serde(default)]
+    catalog_validation: String,
+    // CSV export layout, see `ExportOptions::from_settings`
+    #[serde(default)]
+    csv_quote_style: String,
+    #[serde(default)]
+    csv_include_header: String,
+    #[serde(default)]
+    csv_line_ending: String,
+    #[serde(default)]
+    csv_utf8_bom: String,
+    #[serde(default)]
+    csv_columns: String,
+    // How component, terminal block and terminal are joined, see `DesignationFormatter`; empty
+    // leaves each export layout its own pattern
+    #[serde(default)]
+    designation_pattern: String,
+    // "single", "near_far", "far_only" or "both_addresses"
+    #[serde(default)]
+    labeling_mode: String,
+    // Name of the label exporter, see `label_exporter_by_name`
+    #[serde(default)]
+    label_exporter: String,
+    // "sequential", "per_potential" or "per_page_and_column"
+    #[serde(default)]
+    wire_numbering_scheme: String,
+    // Layout of a wire number, see `WireNumberTemplate`
+    #[serde(default)]
+    wire_number_template: String,
+    // Number new wires as they are added
+    #[serde(default)]
+    auto_wire_numbering: String,
+    // "address_only", "number_only" or "number_and_address"
+    #[serde(default)]
+    wire_number_display: String,
+    // How far the terminal fields advance after a connection is added
+    #[serde(default)]
+    increment_step: String,
+    // ZPL label layout, see `ZplOptions::from_settings`
+    #[serde(default)]
+    zpl_label_width_mm: String,
+    #[serde(default)]
+    zpl_label_height_mm: String,
+    #[serde(default)]
+    zpl_dpi: String,
+    #[serde(default)]
+    zpl_font: String,
+    #[serde(default)]
+    zpl_font_height: String,
+    #[serde(default)]
+    zpl_font_width: String,
+    #[serde(default)]
+    zpl_orientation: String,
+    #[serde(default)]
+    zpl_repeat: String,
+    #[serde(default)]
+    zpl_both_ends: String,
+    // Printer the labels are sent to: `host`, `host:port` or an IP address, port 9100 by default
+    #[serde(default)]
+    zpl_printer_address: String,
+    // PDF label sheet layout, see `PdfOptions::from_settings`
+    #[serde(default)]
+    pdf_sheet_template: String,
+    #[serde(default)]
+    pdf_start_offset: String,
+    #[serde(default)]
+    pdf_max_font_size: String,
+    #[serde(default)]
+    pdf_min_font_size: String,
 }
 
 impl Settings {
@@ -52,6 +124,35 @@ impl Settings {
             csv_save_location: "/home/rsp/documents".to_string(),
             default_csv_delimiter: "|".to_string(),
             file_path: "resources/data/settings.json".into(),
+            catalog_file: "".to_string(),
+            catalog_validation: "warn".to_string(),
+            csv_quote_style: "necessary".to_string(),
+            csv_include_header: "false".to_string(),
+            csv_line_ending: "lf".to_string(),
+            csv_utf8_bom: "false".to_string(),
+            csv_columns: "source,destination".to_string(),
+            label_exporter: "generic_csv".to_string(),
+            designation_pattern: "".to_string(),
+            labeling_mode: "single".to_string(),
+            wire_numbering_scheme: "sequential".to_string(),
+            wire_number_template: "{n}".to_string(),
+            auto_wire_numbering: "false".to_string(),
+            wire_number_display: "address_only".to_string(),
+            increment_step: "1".to_string(),
+            zpl_label_width_mm: "50".to_string(),
+            zpl_label_height_mm: "12".to_string(),
+            zpl_dpi: "300".to_string(),
+            zpl_font: "0".to_string(),
+            zpl_font_height: "30".to_string(),
+            zpl_font_width: "30".to_string(),
+            zpl_orientation: "normal".to_string(),
+            zpl_repeat: "1".to_string(),
+            zpl_both_ends: "false".to_string(),
+            zpl_printer_address: "".to_string(),
+            pdf_sheet_template: "avery_l7651".to_string(),
+            pdf_start_offset: "0".to_string(),
+            pdf_max_font_size: "10".to_string(),
+            pdf_min_font_size: "4".to_string(),
         }
     }
 
@@ -81,6 +182,122 @@ impl Settings {
                 debug!("Accessed 'default_csv_delimiter' setting.");
                 Some(&self.default_csv_delimiter)
             }
+            "catalog_file" => {
+                debug!("Accessed 'catalog_file' setting.");
+                Some(&self.catalog_file)
+            }
+            "catalog_validation" => {
+                debug!("Accessed 'catalog_validation' setting.");
+                Some(&self.catalog_validation)
+            }
+            "csv_quote_style" => {
+                debug!("Accessed 'csv_quote_style' setting.");
+                Some(&self.csv_quote_style)
+            }
+            "csv_include_header" => {
+                debug!("Accessed 'csv_include_header' setting.");
+                Some(&self.csv_include_header)
+            }
+            "csv_line_ending" => {
+                debug!("Accessed 'csv_line_ending' setting.");
+                Some(&self.csv_line_ending)
+            }
+            "csv_utf8_bom" => {
+                debug!("Accessed 'csv_utf8_bom' setting.");
+                Some(&self.csv_utf8_bom)
+            }
+            "csv_columns" => {
+                debug!("Accessed 'csv_columns' setting.");
+                Some(&self.csv_columns)
+            }
+            "designation_pattern" => {
+                debug!("Accessed 'designation_pattern' setting.");
+                Some(&self.designation_pattern)
+            }
+            "labeling_mode" => {
+                debug!("Accessed 'labeling_mode' setting.");
+                Some(&self.labeling_mode)
+            }
+            "label_exporter" => {
+                debug!("Accessed 'label_exporter' setting.");
+                Some(&self.label_exporter)
+            }
+            "wire_numbering_scheme" => {
+                debug!("Accessed 'wire_numbering_scheme' setting.");
+                Some(&self.wire_numbering_scheme)
+            }
+            "wire_number_template" => {
+                debug!("Accessed 'wire_number_template' setting.");
+                Some(&self.wire_number_template)
+            }
+            "auto_wire_numbering" => {
+                debug!("Accessed 'auto_wire_numbering' setting.");
+                Some(&self.auto_wire_numbering)
+            }
+            "wire_number_display" => {
+                debug!("Accessed 'wire_number_display' setting.");
+                Some(&self.wire_number_display)
+            }
+            "increment_step" => {
+                debug!("Accessed 'increment_step' setting.");
+                Some(&self.increment_step)
+            }
+            "zpl_label_width_mm" => {
+                debug!("Accessed 'zpl_label_width_mm' setting.");
+                Some(&self.zpl_label_width_mm)
+            }
+            "zpl_label_height_mm" => {
+                debug!("Accessed 'zpl_label_height_mm' setting.");
+                Some(&self.zpl_label_height_mm)
+            }
+            "zpl_dpi" => {
+                debug!("Accessed 'zpl_dpi' setting.");
+                Some(&self.zpl_dpi)
+            }
+            "zpl_font" => {
+                debug!("Accessed 'zpl_font' setting.");
+                Some(&self.zpl_font)
+            }
+            "zpl_font_height" => {
+                debug!("Accessed 'zpl_font_height' setting.");
+                Some(&self.zpl_font_height)
+            }
+            "zpl_font_width" => {
+                debug!("Accessed 'zpl_font_width' setting.");
+                Some(&self.zpl_font_width)
+            }
+            "zpl_orientation" => {
+                debug!("Accessed 'zpl_orientation' setting.");
+                Some(&self.zpl_orientation)
+            }
+            "zpl_repeat" => {
+                debug!("Accessed 'zpl_repeat' setting.");
+                Some(&self.zpl_repeat)
+            }
+            "zpl_both_ends" => {
+                debug!("Accessed 'zpl_both_ends' setting.");
+                Some(&self.zpl_both_ends)
+            }
+            "zpl_printer_address" => {
+                debug!("Accessed 'zpl_printer_address' setting.");
+                Some(&self.zpl_printer_address)
+            }
+            "pdf_sheet_template" => {
+                debug!("Accessed 'pdf_sheet_template' setting.");
+                Some(&self.pdf_sheet_template)
+            }
+            "pdf_start_offset" => {
+                debug!("Accessed 'pdf_start_offset' setting.");
+                Some(&self.pdf_start_offset)
+            }
+            "pdf_max_font_size" => {
+                debug!("Accessed 'pdf_max_font_size' setting.");
+                Some(&self.pdf_max_font_size)
+            }
+            "pdf_min_font_size" => {
+                debug!("Accessed 'pdf_min_font_size' setting.");
+                Some(&self.pdf_min_font_size)
+            }
             _ => None,
         }
     }
@@ -97,5 +314,34 @@ impl Settings {
         println!("CSV Save Location: {}", self.csv_save_location);
         println!("Default CSV Delimiter: {}", self.default_csv_delimiter);
         println!("Settings File Path: {}", self.file_path.display());
+        println!("Catalog File: {}", self.catalog_file);
+        println!("Catalog Validation: {}", self.catalog_validation);
+        println!("CSV Quote Style: {}", self.csv_quote_style);
+        println!("CSV Include Header: {}", self.csv_include_header);
+        println!("CSV Line Ending: {}", self.csv_line_ending);
+        println!("CSV UTF-8 BOM: {}", self.csv_utf8_bom);
+        println!("CSV Columns: {}", self.csv_columns);
+        println!("Label Exporter: {}", self.label_exporter);
+        println!("Designation Pattern: {}", self.designation_pattern);
+        println!("Labeling Mode: {}", self.labeling_mode);
+        println!("Wire Numbering Scheme: {}", self.wire_numbering_scheme);
+        println!("Wire Number Template: {}", self.wire_number_template);
+        println!("Auto Wire Numbering: {}", self.auto_wire_numbering);
+        println!("Wire Number Display: {}", self.wire_number_display);
+        println!("Increment Step: {}", self.increment_step);
+        println!("ZPL Label Width (mm): {}", self.zpl_label_width_mm);
+        println!("ZPL Label Height (mm): {}", self.zpl_label_height_mm);
+        println!("ZPL DPI: {}", self.zpl_dpi);
+        println!("ZPL Font: {}", self.zpl_font);
+        println!("ZPL Font Height: {}", self.zpl_font_height);
+        println!("ZPL Font Width: {}", self.zpl_font_width);
+        println!("ZPL Orientation: {}", self.zpl_orientation);
+        println!("ZPL Repeat: {}", self.zpl_repeat);
+        println!("ZPL Both Ends: {}", self.zpl_both_ends);
+        println!("ZPL Printer Address: {}", self.zpl_printer_address);
+        println!("PDF Sheet Template: {}", self.pdf_sheet_template);
+        println!("PDF Start Offset: {}", self.pdf_start_offset);
+        println!("PDF Max Font Size: {}", self.pdf_max_font_size);
+        println!("PDF Min Font Size: {}", self.pdf_min_font_size);
     }
 }
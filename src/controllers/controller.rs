@@ -7,9 +7,9 @@
 // use crate::utils::csv_exporting_strategy::{
 //     ExportCableToCSVStrategy, ExportFormat, ExportWireToCSVStrategy,
 // };
-// use crate::utils::{command_manager, file_handler};
+// use crate::command::command_manager::CommandManager;
+// use crate::utils::file_handler;
 // use crate::utils::{
-//     command_manager::CommandManager,
 //     file_handler::FileHandler,
 //     localizer::{self, Localizer},
 // };
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use uuid::Uuid;
+
+use crate::models::terminal::TerminalAddress;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub enum BridgeKind {
+    // Comb bridge plugged into the bridge shaft of the terminal block
+    #[default]
+    PlugIn,
+    // Bridge bar clamped under the terminal screws
+    Screw,
+}
+
+impl fmt::Display for BridgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeKind::PlugIn => write!(f, "Plug-in"),
+            BridgeKind::Screw => write!(f, "Screw"),
+        }
+    }
+}
+
+// Jumper joining neighbouring terminals of one terminal block (e.g. X1-1..X1-5 for 0 V).
+// Bridges take part in net computation but never produce wire labels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bridge {
+    pub component: String,
+    #[serde(default)]
+    pub terminal_block: String,
+    // Bridged terminals in strip order
+    pub terminals: Vec<String>,
+    #[serde(default)]
+    pub kind: BridgeKind,
+    #[serde(default)]
+    pub part_number: Option<String>,
+    // IEC 81346 aspects of the bridged terminal block
+    #[serde(default)]
+    pub function: String,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default = "default_uuid")]
+    pub uuid: Uuid,
+}
+
+impl Bridge {
+    pub fn new(
+        component: String,
+        terminal_block: String,
+        terminals: Vec<String>,
+        kind: BridgeKind,
+    ) -> Self {
+        Self {
+            component,
+            terminal_block,
+            terminals,
+            kind,
+            part_number: None,
+            function: String::new(),
+            location: String::new(),
+            uuid: Uuid::new_v4(),
+        }
+    }
+
+    // Bridges the numbered terminals first..=last
+    pub fn spanning(
+        component: String,
+        terminal_block: String,
+        first: u32,
+        last: u32,
+        kind: BridgeKind,
+    ) -> Self {
+        let terminals = (first..=last).map(|n| n.to_string()).collect();
+        Self::new(component, terminal_block, terminals, kind)
+    }
+
+    pub fn pole_count(&self) -> usize {
+        self.terminals.len()
+    }
+
+    pub fn terminal_addresses(&self) -> Vec<TerminalAddress> {
+        self.terminals
+            .iter()
+            .map(|t| {
+                TerminalAddress::new(&self.component, &self.terminal_block, t)
+                    .with_aspects(&self.function, &self.location)
+            })
+            .collect()
+    }
+}
+
+// One line of the bridge order list
+#[derive(Debug, Clone, PartialEq)]
+pub struct BridgeAccessory {
+    pub kind: BridgeKind,
+    pub pole_count: usize,
+    pub part_number: Option<String>,
+    pub quantity: u32,
+}
+
+// Groups bridges by kind, pole count and part number
+pub fn bridge_accessory_report(bridges: &[Bridge]) -> Vec<BridgeAccessory> {
+    let mut groups: BTreeMap<(BridgeKind, usize, Option<String>), u32> = BTreeMap::new();
+    for bridge in bridges {
+        *groups
+            .entry((bridge.kind, bridge.pole_count(), bridge.part_number.clone()))
+            .or_insert(0) += 1;
+    }
+    groups
+        .into_iter()
+        .map(
+            |((kind, pole_count, part_number), quantity)| BridgeAccessory {
+                kind,
+                pole_count,
+                part_number,
+                quantity,
+            },
+        )
+        .collect()
+}
+
+fn default_uuid() -> Uuid {
+    Uuid::new_v4()
+}
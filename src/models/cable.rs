@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::connection::Connection;
+
+// One conductor of a multi-core cable, identified by its color or number ("BN", "1", "GNYE")
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CableCore {
+    pub designation: String,
+    // UUID of the `Connection` this core carries, if it is in use
+    #[serde(default)]
+    pub connection: Option<Uuid>,
+}
+
+impl CableCore {
+    pub fn new(designation: String) -> Self {
+        Self {
+            designation,
+            connection: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Cable {
+    pub tag: String,
+    #[serde(default)]
+    pub cable_type: String,
+    pub core_count: u32,
+    #[serde(default)]
+    pub cores: Vec<CableCore>,
+    #[serde(default = "default_uuid")]
+    pub uuid: Uuid,
+}
+
+impl Cable {
+    // Creates a cable with the given core colors or numbers. Further cores may be defined later
+    // as long as `core_count` is not exceeded.
+    pub fn new(tag: String, cable_type: String, core_count: u32, cores: Vec<String>) -> Self {
+        Self {
+            tag,
+            cable_type,
+            core_count,
+            cores: cores.into_iter().map(CableCore::new).collect(),
+            uuid: Uuid::new_v4(),
+        }
+    }
+
+    // Creates a cable whose cores are numbered 1..=core_count
+    pub fn with_numbered_cores(tag: String, cable_type: String, core_count: u32) -> Self {
+        let cores = (1..=core_count).map(|n| n.to_string()).collect();
+        Self::new(tag, cable_type, core_count, cores)
+    }
+
+    pub fn core(&self, designation: &str) -> Option<&CableCore> {
+        self.cores.iter().find(|c| c.designation == designation)
+    }
+
+    pub fn core_carrying(&self, connection_uuid: Uuid) -> Option<&CableCore> {
+        self.cores
+            .iter()
+            .find(|c| c.connection == Some(connection_uuid))
+    }
+
+    // Pairs each assigned core with its connection. Cores whose connection no longer exists are
+    // skipped.
+    pub fn assigned_cores<'a>(
+        &'a self,
+        connections: &'a [Connection],
+    ) -> Vec<(&'a CableCore, &'a Connection)> {
+        self.cores
+            .iter()
+            .filter_map(|core| {
+                let uuid = core.connection?;
+                connections
+                    .iter()
+                    .find(|c| c.uuid == uuid)
+                    .map(|connection| (core, connection))
+            })
+            .collect()
+    }
+
+    // Components reached by the cable at its source and destination end
+    pub fn end_components(&self, connections: &[Connection]) -> (Vec<String>, Vec<String>) {
+        let mut source_end: Vec<String> = Vec::new();
+        let mut destination_end: Vec<String> = Vec::new();
+        for (_, connection) in self.assigned_cores(connections) {
+            if !source_end.contains(&connection.src_component) {
+                source_end.push(connection.src_component.clone());
+            }
+            if !destination_end.contains(&connection.dst_component) {
+                destination_end.push(connection.dst_component.clone());
+            }
+        }
+        (source_end, destination_end)
+    }
+}
+
+fn default_uuid() -> Uuid {
+    Uuid::new_v4()
+}
@@ -0,0 +1,325 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::info;
+
+use crate::models::terminal::TerminalAddress;
+
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Invalid terminal specification: {0}")]
+    InvalidTerminalSpec(String),
+    #[error("Unsupported catalog file format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Unknown catalog validation policy: {0}")]
+    InvalidPolicy(String),
+}
+
+// A connection endpoint that does not match the catalog
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum CatalogViolation {
+    #[error("Unknown component {0}")]
+    UnknownComponent(String),
+    #[error("Component {component} has no terminal block {terminal_block}")]
+    UnknownTerminalBlock {
+        component: String,
+        terminal_block: String,
+    },
+    // Displayed like `TerminalAddress`, so an empty terminal block is left out
+    #[error("Terminal {0} does not exist")]
+    UnknownTerminal(TerminalAddress),
+}
+
+// What to do with connections to terminals missing from the catalog
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CatalogPolicy {
+    #[default]
+    Warn,
+    Reject,
+}
+
+impl FromStr for CatalogPolicy {
+    type Err = CatalogError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "warn" => Ok(CatalogPolicy::Warn),
+            "reject" => Ok(CatalogPolicy::Reject),
+            other => Err(CatalogError::InvalidPolicy(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalPattern {
+    // Inclusive numeric range, e.g. 1..=24
+    Range(u32, u32),
+    // Named pin, e.g. A1, 13, PE
+    Named(String),
+}
+
+impl TerminalPattern {
+    fn matches(&self, terminal: &str) -> bool {
+        match self {
+            TerminalPattern::Range(start, end) => terminal
+                .parse::<u32>()
+                .map(|n| (*start..=*end).contains(&n))
+                .unwrap_or(false),
+            TerminalPattern::Named(name) => name == terminal,
+        }
+    }
+}
+
+impl fmt::Display for TerminalPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TerminalPattern::Range(start, end) => write!(f, "{}..={}", start, end),
+            TerminalPattern::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// The allowed terminals of a terminal block, written as `1..=24`, `1-24` or `A1/A2/13/14`.
+// Ranges and names can be mixed: `1..=12/PE`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(try_from = "TerminalSpecRepr", into = "String")]
+pub struct TerminalSpec(pub Vec<TerminalPattern>);
+
+impl TerminalSpec {
+    pub fn allows(&self, terminal: &str) -> bool {
+        self.0.iter().any(|pattern| pattern.matches(terminal))
+    }
+
+    // Every terminal name, with ranges written out
+    pub fn terminals(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|pattern| match pattern {
+                TerminalPattern::Range(start, end) => {
+                    (*start..=*end).map(|n| n.to_string()).collect()
+                }
+                TerminalPattern::Named(name) => vec![name.clone()],
+            })
+            .collect()
+    }
+}
+
+impl FromStr for TerminalSpec {
+    type Err = CatalogError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut patterns = Vec::new();
+        for token in s.split(['/', ',', ';']).map(str::trim) {
+            if token.is_empty() {
+                continue;
+            }
+            let pattern = if let Some((start, end)) = token.split_once("..=") {
+                match (start.trim().parse(), end.trim().parse()) {
+                    (Ok(start), Ok(end)) if start <= end => TerminalPattern::Range(start, end),
+                    _ => return Err(CatalogError::InvalidTerminalSpec(token.to_string())),
+                }
+            } else {
+                // "1-24" is a range, while a pin such as "L1-1" is kept as a name
+                match token.split_once('-').map(|(a, b)| (a.parse(), b.parse())) {
+                    Some((Ok(start), Ok(end))) if start <= end => {
+                        TerminalPattern::Range(start, end)
+                    }
+                    _ => TerminalPattern::Named(token.to_string()),
+                }
+            };
+            patterns.push(pattern);
+        }
+        Ok(TerminalSpec(patterns))
+    }
+}
+
+impl From<TerminalSpec> for String {
+    fn from(value: TerminalSpec) -> Self {
+        value
+            .0
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+// Terminal lists may be given as a single spec string or as a JSON array of names/ranges
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TerminalSpecRepr {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl TryFrom<TerminalSpecRepr> for TerminalSpec {
+    type Error = CatalogError;
+
+    fn try_from(value: TerminalSpecRepr) -> Result<Self, Self::Error> {
+        match value {
+            TerminalSpecRepr::Text(text) => text.parse(),
+            TerminalSpecRepr::List(items) => items.join("/").parse(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerminalBlockDefinition {
+    // Empty for the terminals that sit directly on the device (e.g. a contactor's A1/A2)
+    #[serde(default)]
+    pub name: String,
+    pub terminals: TerminalSpec,
+    // How many conductors one terminal of this block accepts
+    #[serde(default)]
+    pub max_wires_per_terminal: Option<u32>,
+    // Pairs of terminals of this block that are linked inside the device
+    #[serde(default)]
+    pub internal_jumpers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub terminal_blocks: Vec<TerminalBlockDefinition>,
+}
+
+impl ComponentDefinition {
+    pub fn terminal_block(&self, name: &str) -> Option<&TerminalBlockDefinition> {
+        self.terminal_blocks.iter().find(|b| b.name == name)
+    }
+}
+
+// CSV catalogs hold one terminal block per row
+#[derive(Debug, Deserialize)]
+struct CatalogCsvRow {
+    component: String,
+    #[serde(default)]
+    terminal_block: String,
+    terminals: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    max_wires_per_terminal: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ComponentCatalog {
+    #[serde(default)]
+    pub components: Vec<ComponentDefinition>,
+}
+
+impl ComponentCatalog {
+    // Loads a catalog from a `.json` or `.csv` file
+    pub fn load_from_path(path: &Path) -> Result<Self, CatalogError> {
+        info!("Loading component catalog from {}", path.display());
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let contents = fs::read_to_string(path)?;
+        match extension.as_str() {
+            "json" => Ok(serde_json::from_str(&contents)?),
+            "csv" => Self::from_csv_str(&contents),
+            _ => Err(CatalogError::UnsupportedFormat(extension)),
+        }
+    }
+
+    // Expects the header `component,terminal_block,terminals[,description,max_wires_per_terminal]`
+    pub fn from_csv_str(contents: &str) -> Result<Self, CatalogError> {
+        let mut catalog = ComponentCatalog::default();
+        let mut rdr = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(contents.as_bytes());
+        for row in rdr.deserialize() {
+            let row: CatalogCsvRow = row?;
+            let block = TerminalBlockDefinition {
+                name: row.terminal_block,
+                terminals: row.terminals.parse()?,
+                max_wires_per_terminal: row.max_wires_per_terminal,
+                internal_jumpers: Vec::new(),
+            };
+            match catalog
+                .components
+                .iter_mut()
+                .find(|c| c.name == row.component)
+            {
+                Some(component) => component.terminal_blocks.push(block),
+                None => catalog.components.push(ComponentDefinition {
+                    name: row.component,
+                    description: row.description,
+                    terminal_blocks: vec![block],
+                }),
+            }
+        }
+        Ok(catalog)
+    }
+
+    pub fn component(&self, name: &str) -> Option<&ComponentDefinition> {
+        self.components.iter().find(|c| c.name == name)
+    }
+
+    pub fn max_wires_per_terminal(&self, terminal: &TerminalAddress) -> Option<u32> {
+        self.component(&terminal.component)?
+            .terminal_block(&terminal.terminal_block)?
+            .max_wires_per_terminal
+    }
+
+    // Every internal jumper defined in the catalog, as pairs of terminal addresses
+    pub fn internal_jumpers(&self) -> Vec<(TerminalAddress, TerminalAddress)> {
+        let mut jumpers = Vec::new();
+        for component in &self.components {
+            for block in &component.terminal_blocks {
+                for (from, to) in &block.internal_jumpers {
+                    jumpers.push((
+                        TerminalAddress::new(&component.name, &block.name, from),
+                        TerminalAddress::new(&component.name, &block.name, to),
+                    ));
+                }
+            }
+        }
+        jumpers
+    }
+
+    // Checks a single connection endpoint. Endpoints left completely empty are not checked.
+    pub fn check_terminal(
+        &self,
+        component: &str,
+        terminal_block: &str,
+        terminal: &str,
+    ) -> Result<(), CatalogViolation> {
+        if component.is_empty() && terminal_block.is_empty() && terminal.is_empty() {
+            return Ok(());
+        }
+        let definition = self
+            .component(component)
+            .ok_or_else(|| CatalogViolation::UnknownComponent(component.to_string()))?;
+        let block = definition.terminal_block(terminal_block).ok_or_else(|| {
+            CatalogViolation::UnknownTerminalBlock {
+                component: component.to_string(),
+                terminal_block: terminal_block.to_string(),
+            }
+        })?;
+        if block.terminals.allows(terminal) {
+            Ok(())
+        } else {
+            Err(CatalogViolation::UnknownTerminal(TerminalAddress::new(
+                component,
+                terminal_block,
+                terminal,
+            )))
+        }
+    }
+}
@@ -3,6 +3,10 @@ use serde_json::{from_value, Result as JsonResult, Value};
 use tracing::{debug, error};
 use uuid::Uuid;
 
+use crate::models::designation::DesignationFormatter;
+use crate::models::terminal::TerminalAddress;
+use crate::models::wire_attributes::{CrossSection, WireColor};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Connection {
     pub src_component: String,
@@ -13,6 +17,34 @@ pub struct Connection {
     pub dst_terminal: String,
     #[serde(default = "default_uuid")]
     pub uuid: Uuid,
+    // Optional wire attributes
+    #[serde(default)]
+    pub cross_section: Option<CrossSection>,
+    #[serde(default)]
+    pub color: Option<WireColor>,
+    #[serde(default)]
+    pub length_mm: Option<u32>,
+    #[serde(default)]
+    pub wire_type: Option<String>,
+    #[serde(default)]
+    pub signal_name: Option<String>,
+    // IEC 81346 function (=) and location (+) aspects of each end; empty when not used
+    #[serde(default)]
+    pub src_function: String,
+    #[serde(default)]
+    pub src_location: String,
+    #[serde(default)]
+    pub dst_function: String,
+    #[serde(default)]
+    pub dst_location: String,
+    // Wire number printed on the labels, given by the numbering engine or by hand
+    #[serde(default)]
+    pub wire_number: Option<String>,
+    // Schematic page and column the wire is drawn in, used by page-and-column numbering
+    #[serde(default)]
+    pub page: Option<u32>,
+    #[serde(default)]
+    pub column: Option<u32>,
 }
 
 impl Connection {
@@ -32,6 +64,18 @@ impl Connection {
             dst_terminal_block,
             dst_terminal,
             uuid: Uuid::new_v4(),
+            cross_section: None,
+            color: None,
+            length_mm: None,
+            wire_type: None,
+            signal_name: None,
+            src_function: String::new(),
+            src_location: String::new(),
+            dst_function: String::new(),
+            dst_location: String::new(),
+            wire_number: None,
+            page: None,
+            column: None,
         };
         debug!("New connection created: {:?}", conn.uuid);
         conn
@@ -49,33 +93,86 @@ impl Connection {
         attrs.iter().all(|attr| attr.is_empty())
     }
 
-    pub fn to_tuple(&self) -> (String, String) {
+    pub fn source_terminal(&self) -> TerminalAddress {
+        TerminalAddress::new(
+            &self.src_component,
+            &self.src_terminal_block,
+            &self.src_terminal,
+        )
+        .with_aspects(&self.src_function, &self.src_location)
+    }
+
+    pub fn destination_terminal(&self) -> TerminalAddress {
+        TerminalAddress::new(
+            &self.dst_component,
+            &self.dst_terminal_block,
+            &self.dst_terminal,
+        )
+        .with_aspects(&self.dst_function, &self.dst_location)
+    }
+
+    // Sets one end from a full address, including its function and location
+    pub fn set_source_terminal(&mut self, address: TerminalAddress) {
+        self.src_function = address.function;
+        self.src_location = address.location;
+        self.src_component = address.component;
+        self.src_terminal_block = address.terminal_block;
+        self.src_terminal = address.terminal;
+    }
+
+    pub fn set_destination_terminal(&mut self, address: TerminalAddress) {
+        self.dst_function = address.function;
+        self.dst_location = address.location;
+        self.dst_component = address.component;
+        self.dst_terminal_block = address.terminal_block;
+        self.dst_terminal = address.terminal;
+    }
+
+    pub fn in_location(&self, location: &str) -> bool {
+        self.src_location == location || self.dst_location == location
+    }
+
+    pub fn in_function(&self, function: &str) -> bool {
+        self.src_function == function || self.dst_function == function
+    }
+
+    // Source and destination designations written with `formatter`, normally
+    // `ConnectionManager::designation_formatter()`
+    pub fn to_tuple(&self, formatter: &DesignationFormatter) -> (String, String) {
         let tuple = (
-            format!(
-                "{}-{}-{}",
-                self.src_component, self.src_terminal_block, self.src_terminal
-            )
-            .trim_end_matches('-')
-            .to_string(),
-            format!(
-                "{}-{}-{}",
-                self.dst_component, self.dst_terminal_block, self.dst_terminal
-            )
-            .trim_end_matches('-')
-            .to_string(),
+            formatter.format(&self.source_terminal()),
+            formatter.format(&self.destination_terminal()),
         );
         debug!("Connection {} transformed to tuple: {:?}", self.uuid, tuple);
         tuple
     }
 
-    pub fn to_dict(&self) -> serde_json::Value {
-        serde_json::json!({
-            "src_component": self.src_component,
-            "src_terminal_block": self.src_terminal_block,
-            "src_terminal": self.src_terminal,
-            "dst_component": self.dst_component,
-            "dst_terminal_block": self.dst_terminal_block,
-            "dst_terminal": self.dst_terminal,
+    // Wire attributes in export column order: cross-section, color, length, type, signal name.
+    // Unset attributes become empty strings.
+    pub fn attribute_columns(&self) -> [String; 5] {
+        [
+            self.cross_section
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            self.color
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            self.length_mm.map(|l| l.to_string()).unwrap_or_default(),
+            self.wire_type.clone().unwrap_or_default(),
+            self.signal_name.clone().unwrap_or_default(),
+        ]
+    }
+
+    // Serializes every field (including the UUID) so that `from_json_value` can restore an
+    // identical connection.
+    pub fn to_dict(&self) -> JsonResult<Value> {
+        serde_json::to_value(self).map_err(|e| {
+            error!(
+                "Failed to serialize Connection {} to JSON: {:?}",
+                self.uuid, e
+            );
+            e
         })
     }
 
@@ -89,19 +186,12 @@ impl Connection {
 
 impl PartialEq for Connection {
     fn eq(&self, other: &Self) -> bool {
-        let is_normal_equal = self.src_component == other.src_component
-            && self.src_terminal_block == other.src_terminal_block
-            && self.src_terminal == other.src_terminal
-            && self.dst_component == other.dst_component
-            && self.dst_terminal_block == other.dst_terminal_block
-            && self.dst_terminal == other.dst_terminal;
-
-        let is_reverse_equal = self.src_component == other.dst_component
-            && self.src_terminal_block == other.dst_terminal_block
-            && self.src_terminal == other.dst_terminal
-            && self.dst_component == other.src_component
-            && self.dst_terminal_block == other.src_terminal_block
-            && self.dst_terminal == other.src_terminal;
+        let (source, destination) = (self.source_terminal(), self.destination_terminal());
+        let (other_source, other_destination) =
+            (other.source_terminal(), other.destination_terminal());
+
+        let is_normal_equal = source == other_source && destination == other_destination;
+        let is_reverse_equal = source == other_destination && destination == other_source;
 
         is_normal_equal || is_reverse_equal
     }
@@ -1,13 +1,38 @@
 use crate::config::settings::Settings;
+use crate::models::bridge::{bridge_accessory_report, Bridge, BridgeAccessory};
+use crate::models::cable::{Cable, CableCore};
+use crate::models::catalog::{CatalogPolicy, CatalogViolation, ComponentCatalog};
 use crate::models::connection::Connection;
+use crate::models::connectivity::ConnectivityGraph;
+use crate::models::designation::DesignationFormatter;
+use crate::models::project::{Project, ProjectMetadata};
+use crate::models::reference_designation::{FUNCTION_PREFIX, LOCATION_PREFIX};
+use crate::models::suggestion_index::SuggestionIndex;
+use crate::models::terminal::TerminalAddress;
+use crate::models::terminal_capacity::{
+    TerminalCapacityOverride, TerminalCapacityValidator, TerminalOverload,
+};
+use crate::models::wire_numbering::{WireNumberTemplate, WireNumberingEngine, WireNumberingScheme};
+use crate::utils::csv_exporting_strategy::{
+    export_bridge_report_to_csv, ExportCableToCSVStrategy, ExportFormat, ExportToCsvStrategy,
+    ExportWireToCSVStrategy,
+};
+use crate::utils::csv_importing_strategy::ImportOptions;
+use crate::utils::export_options::{ExportOptions, ExportOptionsError};
 use crate::utils::file_handler::{FileHandler, FileHandlerError};
+use crate::utils::label_exporting_strategy::{label_exporter_by_name, LabelExporter, LabelRecord};
+use crate::utils::pdf_exporting_strategy::{PdfExporter, PdfOptions};
+use crate::utils::timestamp;
+use crate::utils::zpl_exporting_strategy::{ZplExporter, ZplOptions};
 
 use csv::Writer;
 use serde::de::Error;
+use std::collections::BTreeMap;
 use std::io::Cursor;
 use std::path::PathBuf;
 use thiserror::Error;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use uuid::Uuid;
 
 #[derive(Debug, Error)]
 pub enum ConnectionManagerError {
@@ -19,6 +44,26 @@ pub enum ConnectionManagerError {
     DuplicateConnection,
     #[error("Connection not found")]
     ConnectionNotFoundError,
+    #[error("Cable {0} not found")]
+    CableNotFound(String),
+    #[error("Cable {0} already exists")]
+    DuplicateCable(String),
+    #[error("Cable {cable} has no core {core}")]
+    CoreNotFound { cable: String, core: String },
+    #[error("Core {core} of cable {cable} is defined twice")]
+    DuplicateCore { cable: String, core: String },
+    #[error("Core {core} of cable {cable} is already assigned")]
+    CoreAlreadyAssigned { cable: String, core: String },
+    #[error("Cable {cable} only has {core_count} cores")]
+    CoreCountExceeded { cable: String, core_count: u32 },
+    #[error("Connection is already carried by cable {0}")]
+    ConnectionAlreadyCarried(String),
+    #[error("Invalid bridge: {0}")]
+    InvalidBridge(String),
+    #[error("Bridge not found")]
+    BridgeNotFound,
+    #[error("Catalog violation: {0}")]
+    CatalogViolation(#[from] CatalogViolation),
     // Include errors related to file handling
     #[error("File error: {0}")]
     FileOperationError(#[from] std::io::Error),
@@ -29,7 +74,8 @@ pub enum ConnectionManagerError {
     FileHandlerError(#[from] FileHandlerError),
 }
 
-pub trait Observer {
+// `Send` so the manager can be shared as `Arc<Mutex<ConnectionManager>>`
+pub trait Observer: Send {
     fn update_connection_list(&self /* parameters */);
 }
 
@@ -43,6 +89,14 @@ impl Observer for GuiObserver {
 
 pub struct ConnectionManager {
     pub connections: Vec<Connection>,
+    pub metadata: ProjectMetadata,
+    pub cables: Vec<Cable>,
+    pub terminal_capacity_overrides: Vec<TerminalCapacityOverride>,
+    pub bridges: Vec<Bridge>,
+    catalog: Option<ComponentCatalog>,
+    catalog_policy: CatalogPolicy,
+    // Autocomplete names, kept up to date as connections are added and deleted
+    suggestions: SuggestionIndex,
     observers: Vec<Box<dyn Observer>>, // Observers pattern implementation might be different based on your application's architecture
     wire_label_path_name: PathBuf,
     settings: Settings,
@@ -61,8 +115,36 @@ impl ConnectionManager {
 
         let file_handler = FileHandler::new(wire_label_path_name.clone());
 
+        let catalog = match settings.get("catalog_file") {
+            Some(path) if !path.is_empty() => {
+                match ComponentCatalog::load_from_path(&PathBuf::from(path)) {
+                    Ok(catalog) => Some(catalog),
+                    Err(e) => {
+                        error!("Failed to load component catalog from {}: {}", path, e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        let catalog_policy = settings
+            .get("catalog_validation")
+            .unwrap_or_default()
+            .parse()
+            .unwrap_or_else(|e| {
+                warn!("Ignoring catalog validation setting: {}", e);
+                CatalogPolicy::default()
+            });
+
         let mut manager = Self {
             connections: Vec::new(),
+            metadata: ProjectMetadata::default(),
+            cables: Vec::new(),
+            terminal_capacity_overrides: Vec::new(),
+            bridges: Vec::new(),
+            catalog,
+            catalog_policy,
+            suggestions: SuggestionIndex::default(),
             observers: Vec::new(),
             wire_label_path_name: wire_label_path_name.clone().unwrap_or_default(),
             settings,
@@ -73,8 +155,14 @@ impl ConnectionManager {
 
         // Attempt to load connections if a path is provided
         if let Some(path) = wire_label_path_name {
-            match manager.file_handler.load_wires() {
-                Ok(loaded_connections) => manager.connections = loaded_connections,
+            match manager.file_handler.load_project() {
+                Ok(project) => {
+                    manager.connections = project.connections;
+                    manager.metadata = project.metadata;
+                    manager.cables = project.cables;
+                    manager.terminal_capacity_overrides = project.terminal_capacity_overrides;
+                    manager.bridges = project.bridges;
+                }
                 Err(e) => {
                     error!("Failed to load connections from {:?}: {}", path, e);
                     // Consider how you want to handle this error.
@@ -83,6 +171,7 @@ impl ConnectionManager {
                 }
             }
         }
+        manager.rebuild_suggestions();
         Ok(manager)
     }
 
@@ -109,12 +198,14 @@ impl ConnectionManager {
     ) -> Result<(), ConnectionManagerError> {
         // Clear existing connections to repopulate
         self.connections.clear();
+        self.suggestions = SuggestionIndex::new(&[], self.catalog.as_ref());
 
         for conn_dict in conn_dicts {
             match Connection::from_json_value(conn_dict.clone()) {
                 Ok(connection) => {
                     // Check for Duplicate connections before adding
                     if !self.connections.contains(&connection) {
+                        self.suggestions.add_connection(&connection);
                         self.connections.push(connection);
                     } else {
                         return Err(ConnectionManagerError::DuplicateConnection);
@@ -131,6 +222,7 @@ impl ConnectionManager {
         Ok(())
     }
 
+    // Deletes the connection and frees the cable core that carried it
     pub fn delete_connection(
         &mut self,
         connection_to_delete: &Connection,
@@ -140,7 +232,13 @@ impl ConnectionManager {
             .iter()
             .position(|x| x == connection_to_delete)
         {
-            self.connections.remove(pos);
+            let removed = self.connections.remove(pos);
+            self.suggestions.remove_connection(&removed);
+            for core in self.cables.iter_mut().flat_map(|c| c.cores.iter_mut()) {
+                if core.connection == Some(removed.uuid) {
+                    core.connection = None;
+                }
+            }
             self.save_json_to_file()?;
             Ok(())
         } else {
@@ -152,8 +250,8 @@ impl ConnectionManager {
         self,
         connection: &Connection,
     ) -> Result<(String, String), ConnectionManagerError> {
-        if self.connections.contains(&connection) {
-            Ok(connection.to_tuple())
+        if self.connections.contains(connection) {
+            Ok(connection.to_tuple(&self.designation_formatter()))
         } else {
             Err(ConnectionManagerError::ConnectionNotFoundError)
         }
@@ -180,12 +278,628 @@ impl ConnectionManager {
             dst_terminal_block,
             dst_terminal,
         );
+        self.insert_connection(connection)
+    }
+
+    // Adds an already constructed connection (keeping its UUID and wire attributes)
+    pub fn insert_connection(
+        &mut self,
+        connection: Connection,
+    ) -> Result<Connection, ConnectionManagerError> {
+        self.insert_connection_at(self.connections.len(), connection)
+    }
+
+    // Like `insert_connection`, but at `index` in the list, e.g. to put back a deleted connection
+    // where it was. Indices past the end append.
+    pub fn insert_connection_at(
+        &mut self,
+        index: usize,
+        connection: Connection,
+    ) -> Result<Connection, ConnectionManagerError> {
         if self.connections.contains(&connection) {
             return Err(ConnectionManagerError::DuplicateConnection);
         }
-        self.connections.push(connection.clone());
+        self.check_catalog(&connection)?;
+        let index = index.min(self.connections.len());
+        self.suggestions.add_connection(&connection);
+        self.connections.insert(index, connection);
+        if self.auto_wire_numbering() {
+            let graph = self.connectivity_graph();
+            self.wire_numbering()
+                .assign_missing(&mut self.connections, &graph);
+        }
+        self.save_json_to_file()?;
+        Ok(self.connections[index].clone())
+    }
+
+    // Replaces the connection with the same UUID in place and returns the one it replaced. The
+    // new terminals must not duplicate another connection and are checked against the catalog.
+    pub fn replace_connection(
+        &mut self,
+        connection: Connection,
+    ) -> Result<Connection, ConnectionManagerError> {
+        let index = self
+            .connections
+            .iter()
+            .position(|c| c.uuid == connection.uuid)
+            .ok_or(ConnectionManagerError::ConnectionNotFoundError)?;
+        if self
+            .connections
+            .iter()
+            .enumerate()
+            .any(|(i, c)| i != index && *c == connection)
+        {
+            return Err(ConnectionManagerError::DuplicateConnection);
+        }
+        self.check_catalog(&connection)?;
+        self.suggestions.remove_connection(&self.connections[index]);
+        self.suggestions.add_connection(&connection);
+        let replaced = std::mem::replace(&mut self.connections[index], connection);
         self.save_json_to_file()?;
-        Ok(connection)
+        Ok(replaced)
+    }
+
+    pub fn find_connection(&self, uuid: Uuid) -> Option<&Connection> {
+        self.connections.iter().find(|c| c.uuid == uuid)
+    }
+
+    // The project's designation pattern, else the `designation_pattern` setting, else the
+    // default. Patterns may also be preset names such as `iec_81346`; invalid ones are logged
+    // and skipped.
+    pub fn designation_formatter(&self) -> DesignationFormatter {
+        self.configured_designation().unwrap_or_default()
+    }
+
+    // The pattern set for the project or in the settings, if there is a valid one
+    fn configured_designation(&self) -> Option<DesignationFormatter> {
+        let patterns = [
+            self.metadata.designation_pattern.as_str(),
+            self.settings.get("designation_pattern").unwrap_or_default(),
+        ];
+        for pattern in patterns.into_iter().filter(|p| !p.trim().is_empty()) {
+            match pattern.parse::<DesignationFormatter>() {
+                Ok(formatter) => return Some(formatter),
+                Err(e) => warn!("Ignoring designation pattern: {}", e),
+            }
+        }
+        None
+    }
+
+    // CSV layout, labeling mode and wire number display from the export settings
+    pub fn export_options(&self) -> Result<ExportOptions, ExportOptionsError> {
+        ExportOptions::from_settings(&self.settings)
+    }
+
+    // Exporters write source and destination with the configured designation pattern; without
+    // one the wire layout uses the default pattern and the cable layout its `K1-X1 [3]` pattern
+    pub fn export_strategy(
+        &self,
+        format: ExportFormat,
+    ) -> Result<Box<dyn ExportToCsvStrategy>, ExportOptionsError> {
+        Ok(format.strategy(self.export_options()?, self.configured_designation()))
+    }
+
+    pub fn wire_export_strategy(&self) -> Result<ExportWireToCSVStrategy, ExportOptionsError> {
+        Ok(ExportWireToCSVStrategy::new(self.export_options()?)
+            .with_designation(self.designation_formatter()))
+    }
+
+    pub fn cable_export_strategy(&self) -> Result<ExportCableToCSVStrategy, ExportOptionsError> {
+        let strategy = ExportCableToCSVStrategy::new(self.export_options()?);
+        Ok(match self.configured_designation() {
+            Some(designation) => strategy.with_designation(designation),
+            None => strategy,
+        })
+    }
+
+    // Labels for every connection, laid out and numbered as the export settings say
+    pub fn connection_labels(&self) -> Result<Vec<LabelRecord>, ExportOptionsError> {
+        Ok(self
+            .wire_export_strategy()?
+            .label_records(&self.connections))
+    }
+
+    // Reads back what `export_strategy` writes with the current settings
+    pub fn import_options(&self) -> Result<ImportOptions, ExportOptionsError> {
+        Ok(ImportOptions::from_export(
+            &self.export_options()?,
+            self.configured_designation(),
+        ))
+    }
+
+    // `connections.json` → `connections.csv`
+    pub fn default_export_path(&self) -> PathBuf {
+        self.output_file_name.with_extension("csv")
+    }
+
+    pub fn export_to_csv(
+        &self,
+        format: ExportFormat,
+        file_path: PathBuf,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.export_strategy(format)?
+            .export_to_csv(file_path, &self.connections)
+    }
+
+    // Exporter named by the `label_exporter` setting, laying out labels with the configured
+    // labeling mode. The ZPL and PDF exporters also take their label layout from the settings.
+    pub fn label_exporter(&self) -> Result<Box<dyn LabelExporter>, ExportOptionsError> {
+        let name = match self
+            .settings
+            .get("label_exporter")
+            .unwrap_or_default()
+            .trim()
+        {
+            "" => "generic_csv",
+            name => name,
+        };
+        let mut exporter: Box<dyn LabelExporter> = match name.to_lowercase().as_str() {
+            "zpl" => Box::new(self.zpl_exporter()?),
+            "pdf" => Box::new(PdfExporter::new(PdfOptions::from_settings(&self.settings)?)),
+            _ => label_exporter_by_name(name)
+                .ok_or_else(|| ExportOptionsError::InvalidLabelExporter(name.to_string()))?,
+        };
+        exporter.set_labeling_mode(self.export_options()?.labeling_mode);
+        Ok(exporter)
+    }
+
+    pub fn zpl_exporter(&self) -> Result<ZplExporter, ExportOptionsError> {
+        Ok(ZplExporter::new(ZplOptions::from_settings(&self.settings)?))
+    }
+
+    // Sends the wire labels to the ZPL printer at the `zpl_printer_address` setting, whatever
+    // the `label_exporter` setting is, and returns how many labels were sent
+    pub fn print_labels(&self) -> Result<usize, Box<dyn std::error::Error>> {
+        let address = self
+            .settings
+            .get("zpl_printer_address")
+            .unwrap_or_default()
+            .trim();
+        if address.is_empty() {
+            return Err(ExportOptionsError::MissingPrinterAddress.into());
+        }
+        let exporter = self.zpl_exporter()?;
+        let labels = exporter.connection_labels(&self.wire_export_strategy()?, &self.connections);
+        exporter.send_to_printer(address, &labels)?;
+        Ok(labels.len())
+    }
+
+    // Writes the wire labels with the configured label exporter and returns the path written,
+    // which carries the exporter's file extension
+    pub fn export_labels(&self, file_path: PathBuf) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        self.export_labels_with(self.label_exporter()?.as_ref(), file_path)
+    }
+
+    // Same as `export_labels`, laid out with `exporter`'s labeling mode
+    pub fn export_labels_with(
+        &self,
+        exporter: &dyn LabelExporter,
+        file_path: PathBuf,
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let labels = exporter.connection_labels(&self.wire_export_strategy()?, &self.connections);
+        let file_path = file_path.with_extension(exporter.extension());
+        exporter.export_labels(file_path.clone(), &labels)?;
+        Ok(file_path)
+    }
+
+    // Engine configured by the `wire_numbering_scheme` and `wire_number_template` settings;
+    // invalid settings fall back to plain sequential numbers
+    pub fn wire_numbering(&self) -> WireNumberingEngine {
+        let setting = |key: &str| self.settings.get(key).unwrap_or_default().trim();
+        let scheme = match setting("wire_numbering_scheme") {
+            "" => WireNumberingScheme::default(),
+            value => value.parse().unwrap_or_else(|e| {
+                warn!("Ignoring wire numbering scheme: {}", e);
+                WireNumberingScheme::default()
+            }),
+        };
+        let template = match setting("wire_number_template") {
+            "" => WireNumberTemplate::default(),
+            value => WireNumberTemplate::new(value).unwrap_or_else(|e| {
+                warn!("Ignoring wire number template: {}", e);
+                WireNumberTemplate::default()
+            }),
+        };
+        WireNumberingEngine::new(scheme, template)
+    }
+
+    pub fn auto_wire_numbering(&self) -> bool {
+        self.settings
+            .get("auto_wire_numbering")
+            .unwrap_or_default()
+            .trim()
+            == "true"
+    }
+
+    // Numbers every wire again from the start, e.g. after the schematic was reorganized
+    pub fn renumber_wires(&mut self) -> Result<(), ConnectionManagerError> {
+        let graph = self.connectivity_graph();
+        self.wire_numbering()
+            .renumber(&mut self.connections, &graph);
+        self.save_json_to_file()
+    }
+
+    // Numbers the wires that have none, leaving existing numbers as they are
+    pub fn number_new_wires(&mut self) -> Result<(), ConnectionManagerError> {
+        let graph = self.connectivity_graph();
+        self.wire_numbering()
+            .assign_missing(&mut self.connections, &graph);
+        self.save_json_to_file()
+    }
+
+    // Sets the wire number of each connection listed by UUID and saves once, e.g. to undo a
+    // renumbering
+    pub fn set_wire_numbers(
+        &mut self,
+        numbers: &[(Uuid, Option<String>)],
+    ) -> Result<(), ConnectionManagerError> {
+        for (uuid, number) in numbers {
+            let connection = self
+                .connections
+                .iter_mut()
+                .find(|c| c.uuid == *uuid)
+                .ok_or(ConnectionManagerError::ConnectionNotFoundError)?;
+            connection.wire_number = number.clone();
+        }
+        self.save_json_to_file()
+    }
+
+    pub fn get_settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn suggestions(&self) -> &SuggestionIndex {
+        &self.suggestions
+    }
+
+    // Builds the suggestion index again, for changes made to `connections` directly
+    pub fn rebuild_suggestions(&mut self) {
+        self.suggestions = SuggestionIndex::new(&self.connections, self.catalog.as_ref());
+    }
+
+    pub fn get_catalog(&self) -> Option<&ComponentCatalog> {
+        self.catalog.as_ref()
+    }
+
+    pub fn get_catalog_policy(&self) -> CatalogPolicy {
+        self.catalog_policy
+    }
+
+    pub fn set_catalog(&mut self, catalog: Option<ComponentCatalog>, policy: CatalogPolicy) {
+        self.catalog = catalog;
+        self.catalog_policy = policy;
+        self.rebuild_suggestions();
+    }
+
+    // Endpoints of the connection that do not exist in the catalog. Always empty when no catalog
+    // is loaded.
+    pub fn catalog_violations(&self, connection: &Connection) -> Vec<CatalogViolation> {
+        let Some(catalog) = &self.catalog else {
+            return Vec::new();
+        };
+        [
+            catalog.check_terminal(
+                &connection.src_component,
+                &connection.src_terminal_block,
+                &connection.src_terminal,
+            ),
+            catalog.check_terminal(
+                &connection.dst_component,
+                &connection.dst_terminal_block,
+                &connection.dst_terminal,
+            ),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect()
+    }
+
+    // Rejects the connection or only logs the violations, depending on the catalog policy
+    pub fn check_catalog(&self, connection: &Connection) -> Result<(), ConnectionManagerError> {
+        for violation in self.catalog_violations(connection) {
+            match self.catalog_policy {
+                CatalogPolicy::Reject => return Err(violation.into()),
+                CatalogPolicy::Warn => warn!("{}", violation),
+            }
+        }
+        Ok(())
+    }
+
+    // Sets (or with `None` clears) the capacity override of a single terminal
+    pub fn set_terminal_capacity(
+        &mut self,
+        terminal: TerminalAddress,
+        max_wires: Option<u32>,
+    ) -> Result<(), ConnectionManagerError> {
+        self.terminal_capacity_overrides
+            .retain(|o| o.terminal != terminal);
+        if let Some(max_wires) = max_wires {
+            self.terminal_capacity_overrides
+                .push(TerminalCapacityOverride {
+                    terminal,
+                    max_wires,
+                });
+        }
+        self.save_json_to_file()
+    }
+
+    // Terminals carrying more wires than their capacity allows
+    pub fn terminal_overloads(&self) -> Vec<TerminalOverload> {
+        TerminalCapacityValidator::new(self.catalog.as_ref(), &self.terminal_capacity_overrides)
+            .validate(&self.connections)
+    }
+
+    // Graph of all wires and bridges plus the catalog's internal jumpers on terminals that are
+    // in use
+    pub fn connectivity_graph(&self) -> ConnectivityGraph {
+        let mut graph = ConnectivityGraph::from_connections(&self.connections);
+        for bridge in &self.bridges {
+            graph.add_bridge(bridge);
+        }
+        if let Some(catalog) = &self.catalog {
+            // Catalog terminals carry no function or location, so a jumper is added once for
+            // every function/location the device is used in
+            let used = graph.terminals().to_vec();
+            for (from, to) in catalog.internal_jumpers() {
+                let mut aspects: Vec<(&str, &str)> = used
+                    .iter()
+                    .filter(|t| same_terminal(t, &from) || same_terminal(t, &to))
+                    .map(|t| (t.function.as_str(), t.location.as_str()))
+                    .collect();
+                aspects.sort();
+                aspects.dedup();
+                for (function, location) in aspects {
+                    graph.add_jumper(
+                        from.clone().with_aspects(function, location),
+                        to.clone().with_aspects(function, location),
+                    );
+                }
+            }
+        }
+        graph
+    }
+
+    pub fn get_bridges(&self) -> &Vec<Bridge> {
+        &self.bridges
+    }
+
+    // Adds a bridge after checking it joins at least two distinct terminals that exist in the
+    // catalog (subject to the catalog policy)
+    pub fn add_bridge(&mut self, bridge: Bridge) -> Result<(), ConnectionManagerError> {
+        if bridge.pole_count() < 2 {
+            return Err(ConnectionManagerError::InvalidBridge(
+                "a bridge needs at least two terminals".to_string(),
+            ));
+        }
+        for (index, terminal) in bridge.terminals.iter().enumerate() {
+            if bridge.terminals[..index].contains(terminal) {
+                return Err(ConnectionManagerError::InvalidBridge(format!(
+                    "terminal {} is bridged twice",
+                    terminal
+                )));
+            }
+        }
+        if let Some(catalog) = &self.catalog {
+            for terminal in &bridge.terminals {
+                if let Err(violation) =
+                    catalog.check_terminal(&bridge.component, &bridge.terminal_block, terminal)
+                {
+                    match self.catalog_policy {
+                        CatalogPolicy::Reject => return Err(violation.into()),
+                        CatalogPolicy::Warn => warn!("{}", violation),
+                    }
+                }
+            }
+        }
+        self.bridges.push(bridge);
+        self.save_json_to_file()?;
+        Ok(())
+    }
+
+    pub fn remove_bridge(&mut self, uuid: Uuid) -> Result<Bridge, ConnectionManagerError> {
+        let index = self
+            .bridges
+            .iter()
+            .position(|b| b.uuid == uuid)
+            .ok_or(ConnectionManagerError::BridgeNotFound)?;
+        let bridge = self.bridges.remove(index);
+        self.save_json_to_file()?;
+        Ok(bridge)
+    }
+
+    // Bridge accessories to order, grouped by kind, pole count and part number
+    pub fn bridge_accessory_report(&self) -> Vec<BridgeAccessory> {
+        bridge_accessory_report(&self.bridges)
+    }
+
+    // Writes a label for each end of every cable followed by one per assigned core, laid out by
+    // the CSV export settings
+    pub fn export_cable_labels(
+        &self,
+        file_path: PathBuf,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.cable_export_strategy()?.export_cable_labels_to_csv(
+            file_path,
+            &self.cables,
+            &self.connections,
+        )
+    }
+
+    // Writes the bridge order list, laid out by the CSV export settings
+    pub fn export_bridge_report(
+        &self,
+        file_path: PathBuf,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        export_bridge_report_to_csv(file_path, &self.bridges, &self.export_options()?)
+    }
+
+    pub fn get_cables(&self) -> &Vec<Cable> {
+        &self.cables
+    }
+
+    // Adds a cable after checking its tag is unique, its cores fit into `core_count` and each of
+    // its connections exists and is not already carried by another core
+    pub fn add_cable(&mut self, cable: Cable) -> Result<(), ConnectionManagerError> {
+        if self.cables.iter().any(|c| c.tag == cable.tag) {
+            return Err(ConnectionManagerError::DuplicateCable(cable.tag));
+        }
+        if cable.cores.len() > cable.core_count as usize {
+            return Err(ConnectionManagerError::CoreCountExceeded {
+                cable: cable.tag,
+                core_count: cable.core_count,
+            });
+        }
+        for (index, core) in cable.cores.iter().enumerate() {
+            if cable.cores[..index]
+                .iter()
+                .any(|c| c.designation == core.designation)
+            {
+                return Err(ConnectionManagerError::DuplicateCore {
+                    cable: cable.tag.clone(),
+                    core: core.designation.clone(),
+                });
+            }
+            if let Some(uuid) = core.connection {
+                if !self.connections.iter().any(|c| c.uuid == uuid) {
+                    return Err(ConnectionManagerError::ConnectionNotFoundError);
+                }
+                if cable.cores[..index]
+                    .iter()
+                    .any(|c| c.connection == Some(uuid))
+                {
+                    return Err(ConnectionManagerError::ConnectionAlreadyCarried(
+                        cable.tag.clone(),
+                    ));
+                }
+                self.check_connection_not_carried(uuid)?;
+            }
+        }
+        self.cables.push(cable);
+        self.save_json_to_file()?;
+        Ok(())
+    }
+
+    pub fn remove_cable(&mut self, tag: &str) -> Result<Cable, ConnectionManagerError> {
+        let index = self
+            .cables
+            .iter()
+            .position(|c| c.tag == tag)
+            .ok_or_else(|| ConnectionManagerError::CableNotFound(tag.to_string()))?;
+        let cable = self.cables.remove(index);
+        self.save_json_to_file()?;
+        Ok(cable)
+    }
+
+    // Routes a connection through a core of a cable. A core that is not defined yet is added as
+    // long as the cable's core count allows it.
+    pub fn assign_core(
+        &mut self,
+        cable_tag: &str,
+        core_designation: &str,
+        connection_uuid: Uuid,
+    ) -> Result<(), ConnectionManagerError> {
+        if !self.connections.iter().any(|c| c.uuid == connection_uuid) {
+            return Err(ConnectionManagerError::ConnectionNotFoundError);
+        }
+        self.check_connection_not_carried(connection_uuid)?;
+
+        let cable = self
+            .cables
+            .iter_mut()
+            .find(|c| c.tag == cable_tag)
+            .ok_or_else(|| ConnectionManagerError::CableNotFound(cable_tag.to_string()))?;
+
+        let index = match cable
+            .cores
+            .iter()
+            .position(|c| c.designation == core_designation)
+        {
+            Some(index) => index,
+            None => {
+                if cable.cores.len() >= cable.core_count as usize {
+                    return Err(ConnectionManagerError::CoreCountExceeded {
+                        cable: cable.tag.clone(),
+                        core_count: cable.core_count,
+                    });
+                }
+                cable
+                    .cores
+                    .push(CableCore::new(core_designation.to_string()));
+                cable.cores.len() - 1
+            }
+        };
+
+        let core = &mut cable.cores[index];
+        if core.connection.is_some() {
+            return Err(ConnectionManagerError::CoreAlreadyAssigned {
+                cable: cable.tag.clone(),
+                core: core.designation.clone(),
+            });
+        }
+        core.connection = Some(connection_uuid);
+        self.save_json_to_file()?;
+        Ok(())
+    }
+
+    pub fn unassign_core(
+        &mut self,
+        cable_tag: &str,
+        core_designation: &str,
+    ) -> Result<(), ConnectionManagerError> {
+        let cable = self
+            .cables
+            .iter_mut()
+            .find(|c| c.tag == cable_tag)
+            .ok_or_else(|| ConnectionManagerError::CableNotFound(cable_tag.to_string()))?;
+        let core = cable
+            .cores
+            .iter_mut()
+            .find(|c| c.designation == core_designation)
+            .ok_or_else(|| ConnectionManagerError::CoreNotFound {
+                cable: cable_tag.to_string(),
+                core: core_designation.to_string(),
+            })?;
+        core.connection = None;
+        self.save_json_to_file()?;
+        Ok(())
+    }
+
+    // Tag of the cable and designation of the core carrying the connection, if one does
+    pub fn core_assignment(&self, connection_uuid: Uuid) -> Option<(String, String)> {
+        self.cables.iter().find_map(|cable| {
+            cable
+                .core_carrying(connection_uuid)
+                .map(|core| (cable.tag.clone(), core.designation.clone()))
+        })
+    }
+
+    fn check_connection_not_carried(
+        &self,
+        connection_uuid: Uuid,
+    ) -> Result<(), ConnectionManagerError> {
+        match self
+            .cables
+            .iter()
+            .find(|c| c.core_carrying(connection_uuid).is_some())
+        {
+            Some(cable) => Err(ConnectionManagerError::ConnectionAlreadyCarried(
+                cable.tag.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // Fingerprint of the wires, cables and bridges (FNV-1a over their JSON), e.g.
+    // `84f2c1d09a6b3e57`. The undo history stores it with every step, so edits made outside of
+    // it can be detected
+    pub fn revision(&self) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let contents = serde_json::to_vec(&(&self.connections, &self.cables, &self.bridges));
+        for byte in contents.unwrap_or_default() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("{:016x}", hash)
     }
 
     pub fn generate_csv_string(&self) -> Result<String, csv::Error> {
@@ -194,12 +908,9 @@ impl ConnectionManager {
             wtr.serialize(conn)?;
         }
         wtr.flush()?;
-        let cursor = wtr.into_inner().map_err(|e| {
-            csv::Error::from(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                e.into_error(),
-            ))
-        })?;
+        let cursor = wtr
+            .into_inner()
+            .map_err(|e| csv::Error::from(std::io::Error::other(e.into_error())))?;
         let data = cursor.into_inner();
 
         // Convert Vec<u8> to String, handling Potential UTF-8 conversion errors
@@ -207,33 +918,102 @@ impl ConnectionManager {
             .map_err(|e| csv::Error::from(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
     }
 
-    pub fn save_json_to_file(&self) -> Result<(), ConnectionManagerError> {
-        let data: Vec<_> = self.connections.iter().map(|c| c.to_dict()).collect();
-        let json_data = serde_json::to_value(data)?;
+    pub fn save_json_to_file(&mut self) -> Result<(), ConnectionManagerError> {
+        let today = timestamp::today();
+        if self.metadata.created.is_empty() {
+            self.metadata.created = today.clone();
+        }
+        self.metadata.modified = today;
 
+        let mut project = Project::new(self.metadata.clone(), self.connections.clone());
+        project.cables = self.cables.clone();
+        project.terminal_capacity_overrides = self.terminal_capacity_overrides.clone();
+        project.bridges = self.bridges.clone();
         self.file_handler
-            .save_to_path(&json_data, &self.output_file_name)?;
+            .save_project_to_path(&project, &self.output_file_name)?;
         info!("Saved connections to {}", self.output_file_name.display());
 
         Ok(())
     }
 
+    // Wires with at least one end in `location` (written without the `+`)
+    pub fn connections_in_location(&self, location: &str) -> Vec<&Connection> {
+        self.connections
+            .iter()
+            .filter(|c| c.in_location(location))
+            .collect()
+    }
+
+    // Wires with at least one end in `function` (written without the `=`)
+    pub fn connections_in_function(&self, function: &str) -> Vec<&Connection> {
+        self.connections
+            .iter()
+            .filter(|c| c.in_function(function))
+            .collect()
+    }
+
+    // Wires per location. A wire between two locations is listed under both; wires without a
+    // location are listed under "".
+    pub fn group_by_location(&self) -> BTreeMap<String, Vec<&Connection>> {
+        group_by(&self.connections, |c| [&c.src_location, &c.dst_location])
+    }
+
+    // Wires per function, like `group_by_location`
+    pub fn group_by_function(&self) -> BTreeMap<String, Vec<&Connection>> {
+        group_by(&self.connections, |c| [&c.src_function, &c.dst_function])
+    }
+
+    // Wires for the connection list filter. `+CAB2` or `=PUMP1` keeps the wires in that location
+    // or function under one group; `+` or `=` alone groups every wire by location or function.
+    // Any other filter, including none, lists every wire under "".
+    pub fn filter_connections(&self, filter: &str) -> BTreeMap<String, Vec<&Connection>> {
+        let filter = filter.trim();
+        let mut chars = filter.chars();
+        match (chars.next(), chars.as_str().trim()) {
+            (Some(LOCATION_PREFIX), "") => self.group_by_location(),
+            (Some(FUNCTION_PREFIX), "") => self.group_by_function(),
+            (Some(LOCATION_PREFIX), location) => {
+                BTreeMap::from([(location.to_string(), self.connections_in_location(location))])
+            }
+            (Some(FUNCTION_PREFIX), function) => {
+                BTreeMap::from([(function.to_string(), self.connections_in_function(function))])
+            }
+            _ => BTreeMap::from([(String::new(), self.connections.iter().collect())]),
+        }
+    }
+
     pub fn print_connections(&self) {
         if self.connections.is_empty() {
             info!("No connections available.");
         } else {
+            let formatter = self.designation_formatter();
             for (index, connection) in self.connections.iter().enumerate() {
-                info!(
-                    "{}: {}-{}-{} | {}-{}-{}",
-                    index + 1,
-                    connection.src_component,
-                    connection.src_terminal_block,
-                    connection.src_terminal,
-                    connection.dst_component,
-                    connection.dst_terminal_block,
-                    connection.dst_terminal,
-                );
+                let (source, destination) = connection.to_tuple(&formatter);
+                info!("{}: {} | {}", index + 1, source, destination);
             }
         }
     }
 }
+
+// Same component, terminal block and terminal, whatever the function and location
+fn same_terminal(a: &TerminalAddress, b: &TerminalAddress) -> bool {
+    a.component == b.component && a.terminal_block == b.terminal_block && a.terminal == b.terminal
+}
+
+fn group_by<'a>(
+    connections: &'a [Connection],
+    keys: impl Fn(&'a Connection) -> [&'a String; 2],
+) -> BTreeMap<String, Vec<&'a Connection>> {
+    let mut groups: BTreeMap<String, Vec<&Connection>> = BTreeMap::new();
+    for connection in connections {
+        let [source, destination] = keys(connection);
+        groups.entry(source.clone()).or_default().push(connection);
+        if destination != source {
+            groups
+                .entry(destination.clone())
+                .or_default()
+                .push(connection);
+        }
+    }
+    groups
+}
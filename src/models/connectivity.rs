@@ -0,0 +1,216 @@
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use crate::models::bridge::Bridge;
+use crate::models::connection::Connection;
+use crate::models::terminal::TerminalAddress;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeKind {
+    // A wire, identified by the UUID of its `Connection`
+    Wire(Uuid),
+    // An electrical link that is not a wire, e.g. a jumper inside a device
+    Jumper,
+    // A terminal block bridge, identified by the UUID of its `Bridge`
+    Bridge(Uuid),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    pub from: TerminalAddress,
+    pub to: TerminalAddress,
+    pub kind: EdgeKind,
+}
+
+// A set of electrically common terminals (one potential)
+pub type Net = Vec<TerminalAddress>;
+
+// Graph of terminals (nodes) joined by wires, jumpers and bridges (edges), built over the flat
+// connection list
+#[derive(Debug, Default)]
+pub struct ConnectivityGraph {
+    terminals: Vec<TerminalAddress>,
+    index: HashMap<TerminalAddress, usize>,
+    edges: Vec<(usize, usize, EdgeKind)>,
+}
+
+impl ConnectivityGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_connections(connections: &[Connection]) -> Self {
+        let mut graph = Self::new();
+        for connection in connections {
+            graph.add_wire(connection);
+        }
+        graph
+    }
+
+    pub fn add_wire(&mut self, connection: &Connection) {
+        let from = connection.source_terminal();
+        let to = connection.destination_terminal();
+        if from.is_empty() || to.is_empty() {
+            return;
+        }
+        self.add_edge(from, to, EdgeKind::Wire(connection.uuid));
+    }
+
+    pub fn add_jumper(&mut self, from: TerminalAddress, to: TerminalAddress) {
+        self.add_edge(from, to, EdgeKind::Jumper);
+    }
+
+    // Links each bridged terminal to the next one on the strip
+    pub fn add_bridge(&mut self, bridge: &Bridge) {
+        let terminals = bridge.terminal_addresses();
+        for pair in terminals.windows(2) {
+            self.add_edge(
+                pair[0].clone(),
+                pair[1].clone(),
+                EdgeKind::Bridge(bridge.uuid),
+            );
+        }
+    }
+
+    pub fn contains(&self, terminal: &TerminalAddress) -> bool {
+        self.index.contains_key(terminal)
+    }
+
+    pub fn terminals(&self) -> &[TerminalAddress] {
+        &self.terminals
+    }
+
+    fn add_edge(&mut self, from: TerminalAddress, to: TerminalAddress, kind: EdgeKind) {
+        let a = self.node(from);
+        let b = self.node(to);
+        self.edges.push((a, b, kind));
+    }
+
+    fn node(&mut self, terminal: TerminalAddress) -> usize {
+        if let Some(&i) = self.index.get(&terminal) {
+            return i;
+        }
+        self.terminals.push(terminal.clone());
+        self.index.insert(terminal, self.terminals.len() - 1);
+        self.terminals.len() - 1
+    }
+
+    // Number of edges of every node, as (all edges, wires only)
+    fn degrees(&self) -> Vec<(usize, usize)> {
+        let mut degrees = vec![(0, 0); self.terminals.len()];
+        for &(a, b, kind) in &self.edges {
+            let wire = matches!(kind, EdgeKind::Wire(_)) as usize;
+            for node in [a, b] {
+                degrees[node].0 += 1;
+                degrees[node].1 += wire;
+            }
+        }
+        degrees
+    }
+
+    // Union-find over all edges; returns the root of every node
+    fn roots(&self) -> Vec<usize> {
+        let mut parent: Vec<usize> = (0..self.terminals.len()).collect();
+        for &(a, b, _) in &self.edges {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            if ra != rb {
+                parent[ra] = rb;
+            }
+        }
+        (0..parent.len()).map(|i| find(&mut parent, i)).collect()
+    }
+
+    // All nets, each sorted, ordered by their first terminal
+    pub fn nets(&self) -> Vec<Net> {
+        let mut groups: HashMap<usize, BTreeSet<TerminalAddress>> = HashMap::new();
+        for (node, root) in self.roots().into_iter().enumerate() {
+            groups
+                .entry(root)
+                .or_default()
+                .insert(self.terminals[node].clone());
+        }
+        let mut nets: Vec<Net> = groups
+            .into_values()
+            .map(|set| set.into_iter().collect())
+            .collect();
+        nets.sort();
+        nets
+    }
+
+    pub fn net_of(&self, terminal: &TerminalAddress) -> Option<Net> {
+        let node = *self.index.get(terminal)?;
+        let roots = self.roots();
+        let mut net: Net = (0..self.terminals.len())
+            .filter(|&i| roots[i] == roots[node])
+            .map(|i| self.terminals[i].clone())
+            .collect();
+        net.sort();
+        Some(net)
+    }
+
+    // Everything transitively connected to `terminal`, excluding the terminal itself
+    pub fn connected_to(&self, terminal: &TerminalAddress) -> Vec<TerminalAddress> {
+        self.net_of(terminal)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|t| t != terminal)
+            .collect()
+    }
+
+    // Terminals where a wire ends without going on: exactly one wire and no jumper or bridge.
+    // Terminals that only jumpers or bridges reach, such as an unused jumpered contact or the
+    // last terminal of a bridge, are not open ends.
+    pub fn open_ends(&self) -> Vec<TerminalAddress> {
+        let mut open: Vec<TerminalAddress> = self
+            .degrees()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, degree)| degree == (1, 1))
+            .map(|(node, _)| self.terminals[node].clone())
+            .collect();
+        open.sort();
+        open
+    }
+
+    // Edges that close a loop: both ends were already connected through other edges. This
+    // includes parallel wires between the same two terminals.
+    pub fn loops(&self) -> Vec<GraphEdge> {
+        let mut parent: Vec<usize> = (0..self.terminals.len()).collect();
+        let mut closing = Vec::new();
+        for &(a, b, kind) in &self.edges {
+            let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+            if ra == rb {
+                closing.push(GraphEdge {
+                    from: self.terminals[a].clone(),
+                    to: self.terminals[b].clone(),
+                    kind,
+                });
+            } else {
+                parent[ra] = rb;
+            }
+        }
+        closing
+    }
+
+    // Nets made of a single wire whose two ends are connected to nothing else
+    pub fn islands(&self) -> Vec<Net> {
+        let degrees = self.degrees();
+        self.nets()
+            .into_iter()
+            .filter(|net| {
+                net.len() == 2
+                    && net
+                        .iter()
+                        .all(|terminal| degrees[self.index[terminal]] == (1, 1))
+            })
+            .collect()
+    }
+}
+
+fn find(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
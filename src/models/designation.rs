@@ -0,0 +1,345 @@
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::models::terminal::TerminalAddress;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DesignationError {
+    #[error("Unknown placeholder {{{0}}}")]
+    UnknownPlaceholder(String),
+    #[error("Unclosed '{{' in designation pattern {0}")]
+    UnclosedBrace(String),
+    #[error("Designation pattern {0} has no placeholder")]
+    NoPlaceholder(String),
+    #[error("{text} does not match the designation pattern {pattern}")]
+    NoMatch { pattern: String, text: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DesignationPart {
+    Function,
+    Location,
+    Component,
+    TerminalBlock,
+    Terminal,
+}
+
+impl DesignationPart {
+    // `terminal_block` before `terminal`, which is a prefix of it
+    const NAMES: [(&'static str, DesignationPart); 5] = [
+        ("function", DesignationPart::Function),
+        ("location", DesignationPart::Location),
+        ("terminal_block", DesignationPart::TerminalBlock),
+        ("component", DesignationPart::Component),
+        ("terminal", DesignationPart::Terminal),
+    ];
+
+    fn value_mut<'a>(&self, address: &'a mut TerminalAddress) -> &'a mut String {
+        match self {
+            DesignationPart::Function => &mut address.function,
+            DesignationPart::Location => &mut address.location,
+            DesignationPart::Component => &mut address.component,
+            DesignationPart::TerminalBlock => &mut address.terminal_block,
+            DesignationPart::Terminal => &mut address.terminal,
+        }
+    }
+
+    fn value<'a>(&self, address: &'a TerminalAddress) -> &'a str {
+        match self {
+            DesignationPart::Function => &address.function,
+            DesignationPart::Location => &address.location,
+            DesignationPart::Component => &address.component,
+            DesignationPart::TerminalBlock => &address.terminal_block,
+            DesignationPart::Terminal => &address.terminal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Part {
+        part: DesignationPart,
+        prefix: String,
+        suffix: String,
+    },
+}
+
+// Turns terminal addresses into designation text following a pattern such as
+// `{component}-{terminal_block}-{terminal}`, `{=component}{+terminal_block}{:terminal}` or
+// `{terminal_block}.{terminal}`. `{function}` and `{location}` give the IEC 81346 aspects.
+//
+// Text outside braces is a separator: it belongs to the part right after it and is only written
+// when that part and an earlier one are non-empty. Text starting or ending the pattern is
+// written whenever any part is non-empty. Text inside braces sticks to its part and is written
+// whenever that part is non-empty, which is how IEC prefixes like `=`, `+` and `:` are written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignationFormatter {
+    pattern: String,
+    segments: Vec<Segment>,
+}
+
+impl DesignationFormatter {
+    pub const DEFAULT_PATTERN: &'static str = "{component}-{terminal_block}-{terminal}";
+    // `K1-X1 [3]`, the layout of `ExportCableToCSVStrategy`
+    pub const CABLE_PATTERN: &'static str = "{component}-{terminal_block}{ [terminal]}";
+    // Full IEC 81346 reference designation, `=PUMP1+CAB2-K1-X1:A1`
+    pub const IEC_81346_PATTERN: &'static str =
+        "{=function}{+location}{-component}{-terminal_block}{:terminal}";
+
+    // Names that can be used instead of a pattern, e.g. in the `designation_pattern` setting
+    pub const PRESETS: [(&'static str, &'static str); 3] = [
+        ("default", Self::DEFAULT_PATTERN),
+        ("cable", Self::CABLE_PATTERN),
+        ("iec_81346", Self::IEC_81346_PATTERN),
+    ];
+
+    pub fn new(pattern: &str) -> Result<Self, DesignationError> {
+        let mut segments = Vec::new();
+        let mut rest = pattern;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                segments.push(Segment::Literal(rest[..open].to_string()));
+            }
+            let close = rest[open..]
+                .find('}')
+                .map(|i| open + i)
+                .ok_or_else(|| DesignationError::UnclosedBrace(pattern.to_string()))?;
+            let inner = &rest[open + 1..close];
+            let (name, part) = DesignationPart::NAMES
+                .iter()
+                .find(|(name, _)| inner.contains(name))
+                .ok_or_else(|| DesignationError::UnknownPlaceholder(inner.to_string()))?;
+            let start = inner.find(name).unwrap_or_default();
+            segments.push(Segment::Part {
+                part: *part,
+                prefix: inner[..start].to_string(),
+                suffix: inner[start + name.len()..].to_string(),
+            });
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        if !segments.iter().any(|s| matches!(s, Segment::Part { .. })) {
+            return Err(DesignationError::NoPlaceholder(pattern.to_string()));
+        }
+        Ok(Self {
+            pattern: pattern.to_string(),
+            segments,
+        })
+    }
+
+    pub fn cable() -> Self {
+        Self::new(Self::CABLE_PATTERN).expect("built-in cable pattern is valid")
+    }
+
+    pub fn iec_81346() -> Self {
+        Self::new(Self::IEC_81346_PATTERN).expect("built-in IEC 81346 pattern is valid")
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    // Whether `c` is written by the pattern itself, as a separator or as a part's prefix or
+    // suffix. `:` is for `{=component}{+terminal_block}{:terminal}`, not for the default pattern.
+    pub fn uses(&self, c: char) -> bool {
+        self.segments.iter().any(|segment| match segment {
+            Segment::Literal(text) => text.contains(c),
+            Segment::Part { prefix, suffix, .. } => prefix.contains(c) || suffix.contains(c),
+        })
+    }
+
+    pub fn format(&self, address: &TerminalAddress) -> String {
+        let first_part = self
+            .segments
+            .iter()
+            .position(|s| matches!(s, Segment::Part { .. }))
+            .unwrap_or_default();
+        let last_part = self
+            .segments
+            .iter()
+            .rposition(|s| matches!(s, Segment::Part { .. }))
+            .unwrap_or_default();
+
+        let mut body = String::new();
+        let mut pending_separator: Option<&str> = None;
+        for segment in &self.segments[first_part..=last_part] {
+            match segment {
+                Segment::Literal(text) => {
+                    if !body.is_empty() {
+                        pending_separator = Some(text);
+                    }
+                }
+                Segment::Part {
+                    part,
+                    prefix,
+                    suffix,
+                } => {
+                    let value = part.value(address);
+                    if value.is_empty() {
+                        pending_separator = None;
+                        continue;
+                    }
+                    if let Some(separator) = pending_separator.take() {
+                        body.push_str(separator);
+                    }
+                    body.push_str(prefix);
+                    body.push_str(value);
+                    body.push_str(suffix);
+                }
+            }
+        }
+        if body.is_empty() {
+            return body;
+        }
+
+        let literal = |segments: &[Segment]| -> String {
+            segments
+                .iter()
+                .filter_map(|s| match s {
+                    Segment::Literal(text) => Some(text.as_str()),
+                    Segment::Part { .. } => None,
+                })
+                .collect()
+        };
+        format!(
+            "{}{}{}",
+            literal(&self.segments[..first_part]),
+            body,
+            literal(&self.segments[last_part + 1..])
+        )
+    }
+
+    // The inverse of `format`. Parts missing from `text` are left empty; when that is
+    // ambiguous, the first and last parts are kept, so `K1-A1` with the default pattern is
+    // component `K1` and terminal `A1`.
+    pub fn parse(&self, text: &str) -> Result<TerminalAddress, DesignationError> {
+        let text = text.trim();
+        let parts: Vec<usize> = self
+            .segments
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| matches!(s, Segment::Part { .. }))
+            .map(|(i, _)| i)
+            .collect();
+
+        // Every combination of present parts, most parts first, then those keeping the first
+        // and the last part
+        let mut masks: Vec<u32> = (1..1u32 << parts.len()).collect();
+        let last = 1 << (parts.len() - 1);
+        masks.sort_by_key(|mask| {
+            (
+                std::cmp::Reverse(mask.count_ones()),
+                mask & 1 == 0,
+                mask & last == 0,
+            )
+        });
+        masks
+            .into_iter()
+            .find_map(|mask| {
+                let present: Vec<usize> = parts
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| mask & (1 << bit) != 0)
+                    .map(|(_, &i)| i)
+                    .collect();
+                let values = match_tokens(text, &self.tokens(&present))?;
+                let mut address = TerminalAddress::new("", "", "");
+                for (&i, value) in present.iter().zip(values) {
+                    if let Segment::Part { part, .. } = &self.segments[i] {
+                        *part.value_mut(&mut address) = value.to_string();
+                    }
+                }
+                Some(address)
+            })
+            .ok_or_else(|| DesignationError::NoMatch {
+                pattern: self.pattern.clone(),
+                text: text.to_string(),
+            })
+    }
+
+    // What `format` writes when only the parts at the segment indices `present` are non-empty,
+    // with `None` standing for a part's value
+    fn tokens(&self, present: &[usize]) -> Vec<Option<String>> {
+        let mut tokens = Vec::new();
+        let mut fixed = String::new();
+        let first = present.first().copied().unwrap_or_default();
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                // Leading and trailing text, and the separator right before a later part
+                Segment::Literal(text) => {
+                    let leading = !self.segments[..i]
+                        .iter()
+                        .any(|s| matches!(s, Segment::Part { .. }));
+                    let trailing = !self.segments[i + 1..]
+                        .iter()
+                        .any(|s| matches!(s, Segment::Part { .. }));
+                    if leading || trailing || (present.contains(&(i + 1)) && i + 1 != first) {
+                        fixed.push_str(text);
+                    }
+                }
+                Segment::Part { prefix, suffix, .. } if present.contains(&i) => {
+                    fixed.push_str(prefix);
+                    tokens.push(Some(std::mem::take(&mut fixed)));
+                    tokens.push(None);
+                    fixed.push_str(suffix);
+                }
+                Segment::Part { .. } => {}
+            }
+        }
+        tokens.push(Some(fixed));
+        tokens
+    }
+
+    pub fn format_parts(&self, component: &str, terminal_block: &str, terminal: &str) -> String {
+        self.format(&TerminalAddress::new(component, terminal_block, terminal))
+    }
+}
+
+// Matches `text` against fixed strings (`Some`) and non-empty values (`None`), trying the
+// shortest value first. Returns the values in order.
+fn match_tokens<'a>(text: &'a str, tokens: &[Option<String>]) -> Option<Vec<&'a str>> {
+    match tokens.split_first() {
+        None => text.is_empty().then(Vec::new),
+        Some((Some(fixed), rest)) => match_tokens(text.strip_prefix(fixed.as_str())?, rest),
+        Some((None, rest)) => text
+            .char_indices()
+            .skip(1)
+            .map(|(end, _)| end)
+            .chain([text.len()])
+            .filter(|&end| end > 0)
+            .find_map(|end| {
+                let mut values = match_tokens(&text[end..], rest)?;
+                values.insert(0, &text[..end]);
+                Some(values)
+            }),
+    }
+}
+
+impl Default for DesignationFormatter {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_PATTERN).expect("built-in default pattern is valid")
+    }
+}
+
+// Accepts a preset name or a pattern
+impl FromStr for DesignationFormatter {
+    type Err = DesignationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        match Self::PRESETS.iter().find(|(preset, _)| *preset == name) {
+            Some((_, pattern)) => Self::new(pattern),
+            None => Self::new(s),
+        }
+    }
+}
+
+impl fmt::Display for DesignationFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
@@ -1,2 +1,14 @@
+pub mod bridge;
+pub mod cable;
+pub mod catalog;
 pub mod connection_manager;
+pub mod connectivity;
 pub mod connection;
+pub mod project;
+pub mod terminal;
+pub mod terminal_capacity;
+pub mod wire_attributes;
+pub mod designation;
+pub mod reference_designation;
+pub mod wire_numbering;
+pub mod suggestion_index;
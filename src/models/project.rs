@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::bridge::Bridge;
+use crate::models::cable::Cable;
+use crate::models::connection::Connection;
+use crate::models::terminal_capacity::TerminalCapacityOverride;
+
+// Version written by this build. Bump it together with a new entry in
+// `utils::migrations::MIGRATIONS` whenever the on-disk layout changes.
+pub const CURRENT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ProjectMetadata {
+    pub job_number: String,
+    pub customer: String,
+    pub cabinet: String,
+    pub author: String,
+    // Dates are stored as `YYYY-MM-DD`
+    pub created: String,
+    pub modified: String,
+    // Overrides the `designation_pattern` setting for this project when not empty
+    pub designation_pattern: String,
+}
+
+// The envelope written to a project file: format version, metadata and the wire list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Project {
+    pub format_version: u32,
+    #[serde(default)]
+    pub metadata: ProjectMetadata,
+    #[serde(default)]
+    pub connections: Vec<Connection>,
+    #[serde(default)]
+    pub cables: Vec<Cable>,
+    #[serde(default)]
+    pub terminal_capacity_overrides: Vec<TerminalCapacityOverride>,
+    #[serde(default)]
+    pub bridges: Vec<Bridge>,
+}
+
+impl Project {
+    pub fn new(metadata: ProjectMetadata, connections: Vec<Connection>) -> Self {
+        Self {
+            format_version: CURRENT_FORMAT_VERSION,
+            metadata,
+            connections,
+            cables: Vec::new(),
+            terminal_capacity_overrides: Vec::new(),
+            bridges: Vec::new(),
+        }
+    }
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self::new(ProjectMetadata::default(), Vec::new())
+    }
+}
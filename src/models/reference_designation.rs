@@ -0,0 +1,112 @@
+use thiserror::Error;
+
+use crate::models::designation::DesignationFormatter;
+use crate::models::terminal::TerminalAddress;
+
+// IEC 81346 aspect prefixes, plus `:` for the terminal
+pub const FUNCTION_PREFIX: char = '=';
+pub const LOCATION_PREFIX: char = '+';
+pub const PRODUCT_PREFIX: char = '-';
+pub const TERMINAL_PREFIX: char = ':';
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ReferenceDesignationError {
+    #[error("Empty reference designation")]
+    Empty,
+    #[error("Empty {aspect} aspect in {designation}")]
+    EmptyAspect {
+        aspect: &'static str,
+        designation: String,
+    },
+    #[error("More than one terminal in {0}")]
+    MultipleTerminals(String),
+}
+
+// Parses a full reference designation such as `=PUMP1+CAB2-K1:A1` or `+CAB2-X1-X2:3`.
+//
+// Repeated function or location levels are kept together (`=PLANT=PUMP1` gives the function
+// `PLANT=PUMP1`). With several product levels the last one is the terminal block and the
+// others form the component: `-A1-K1-X1` gives component `A1-K1` and terminal block `X1`. Text
+// before the first prefix is read as the product aspect, so `K1:A1` is accepted too.
+//
+// Only `:` starts a terminal here, so `K1-A1` is component `K1` with terminal block `A1`. The
+// default designation pattern reads the same text as component `K1` and terminal `A1`
+// (`DesignationFormatter::parse`), so quick entry only hands text containing `=`, `+` or `:` to
+// this parser.
+pub fn parse_reference_designation(
+    designation: &str,
+) -> Result<TerminalAddress, ReferenceDesignationError> {
+    let designation = designation.trim();
+    if designation.is_empty() {
+        return Err(ReferenceDesignationError::Empty);
+    }
+
+    let mut functions = Vec::new();
+    let mut locations = Vec::new();
+    let mut products = Vec::new();
+    let mut terminal: Option<String> = None;
+
+    // (prefix, text) pairs, with `-` assumed for text before the first prefix
+    let mut segments: Vec<(char, String)> = Vec::new();
+    for c in designation.chars() {
+        let in_terminal = segments.last().is_some_and(|(p, _)| *p == TERMINAL_PREFIX);
+        match c {
+            // Everything after `:` belongs to the terminal, e.g. `:L1-1`
+            _ if in_terminal && c != TERMINAL_PREFIX => segments.last_mut().unwrap().1.push(c),
+            FUNCTION_PREFIX | LOCATION_PREFIX | PRODUCT_PREFIX | TERMINAL_PREFIX => {
+                segments.push((c, String::new()))
+            }
+            _ => match segments.last_mut() {
+                Some((_, text)) => text.push(c),
+                None => segments.push((PRODUCT_PREFIX, c.to_string())),
+            },
+        }
+    }
+
+    for (prefix, text) in segments {
+        let text = text.trim().to_string();
+        let aspect = match prefix {
+            FUNCTION_PREFIX => "function",
+            LOCATION_PREFIX => "location",
+            PRODUCT_PREFIX => "product",
+            _ => "terminal",
+        };
+        if text.is_empty() {
+            return Err(ReferenceDesignationError::EmptyAspect {
+                aspect,
+                designation: designation.to_string(),
+            });
+        }
+        match prefix {
+            FUNCTION_PREFIX => functions.push(text),
+            LOCATION_PREFIX => locations.push(text),
+            PRODUCT_PREFIX => products.push(text),
+            _ if terminal.is_some() => {
+                return Err(ReferenceDesignationError::MultipleTerminals(
+                    designation.to_string(),
+                ))
+            }
+            _ => terminal = Some(text),
+        }
+    }
+
+    let terminal_block = if products.len() > 1 {
+        products.pop().unwrap_or_default()
+    } else {
+        String::new()
+    };
+    let component = products.join(&PRODUCT_PREFIX.to_string());
+    Ok(
+        TerminalAddress::new(&component, &terminal_block, &terminal.unwrap_or_default())
+            .with_aspects(
+                &functions.join(&FUNCTION_PREFIX.to_string()),
+                &locations.join(&LOCATION_PREFIX.to_string()),
+            ),
+    )
+}
+
+// The inverse of `parse_reference_designation`: `=PUMP1+CAB2-K1-X1:A1`, leaving out empty
+// aspects
+pub fn format_reference_designation(address: &TerminalAddress) -> String {
+    DesignationFormatter::iec_81346().format(address)
+}
@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::models::catalog::ComponentCatalog;
+use crate::models::connection::Connection;
+use crate::models::terminal::TerminalAddress;
+
+// Names used for one input field with how often they occur in the project. Catalog names are
+// kept even when no connection uses them.
+#[derive(Debug, Clone, Default)]
+struct Names {
+    counts: HashMap<String, usize>,
+    catalog: BTreeSet<String>,
+}
+
+impl Names {
+    fn add(&mut self, name: &str) {
+        if !name.is_empty() {
+            *self.counts.entry(name.to_string()).or_default() += 1;
+        }
+    }
+
+    fn remove(&mut self, name: &str) {
+        if let Some(count) = self.counts.get_mut(name) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(name);
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.counts.is_empty() && self.catalog.is_empty()
+    }
+
+    // Prefix matches first, then substring matches, then fuzzy (subsequence) matches, each
+    // ordered by how often the name is used. Matching ignores case.
+    fn suggest(&self, query: &str, limit: usize) -> Vec<String> {
+        let query = query.trim().to_lowercase();
+        let catalog_only = self
+            .catalog
+            .iter()
+            .filter(|name| !self.counts.contains_key(*name))
+            .map(|name| (name, 0));
+        let mut matches: Vec<(u8, Reverse<usize>, usize, &String)> = self
+            .counts
+            .iter()
+            .map(|(name, count)| (name, *count))
+            .chain(catalog_only)
+            .filter_map(|(name, count)| {
+                let tier = match_tier(&name.to_lowercase(), &query)?;
+                Some((tier, Reverse(count), name.len(), name))
+            })
+            .collect();
+        matches.sort();
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(.., name)| name.clone())
+            .collect()
+    }
+}
+
+fn match_tier(name: &str, query: &str) -> Option<u8> {
+    if name.starts_with(query) {
+        Some(0)
+    } else if name.contains(query) {
+        Some(1)
+    } else {
+        let mut chars = name.chars();
+        query.chars().all(|q| chars.any(|c| c == q)).then_some(2)
+    }
+}
+
+// Ranked suggestions for the component, terminal block and terminal inputs, built from the
+// project's connections and the component catalog. Terminal block suggestions are limited to the
+// blocks used on (or defined for) the chosen component, terminal suggestions to the chosen block.
+#[derive(Debug, Clone, Default)]
+pub struct SuggestionIndex {
+    components: Names,
+    terminal_blocks: Names,
+    terminals: Names,
+    blocks_by_component: HashMap<String, Names>,
+    terminals_by_block: HashMap<(String, String), Names>,
+}
+
+impl SuggestionIndex {
+    pub fn new(connections: &[Connection], catalog: Option<&ComponentCatalog>) -> Self {
+        let mut index = Self::default();
+        if let Some(catalog) = catalog {
+            index.add_catalog(catalog);
+        }
+        for connection in connections {
+            index.add_connection(connection);
+        }
+        index
+    }
+
+    pub fn add_connection(&mut self, connection: &Connection) {
+        for end in [
+            connection.source_terminal(),
+            connection.destination_terminal(),
+        ] {
+            self.add_end(&end);
+        }
+    }
+
+    pub fn remove_connection(&mut self, connection: &Connection) {
+        for end in [
+            connection.source_terminal(),
+            connection.destination_terminal(),
+        ] {
+            self.remove_end(&end);
+        }
+    }
+
+    pub fn suggest_components(&self, query: &str, limit: usize) -> Vec<String> {
+        self.components.suggest(query, limit)
+    }
+
+    // Falls back to every terminal block when the component has none
+    pub fn suggest_terminal_blocks(
+        &self,
+        component: &str,
+        query: &str,
+        limit: usize,
+    ) -> Vec<String> {
+        self.blocks_by_component
+            .get(component)
+            .unwrap_or(&self.terminal_blocks)
+            .suggest(query, limit)
+    }
+
+    // Falls back to every terminal when the component and terminal block have none
+    pub fn suggest_terminals(
+        &self,
+        component: &str,
+        terminal_block: &str,
+        query: &str,
+        limit: usize,
+    ) -> Vec<String> {
+        self.terminals_by_block
+            .get(&(component.to_string(), terminal_block.to_string()))
+            .unwrap_or(&self.terminals)
+            .suggest(query, limit)
+    }
+
+    fn add_catalog(&mut self, catalog: &ComponentCatalog) {
+        for component in &catalog.components {
+            self.components.catalog.insert(component.name.clone());
+            for block in &component.terminal_blocks {
+                if !block.name.is_empty() {
+                    self.terminal_blocks.catalog.insert(block.name.clone());
+                    self.blocks_by_component
+                        .entry(component.name.clone())
+                        .or_default()
+                        .catalog
+                        .insert(block.name.clone());
+                }
+                let terminals = block.terminals.terminals();
+                self.terminals.catalog.extend(terminals.iter().cloned());
+                self.terminals_by_block
+                    .entry((component.name.clone(), block.name.clone()))
+                    .or_default()
+                    .catalog
+                    .extend(terminals);
+            }
+        }
+    }
+
+    fn add_end(&mut self, end: &TerminalAddress) {
+        self.components.add(&end.component);
+        self.terminal_blocks.add(&end.terminal_block);
+        self.terminals.add(&end.terminal);
+        if !end.terminal_block.is_empty() {
+            self.blocks_by_component
+                .entry(end.component.clone())
+                .or_default()
+                .add(&end.terminal_block);
+        }
+        if !end.terminal.is_empty() {
+            self.terminals_by_block
+                .entry((end.component.clone(), end.terminal_block.clone()))
+                .or_default()
+                .add(&end.terminal);
+        }
+    }
+
+    fn remove_end(&mut self, end: &TerminalAddress) {
+        self.components.remove(&end.component);
+        self.terminal_blocks.remove(&end.terminal_block);
+        self.terminals.remove(&end.terminal);
+        // Scopes left without names are dropped, so lookups fall back to every name again
+        if let Some(blocks) = self.blocks_by_component.get_mut(&end.component) {
+            blocks.remove(&end.terminal_block);
+            if blocks.is_empty() {
+                self.blocks_by_component.remove(&end.component);
+            }
+        }
+        let key = (end.component.clone(), end.terminal_block.clone());
+        if let Some(terminals) = self.terminals_by_block.get_mut(&key) {
+            terminals.remove(&end.terminal);
+            if terminals.is_empty() {
+                self.terminals_by_block.remove(&key);
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::models::reference_designation::format_reference_designation;
+
+// One physical terminal: component, terminal block and terminal designation, optionally
+// qualified by the IEC 81346 function (=) and location (+) aspects
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TerminalAddress {
+    #[serde(default)]
+    pub function: String,
+    #[serde(default)]
+    pub location: String,
+    pub component: String,
+    #[serde(default)]
+    pub terminal_block: String,
+    pub terminal: String,
+}
+
+impl TerminalAddress {
+    pub fn new(component: &str, terminal_block: &str, terminal: &str) -> Self {
+        Self {
+            function: String::new(),
+            location: String::new(),
+            component: component.to_string(),
+            terminal_block: terminal_block.to_string(),
+            terminal: terminal.to_string(),
+        }
+    }
+
+    pub fn with_aspects(mut self, function: &str, location: &str) -> Self {
+        self.function = function.to_string();
+        self.location = location.to_string();
+        self
+    }
+
+    // Function and location only qualify a terminal; an address with nothing but those is empty
+    pub fn is_empty(&self) -> bool {
+        self.component.is_empty() && self.terminal_block.is_empty() && self.terminal.is_empty()
+    }
+}
+
+impl fmt::Display for TerminalAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.function.is_empty() || !self.location.is_empty() {
+            return write!(f, "{}", format_reference_designation(self));
+        }
+        let parts = [&self.component, &self.terminal_block, &self.terminal];
+        let parts: Vec<&str> = parts
+            .iter()
+            .filter(|p| !p.is_empty())
+            .map(|p| p.as_str())
+            .collect();
+        write!(f, "{}", parts.join("-"))
+    }
+}
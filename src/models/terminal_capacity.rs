@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::models::catalog::ComponentCatalog;
+use crate::models::connection::Connection;
+use crate::models::terminal::TerminalAddress;
+
+// Used when neither the catalog nor an override gives a capacity: most terminals clamp one
+// conductor, or two with a twin ferrule
+pub const DEFAULT_MAX_WIRES_PER_TERMINAL: u32 = 2;
+
+// Per-terminal capacity stored with the project, taking precedence over the catalog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerminalCapacityOverride {
+    pub terminal: TerminalAddress,
+    pub max_wires: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Terminal {terminal} has {count} wires but accepts at most {capacity}")]
+pub struct TerminalOverload {
+    pub terminal: TerminalAddress,
+    pub count: u32,
+    pub capacity: u32,
+}
+
+pub struct TerminalCapacityValidator<'a> {
+    catalog: Option<&'a ComponentCatalog>,
+    overrides: &'a [TerminalCapacityOverride],
+    default_capacity: u32,
+}
+
+impl<'a> TerminalCapacityValidator<'a> {
+    pub fn new(
+        catalog: Option<&'a ComponentCatalog>,
+        overrides: &'a [TerminalCapacityOverride],
+    ) -> Self {
+        Self {
+            catalog,
+            overrides,
+            default_capacity: DEFAULT_MAX_WIRES_PER_TERMINAL,
+        }
+    }
+
+    pub fn with_default_capacity(mut self, default_capacity: u32) -> Self {
+        self.default_capacity = default_capacity;
+        self
+    }
+
+    // Override first, then the catalog default for the terminal block, then the global default
+    pub fn capacity_of(&self, terminal: &TerminalAddress) -> u32 {
+        self.overrides
+            .iter()
+            .find(|o| &o.terminal == terminal)
+            .map(|o| o.max_wires)
+            .or_else(|| {
+                self.catalog
+                    .and_then(|catalog| catalog.max_wires_per_terminal(terminal))
+            })
+            .unwrap_or(self.default_capacity)
+    }
+
+    // Number of wire ends landing on each terminal, counting both `src_*` and `dst_*` sides
+    pub fn wire_counts(connections: &[Connection]) -> BTreeMap<TerminalAddress, u32> {
+        let mut counts = BTreeMap::new();
+        for connection in connections {
+            for terminal in [
+                connection.source_terminal(),
+                connection.destination_terminal(),
+            ] {
+                if !terminal.is_empty() {
+                    *counts.entry(terminal).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    pub fn validate(&self, connections: &[Connection]) -> Vec<TerminalOverload> {
+        Self::wire_counts(connections)
+            .into_iter()
+            .filter_map(|(terminal, count)| {
+                let capacity = self.capacity_of(&terminal);
+                (count > capacity).then_some(TerminalOverload {
+                    terminal,
+                    count,
+                    capacity,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum WireAttributeError {
+    #[error("Invalid cross-section: {0}")]
+    InvalidCrossSection(String),
+    #[error("Invalid wire color: {0}")]
+    InvalidColor(String),
+    #[error("Invalid wire length: {0}")]
+    InvalidLength(String),
+}
+
+// Conductor cross-section, either metric or American Wire Gauge. Serialized as its display
+// string ("1.5 mm²", "18 AWG") so it fits into a single CSV column.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CrossSection {
+    SquareMillimetres(f64),
+    Awg(u32),
+}
+
+impl fmt::Display for CrossSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossSection::SquareMillimetres(value) => write!(f, "{} mm²", value),
+            CrossSection::Awg(gauge) => write!(f, "{} AWG", gauge),
+        }
+    }
+}
+
+impl FromStr for CrossSection {
+    type Err = WireAttributeError;
+
+    // Accepts "1.5", "1,5", "1.5mm2", "1.5 mm²", "18 AWG" and "AWG18"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || WireAttributeError::InvalidCrossSection(s.to_string());
+        let normalized = s.trim().to_lowercase().replace(' ', "");
+
+        if let Some(gauge) = normalized
+            .strip_suffix("awg")
+            .or_else(|| normalized.strip_prefix("awg"))
+        {
+            return gauge.parse().map(CrossSection::Awg).map_err(|_| invalid());
+        }
+
+        let value = normalized
+            .trim_end_matches("mm²")
+            .trim_end_matches("mm2")
+            .replace(',', ".");
+        match value.parse::<f64>() {
+            Ok(value) if value > 0.0 => Ok(CrossSection::SquareMillimetres(value)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for CrossSection {
+    type Error = WireAttributeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CrossSection> for String {
+    fn from(value: CrossSection) -> Self {
+        value.to_string()
+    }
+}
+
+// Insulation color using the IEC 60757 codes. Colors outside the standard set are kept verbatim.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum WireColor {
+    Black,
+    Brown,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Violet,
+    Grey,
+    White,
+    Pink,
+    Turquoise,
+    GreenYellow,
+    Other(String),
+}
+
+impl WireColor {
+    pub fn code(&self) -> &str {
+        match self {
+            WireColor::Black => "BK",
+            WireColor::Brown => "BN",
+            WireColor::Red => "RD",
+            WireColor::Orange => "OG",
+            WireColor::Yellow => "YE",
+            WireColor::Green => "GN",
+            WireColor::Blue => "BU",
+            WireColor::Violet => "VT",
+            WireColor::Grey => "GY",
+            WireColor::White => "WH",
+            WireColor::Pink => "PK",
+            WireColor::Turquoise => "TQ",
+            WireColor::GreenYellow => "GNYE",
+            WireColor::Other(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for WireColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for WireColor {
+    type Err = WireAttributeError;
+
+    // Accepts IEC codes ("BU") as well as English names ("blue")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let color = match trimmed.to_uppercase().replace(['-', '/', ' '], "").as_str() {
+            "" => return Err(WireAttributeError::InvalidColor(s.to_string())),
+            "BK" | "BLACK" => WireColor::Black,
+            "BN" | "BROWN" => WireColor::Brown,
+            "RD" | "RED" => WireColor::Red,
+            "OG" | "ORANGE" => WireColor::Orange,
+            "YE" | "YELLOW" => WireColor::Yellow,
+            "GN" | "GREEN" => WireColor::Green,
+            "BU" | "BLUE" => WireColor::Blue,
+            "VT" | "VIOLET" | "PURPLE" => WireColor::Violet,
+            "GY" | "GREY" | "GRAY" => WireColor::Grey,
+            "WH" | "WHITE" => WireColor::White,
+            "PK" | "PINK" => WireColor::Pink,
+            "TQ" | "TURQUOISE" => WireColor::Turquoise,
+            "GNYE" | "GREENYELLOW" => WireColor::GreenYellow,
+            _ => WireColor::Other(trimmed.to_string()),
+        };
+        Ok(color)
+    }
+}
+
+impl TryFrom<String> for WireColor {
+    type Error = WireAttributeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<WireColor> for String {
+    fn from(value: WireColor) -> Self {
+        value.code().to_string()
+    }
+}
+
+// Cut lengths are entered in whole millimetres
+pub fn parse_length_mm(s: &str) -> Result<u32, WireAttributeError> {
+    s.trim()
+        .trim_end_matches("mm")
+        .trim()
+        .parse()
+        .map_err(|_| WireAttributeError::InvalidLength(s.to_string()))
+}
@@ -0,0 +1,263 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::models::connection::Connection;
+use crate::models::connectivity::ConnectivityGraph;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum WireNumberingError {
+    #[error("Unknown wire numbering scheme: {0}")]
+    UnknownScheme(String),
+    #[error("Unknown placeholder {{{0}}} in wire number template")]
+    UnknownPlaceholder(String),
+    #[error("Wire number template {0} has no {{n}} placeholder")]
+    MissingCounter(String),
+    #[error("Unclosed '{{' in wire number template {0}")]
+    UnclosedBrace(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    // Counter, zero padded to the given width
+    Counter(usize),
+    Potential,
+    Page,
+    Column,
+}
+
+// Wire number layout such as `W{n:4}` (W0001), `{potential}-{n:3}` (24V-003) or
+// `{page}.{column}{n}` (12.31). `{n}` is required; `{n:W}` pads it to W digits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WireNumberTemplate {
+    template: String,
+    parts: Vec<TemplatePart>,
+}
+
+impl WireNumberTemplate {
+    pub const DEFAULT_TEMPLATE: &'static str = "{n}";
+
+    pub fn new(template: &str) -> Result<Self, WireNumberingError> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                parts.push(TemplatePart::Literal(rest[..open].to_string()));
+            }
+            let close = rest[open..]
+                .find('}')
+                .map(|i| open + i)
+                .ok_or_else(|| WireNumberingError::UnclosedBrace(template.to_string()))?;
+            let placeholder = &rest[open + 1..close];
+            let part = match placeholder.split_once(':') {
+                Some(("n", width)) => TemplatePart::Counter(width.parse().map_err(|_| {
+                    WireNumberingError::UnknownPlaceholder(placeholder.to_string())
+                })?),
+                _ => match placeholder {
+                    "n" => TemplatePart::Counter(0),
+                    "potential" => TemplatePart::Potential,
+                    "page" => TemplatePart::Page,
+                    "column" => TemplatePart::Column,
+                    other => return Err(WireNumberingError::UnknownPlaceholder(other.to_string())),
+                },
+            };
+            parts.push(part);
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Literal(rest.to_string()));
+        }
+        if !parts.iter().any(|p| matches!(p, TemplatePart::Counter(_))) {
+            return Err(WireNumberingError::MissingCounter(template.to_string()));
+        }
+        Ok(Self {
+            template: template.to_string(),
+            parts,
+        })
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    fn render(&self, n: u32, scope: &NumberScope) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::Counter(width) => format!("{:0width$}", n, width = *width),
+                TemplatePart::Potential => scope.potential.clone(),
+                TemplatePart::Page => scope.page.to_string(),
+                TemplatePart::Column => scope.column.to_string(),
+            })
+            .collect()
+    }
+}
+
+// Plain running numbers, `1`, `2`, ...
+impl Default for WireNumberTemplate {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TEMPLATE).expect("built-in wire number template is valid")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WireNumberingScheme {
+    // One running number over all wires
+    #[default]
+    Sequential,
+    // All wires of one potential (net) share a number, counted per signal name
+    PerPotential,
+    // Counted separately for every schematic page and column
+    PerPageAndColumn,
+}
+
+impl FromStr for WireNumberingScheme {
+    type Err = WireNumberingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sequential" => Ok(WireNumberingScheme::Sequential),
+            "potential" | "per_potential" => Ok(WireNumberingScheme::PerPotential),
+            "page_column" | "per_page_and_column" => Ok(WireNumberingScheme::PerPageAndColumn),
+            other => Err(WireNumberingError::UnknownScheme(other.to_string())),
+        }
+    }
+}
+
+// What a counter runs over: a potential name, a page and column, or everything
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+struct NumberScope {
+    potential: String,
+    page: u32,
+    column: u32,
+}
+
+pub struct WireNumberingEngine {
+    pub scheme: WireNumberingScheme,
+    pub template: WireNumberTemplate,
+}
+
+impl WireNumberingEngine {
+    pub fn new(scheme: WireNumberingScheme, template: WireNumberTemplate) -> Self {
+        Self { scheme, template }
+    }
+
+    // Throws away every existing number and numbers all wires in list order
+    pub fn renumber(&self, connections: &mut [Connection], graph: &ConnectivityGraph) {
+        for connection in connections.iter_mut() {
+            connection.wire_number = None;
+        }
+        self.assign_missing(connections, graph);
+    }
+
+    // Numbers only the wires that have no number yet, using the lowest free numbers. Existing
+    // numbers are never changed, so adding wires keeps the numbers already printed on labels.
+    pub fn assign_missing(&self, connections: &mut [Connection], graph: &ConnectivityGraph) {
+        let mut used: HashSet<String> = connections
+            .iter()
+            .filter_map(|c| c.wire_number.clone())
+            .collect();
+        let groups = self.groups(connections, graph);
+
+        // Number already given to each group
+        let mut group_numbers: HashMap<usize, String> = HashMap::new();
+        for (connection, group) in connections.iter().zip(&groups) {
+            if let Some(number) = &connection.wire_number {
+                group_numbers
+                    .entry(*group)
+                    .or_insert_with(|| number.clone());
+            }
+        }
+
+        let potentials = self.potentials(connections, &groups);
+        let scopes: Vec<NumberScope> = connections
+            .iter()
+            .zip(&groups)
+            .map(|(connection, group)| self.scope(connection, *group, &potentials))
+            .collect();
+        let mut counters: HashMap<NumberScope, u32> = HashMap::new();
+        for (i, connection) in connections.iter_mut().enumerate() {
+            if connection.wire_number.is_some() {
+                continue;
+            }
+            let number = group_numbers
+                .entry(groups[i])
+                .or_insert_with(|| {
+                    let counter = counters.entry(scopes[i].clone()).or_insert(0);
+                    loop {
+                        *counter += 1;
+                        let candidate = self.template.render(*counter, &scopes[i]);
+                        if used.insert(candidate.clone()) {
+                            break candidate;
+                        }
+                    }
+                })
+                .clone();
+            connection.wire_number = Some(number);
+        }
+    }
+
+    // Wires sharing a group share a number: one group per net for `PerPotential`, one group
+    // per wire otherwise
+    fn groups(&self, connections: &[Connection], graph: &ConnectivityGraph) -> Vec<usize> {
+        match self.scheme {
+            WireNumberingScheme::PerPotential => {
+                let nets = graph.nets();
+                let net_index: HashMap<_, usize> = nets
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, net)| net.iter().map(move |t| (t.clone(), i)))
+                    .collect();
+                connections
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| {
+                        net_index
+                            .get(&c.source_terminal())
+                            .copied()
+                            // Wires missing from the graph form their own group
+                            .unwrap_or(nets.len() + i)
+                    })
+                    .collect()
+            }
+            _ => (0..connections.len()).collect(),
+        }
+    }
+
+    // The first signal name found on any wire of each group, looked up once per group
+    fn potentials(&self, connections: &[Connection], groups: &[usize]) -> HashMap<usize, String> {
+        let mut potentials = HashMap::new();
+        if self.scheme == WireNumberingScheme::PerPotential {
+            for (connection, group) in connections.iter().zip(groups) {
+                if let Some(signal_name) = &connection.signal_name {
+                    potentials
+                        .entry(*group)
+                        .or_insert_with(|| signal_name.clone());
+                }
+            }
+        }
+        potentials
+    }
+
+    fn scope(
+        &self,
+        connection: &Connection,
+        group: usize,
+        potentials: &HashMap<usize, String>,
+    ) -> NumberScope {
+        match self.scheme {
+            WireNumberingScheme::Sequential => NumberScope::default(),
+            WireNumberingScheme::PerPotential => NumberScope {
+                potential: potentials.get(&group).cloned().unwrap_or_default(),
+                ..NumberScope::default()
+            },
+            WireNumberingScheme::PerPageAndColumn => NumberScope {
+                page: connection.page.unwrap_or_default(),
+                column: connection.column.unwrap_or_default(),
+                ..NumberScope::default()
+            },
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use iced::multi_window;
 use iced::window::Id as WindowId;
-use iced::{self, Command, Element};
+use iced::{self, Command, Element, Subscription};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -146,9 +146,22 @@ impl multi_window::Application for MainApplication {
                 );
                 Command::none()
             }
+            AppMessage::Main(message) => self
+                .app_state
+                .main_window_state
+                .update(message)
+                .map(AppMessage::Main),
         }
     }
 
+    // Keyboard shortcuts of the main window (undo and redo)
+    fn subscription(&self) -> Subscription<Self::Message> {
+        self.app_state
+            .main_window_state
+            .subscription()
+            .map(AppMessage::Main)
+    }
+
     fn view(&self, window: WindowId) -> Element<'_, Self::Message, Self::Theme, iced::Renderer> {
         match self.app_state.active_window {
             ActiveWindow::Main if Some(window) == self.main_window_id => self
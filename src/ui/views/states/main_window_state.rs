@@ -1,18 +1,33 @@
+use iced::keyboard::{self, Key, Modifiers};
 use iced::theme::Button as ThemeButton;
 use iced::widget::scrollable::Viewport;
 use iced::widget::{
     button, scrollable, text_input, Button, Checkbox, Column, Container, Row, Scrollable, Text,
     TextInput,
 };
-use iced::{Command, Element, Length};
+use iced::{Command, Element, Length, Subscription};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use tracing::warn;
+use std::sync::{Arc, Mutex};
+use tracing::error;
 use uuid::Uuid;
 
+use crate::command::command::{AddConnectionsCommand, DeleteConnectionCommand, NumberWiresCommand};
+use crate::command::command_manager::CommandManager;
+use crate::command::operation_log::OperationLog;
+
 use crate::models::connection::Connection;
 use crate::models::connection_manager::ConnectionManager;
+use crate::models::reference_designation::{
+    parse_reference_designation, FUNCTION_PREFIX, LOCATION_PREFIX,
+};
+use crate::models::terminal::TerminalAddress;
+use crate::models::wire_attributes::{parse_length_mm, WireAttributeError};
 use crate::ui::theme::ConnectionStyle;
+use crate::utils::csv_exporting_strategy::ExportFormat;
+use crate::utils::increment::increment_terminal;
+use crate::utils::quick_entry::QuickEntry;
+use crate::utils::range_expansion::expand_connection;
 use crate::LOCALIZER;
 
 pub struct MainWindowState {
@@ -36,6 +51,27 @@ pub struct MainWindowState {
     dst_terminal_block_value: String,
     dst_terminal_value: String,
 
+    // Current values for the optional wire attribute inputs
+    cross_section_value: String,
+    wire_color_value: String,
+    wire_length_value: String,
+    wire_type_value: String,
+    signal_name_value: String,
+
+    // Input that was edited last; its suggestions are shown below the inputs
+    active_field: Option<EntryField>,
+
+    // Single-line entry such as `K1-A1 > X1-3` and its parse error, shown right below it
+    quick_entry_value: String,
+    quick_entry_error: Option<String>,
+
+    // Connection list filter such as `+CAB2` or `=PUMP1`, see
+    // `ConnectionManager::filter_connections`
+    connection_filter_value: String,
+
+    // Feedback shown below the inputs (added connection, validation errors)
+    status_message: Option<String>,
+
     // State for buttons
     edit_button_state: button::State,
     delete_button_state: button::State,
@@ -52,23 +88,52 @@ pub struct MainWindowState {
     // State for the list view
     connections_scroll: scrollable::State,
     connections_buttons: Vec<button::State>,
-    connections: Vec<Connection>,
     selected_connections: HashMap<Uuid, bool>,
     scroll_state: scrollable::State,
 
     // Commands and connection manager
-    command_stack: Vec<Box<dyn crate::command::command::Command>>,
-    undo_stack: Vec<Box<dyn crate::command::command::Command>>,
-    connection_manager: ConnectionManager,
+    command_manager: CommandManager,
+    connection_manager: Arc<Mutex<ConnectionManager>>,
+}
+
+// The six designation inputs, for autocomplete
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryField {
+    SrcComponent,
+    SrcTerminalBlock,
+    SrcTerminal,
+    DstComponent,
+    DstTerminalBlock,
+    DstTerminal,
 }
 
+// Number of suggestions shown below the inputs
+const SUGGESTION_LIMIT: usize = 5;
+
 #[derive(Debug, Clone)]
 pub enum MainWindowMessage {
     Open,
     AddConnectionPressed,
     SourceChanged(String),
     DestinationChanged(String),
+    SrcComponentChanged(String),
+    SrcTerminalBlockChanged(String),
+    SrcTerminalChanged(String),
+    DstComponentChanged(String),
+    DstTerminalBlockChanged(String),
+    DstTerminalChanged(String),
+    CrossSectionChanged(String),
+    WireColorChanged(String),
+    WireLengthChanged(String),
+    WireTypeChanged(String),
+    SignalNameChanged(String),
+    QuickEntryChanged(String),
+    SuggestionPicked(EntryField, String),
+    QuickEntrySubmitted,
+    ConnectionFilterChanged(String),
     ConnectionSelected(usize), // Index of the selected connection
+    UndoPressed,
+    RedoPressed,
     SaveFilePressed,
     LockDestinationChanged(bool),
     IncrementChanged(bool),
@@ -78,6 +143,11 @@ pub enum MainWindowMessage {
     OpenSettingsWindow,
     ExportWiresPressed,
     ExportCablesPressed,
+    ExportLabelsPressed,
+    PrintLabelsPressed,
+    ExportBridgesPressed,
+    NumberWiresPressed,
+    RenumberWiresPressed,
     QuitPressed,
     IncrementField1Changed(bool),
     IncrementField2Changed(bool),
@@ -97,10 +167,25 @@ impl MainWindowState {
         // Determine file paths from flags, providing defaults as necessary
 
         // Initialize the ConnectionManager
+        let history_path = OperationLog::path_for_project(&output_file_path);
         let connection_manager =
             ConnectionManager::new(Some(wire_label_path), output_file_path, settings_file_path)
                 .expect("Failed to initialize ConnectionManager");
 
+        // Restore the undo history saved next to the project
+        let log = OperationLog::new(
+            history_path,
+            OperationLog::default_author(&connection_manager.metadata.author),
+        );
+        let connection_manager = Arc::new(Mutex::new(connection_manager));
+        let command_manager = match CommandManager::restore(log.clone(), &connection_manager) {
+            Ok(command_manager) => command_manager,
+            Err(e) => {
+                error!("Failed to restore undo history: {}", e);
+                CommandManager::with_log(log, &connection_manager)
+            }
+        };
+
         // Return the initial application state and any initial commands
         Self {
             // Text input states for source
@@ -123,6 +208,22 @@ impl MainWindowState {
             dst_terminal_block_value: String::new(),
             dst_terminal_value: String::new(),
 
+            // Current values for the optional wire attribute inputs
+            cross_section_value: String::new(),
+            wire_color_value: String::new(),
+            wire_length_value: String::new(),
+            wire_type_value: String::new(),
+            signal_name_value: String::new(),
+
+            active_field: None,
+
+            quick_entry_value: String::new(),
+            quick_entry_error: None,
+
+            connection_filter_value: String::new(),
+
+            status_message: None,
+
             // State for buttons
             edit_button_state: button::State::new(),
             delete_button_state: button::State::new(),
@@ -139,41 +240,364 @@ impl MainWindowState {
             // State for the list view
             connections_scroll: scrollable::State::new(),
             connections_buttons: Vec::new(),
-            connections: Self::generate_dummy_connections(),
             selected_connections: HashMap::new(),
             scroll_state: scrollable::State::new(),
 
             // Commands and connection manager
-            command_stack: Vec::new(),
-            undo_stack: Vec::new(),
+            command_manager,
             connection_manager,
         }
     }
 
-    // TODO: Remove this dummy code
-    fn generate_dummy_connections() -> Vec<Connection> {
-        warn!("Remove this function");
-        (1..51)
-            .map(|i| {
-                Connection::new(
-                    format!("SrcComp{}", i),
-                    format!("SrcBlock{}", i),
-                    format!("SrcTerm{}", i),
-                    format!("DstComp{}", i),
-                    format!("DstBlock{}", i),
-                    format!("DstTerm{}", i),
-                )
-            })
+    // Builds a connection from the current input values. Empty attribute inputs are left unset.
+    fn build_connection(&self) -> Result<Connection, WireAttributeError> {
+        let mut connection = Connection::new(
+            self.src_component_value.clone(),
+            self.src_terminal_block_value.clone(),
+            self.src_terminal_value.clone(),
+            self.dst_component_value.clone(),
+            self.dst_terminal_block_value.clone(),
+            self.dst_terminal_value.clone(),
+        );
+        connection.set_source_terminal(with_reference_aspects(connection.source_terminal()));
+        connection
+            .set_destination_terminal(with_reference_aspects(connection.destination_terminal()));
+        connection.cross_section = non_empty(&self.cross_section_value)
+            .map(str::parse)
+            .transpose()?;
+        connection.color = non_empty(&self.wire_color_value)
+            .map(str::parse)
+            .transpose()?;
+        connection.length_mm = non_empty(&self.wire_length_value)
+            .map(parse_length_mm)
+            .transpose()?;
+        connection.wire_type = non_empty(&self.wire_type_value).map(String::from);
+        connection.signal_name = non_empty(&self.signal_name_value).map(String::from);
+        Ok(connection)
+    }
+
+    fn field_value_mut(&mut self, field: EntryField) -> &mut String {
+        match field {
+            EntryField::SrcComponent => &mut self.src_component_value,
+            EntryField::SrcTerminalBlock => &mut self.src_terminal_block_value,
+            EntryField::SrcTerminal => &mut self.src_terminal_value,
+            EntryField::DstComponent => &mut self.dst_component_value,
+            EntryField::DstTerminalBlock => &mut self.dst_terminal_block_value,
+            EntryField::DstTerminal => &mut self.dst_terminal_value,
+        }
+    }
+
+    fn edit_field(&mut self, field: EntryField, value: String) {
+        *self.field_value_mut(field) = value;
+        self.active_field = Some(field);
+    }
+
+    // Ranked names for `field`; block and terminal suggestions follow the component (and block)
+    // entered on the same side
+    fn suggestions(&self, mgr: &ConnectionManager, field: EntryField) -> Vec<String> {
+        let index = mgr.suggestions();
+        let (component, terminal_block, terminal) = match field {
+            EntryField::SrcComponent | EntryField::SrcTerminalBlock | EntryField::SrcTerminal => (
+                &self.src_component_value,
+                &self.src_terminal_block_value,
+                &self.src_terminal_value,
+            ),
+            _ => (
+                &self.dst_component_value,
+                &self.dst_terminal_block_value,
+                &self.dst_terminal_value,
+            ),
+        };
+        let (suggestions, value) = match field {
+            EntryField::SrcComponent | EntryField::DstComponent => (
+                index.suggest_components(component, SUGGESTION_LIMIT),
+                component,
+            ),
+            EntryField::SrcTerminalBlock | EntryField::DstTerminalBlock => (
+                index.suggest_terminal_blocks(component, terminal_block, SUGGESTION_LIMIT),
+                terminal_block,
+            ),
+            EntryField::SrcTerminal | EntryField::DstTerminal => (
+                index.suggest_terminals(component, terminal_block, terminal, SUGGESTION_LIMIT),
+                terminal,
+            ),
+        };
+        // Nothing to complete once the input holds the name
+        suggestions
+            .into_iter()
+            .filter(|suggestion| suggestion != value)
             .collect()
     }
 
+    // Advances the terminal fields whose increment box is checked, leaving the destination
+    // alone while it is locked. Returns false if a checked field could not be incremented; it
+    // then keeps its value.
+    fn advance_terminals(&mut self) -> bool {
+        let (step, increment_source, increment_destination) = self.increments();
+        advance(
+            step,
+            [
+                (increment_source, &mut self.src_terminal_value),
+                (increment_destination, &mut self.dst_terminal_value),
+            ],
+        )
+    }
+
+    // The `increment_step` setting and whether source and destination terminals advance
+    fn increments(&self) -> (u32, bool, bool) {
+        let step = self
+            .connection_manager
+            .lock()
+            .unwrap()
+            .get_settings()
+            .get("increment_step")
+            .and_then(|step| step.trim().parse().ok())
+            .unwrap_or(1);
+        (
+            step,
+            self.increment_field1_checked,
+            self.increment_field2_checked && !self.lock_destination_checked,
+        )
+    }
+
+    // Adds the connections as one undo step and returns them as stored (with their wire numbers,
+    // if numbered automatically)
+    fn add_connections(&mut self, connections: Vec<Connection>) -> Result<Vec<Connection>, String> {
+        let uuids: Vec<Uuid> = connections.iter().map(|c| c.uuid).collect();
+        let command = AddConnectionsCommand::new(self.connection_manager.clone(), connections);
+        self.command_manager
+            .execute_command(Box::new(command))
+            .map_err(|e| e.to_string())?;
+        let mgr = self.connection_manager.lock().unwrap();
+        Ok(uuids
+            .iter()
+            .filter_map(|uuid| mgr.connections.iter().find(|c| c.uuid == *uuid).cloned())
+            .collect())
+    }
+
+    // Deletes the selected connections as one undo step; if any of them cannot be deleted, none
+    // are
+    fn delete_selected(&mut self) {
+        let uuids: Vec<Uuid> = self
+            .selected_connections
+            .iter()
+            .filter(|(_, selected)| **selected)
+            .map(|(uuid, _)| *uuid)
+            .collect();
+        if uuids.is_empty() {
+            return;
+        }
+        let command = DeleteConnectionCommand::new(uuids, self.connection_manager.clone());
+        match self.command_manager.execute_command(Box::new(command)) {
+            Ok(()) => {
+                self.selected_connections.clear();
+                self.status_message = None;
+            }
+            Err(e) => self.status_message = Some(e.to_string()),
+        }
+    }
+
+    // Numbers wires with the wire numbering settings as one undo step; `renumber` throws away
+    // the existing numbers first
+    fn number_wires(&mut self, renumber: bool) {
+        let command = if renumber {
+            NumberWiresCommand::renumber(self.connection_manager.clone())
+        } else {
+            NumberWiresCommand::number_new(self.connection_manager.clone())
+        };
+        self.status_message = self
+            .command_manager
+            .execute_command(Box::new(command))
+            .err()
+            .map(|e| e.to_string());
+    }
+
+    // Writes the wire list next to the project file, laid out by the CSV export settings
+    fn export_wires(&mut self) {
+        let localizer = LOCALIZER.as_ref();
+        let mgr = self.connection_manager.lock().unwrap();
+        let file_path = mgr.default_export_path();
+        let result = mgr.export_to_csv(ExportFormat::Wire, file_path.clone());
+        drop(mgr);
+        self.status_message = Some(match result {
+            Ok(()) => localizer
+                .get("exported_file")
+                .replace("{filename}", &file_path.display().to_string()),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    // Writes the wire labels next to the project file with the exporter chosen in the settings
+    fn export_labels(&mut self) {
+        let localizer = LOCALIZER.as_ref();
+        let mgr = self.connection_manager.lock().unwrap();
+        let result = mgr.export_labels(mgr.default_export_path());
+        drop(mgr);
+        self.status_message = Some(match result {
+            Ok(file_path) => localizer
+                .get("exported_file")
+                .replace("{filename}", &file_path.display().to_string()),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    // Sends the wire labels to the ZPL printer set in the settings
+    fn print_labels(&mut self) {
+        let localizer = LOCALIZER.as_ref();
+        let result = self.connection_manager.lock().unwrap().print_labels();
+        self.status_message = Some(match result {
+            Ok(count) => localizer
+                .get("printed_labels")
+                .replace("{count}", &count.to_string()),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    // Writes the cable labels next to the project file, e.g. `connections.cables.csv`
+    fn export_cables(&mut self) {
+        let localizer = LOCALIZER.as_ref();
+        let mgr = self.connection_manager.lock().unwrap();
+        let file_path = mgr.default_export_path().with_extension("cables.csv");
+        let result = mgr.export_cable_labels(file_path.clone());
+        drop(mgr);
+        self.status_message = Some(match result {
+            Ok(()) => localizer
+                .get("exported_file")
+                .replace("{filename}", &file_path.display().to_string()),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    // Writes the bridge order list next to the project file, e.g. `connections.bridges.csv`
+    fn export_bridges(&mut self) {
+        let localizer = LOCALIZER.as_ref();
+        let mgr = self.connection_manager.lock().unwrap();
+        let file_path = mgr.default_export_path().with_extension("bridges.csv");
+        let result = mgr.export_bridge_report(file_path.clone());
+        drop(mgr);
+        self.status_message = Some(match result {
+            Ok(()) => localizer
+                .get("exported_file")
+                .replace("{filename}", &file_path.display().to_string()),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    // Ctrl+Z undoes, Ctrl+Y and Ctrl+Shift+Z redo (Cmd on macOS)
+    pub fn subscription(&self) -> Subscription<MainWindowMessage> {
+        keyboard::on_key_press(shortcut)
+    }
+
+    // Parses the quick-entry line and adds its connections. The line then advances like the
+    // terminal fields; if it cannot be used it is kept and the error is shown below it.
+    fn submit_quick_entry(&mut self) {
+        let localizer = LOCALIZER.as_ref();
+        let formatter = self
+            .connection_manager
+            .lock()
+            .unwrap()
+            .designation_formatter();
+        let result = QuickEntry::parse(&self.quick_entry_value, &formatter)
+            .and_then(|entry| Ok((entry.connections()?, entry)))
+            .map_err(|e| e.to_string())
+            .and_then(|(connections, entry)| Ok((self.add_connections(connections)?, entry)));
+        let (added, mut entry) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                self.quick_entry_error = Some(e);
+                return;
+            }
+        };
+        self.quick_entry_error = None;
+        let mut message = self.added_message(&added);
+        // As with the fields, ranges are not advanced
+        if added.len() == 1 {
+            let (step, increment_source, increment_destination) = self.increments();
+            let incremented = advance(
+                step,
+                [
+                    (increment_source, &mut entry.source.terminal),
+                    (increment_destination, &mut entry.destination.terminal),
+                ],
+            );
+            if !incremented {
+                message.push_str(&format!(" {}", localizer.get("increment_error")));
+            }
+            self.quick_entry_value = entry.to_line(&formatter);
+        }
+        self.status_message = Some(message);
+    }
+
+    // Status line for newly added connections, including catalog warnings and overloaded
+    // terminals
+    fn added_message(&self, added: &[Connection]) -> String {
+        let localizer = LOCALIZER.as_ref();
+        let mgr = self.connection_manager.lock().unwrap();
+        let formatter = mgr.designation_formatter();
+        let pair = |connection: &Connection| {
+            let (source, destination) = connection.to_tuple(&formatter);
+            format!("{}, {}", source, destination)
+        };
+        let mut message = match added {
+            [connection] => {
+                let (source, destination) = connection.to_tuple(&formatter);
+                localizer
+                    .get("added_connection")
+                    .replace("{source}", &source)
+                    .replace("{destination}", &destination)
+            }
+            [first, .., last] => localizer
+                .get("added_connections")
+                .replace("{count}", &added.len().to_string())
+                .replace("{first}", &pair(first))
+                .replace("{last}", &pair(last)),
+            [] => String::new(),
+        };
+        // Connections outside the catalog are only kept when the policy is "warn"
+        for connection in added {
+            for violation in mgr.catalog_violations(connection) {
+                message.push_str(&format!(" ({})", violation));
+            }
+        }
+        let ends: Vec<TerminalAddress> = added
+            .iter()
+            .flat_map(|c| [c.source_terminal(), c.destination_terminal()])
+            .collect();
+        for overload in mgr.terminal_overloads() {
+            if ends.contains(&overload.terminal) {
+                message.push_str(&format!(" ({})", overload));
+            }
+        }
+        message
+    }
+
     pub fn update(&mut self, message: MainWindowMessage) -> Command<MainWindowMessage> {
         match message {
             MainWindowMessage::ConnectionSelected(_) => {
                 // Handle what to do when selecting a connection
             }
             MainWindowMessage::AddConnectionPressed => {
-                // Handle the Add Connection button being pressed
+                let localizer = LOCALIZER.as_ref();
+                // Ranges such as `1-16` give one connection per terminal, added as one undo step
+                let result = self
+                    .build_connection()
+                    .map_err(|e| e.to_string())
+                    .and_then(|connection| {
+                        expand_connection(&connection).map_err(|e| e.to_string())
+                    })
+                    .and_then(|connections| self.add_connections(connections));
+                self.status_message = Some(match result {
+                    Ok(added) => {
+                        let mut message = self.added_message(&added);
+                        // Incrementing a range would not give the next range, so only single
+                        // connections advance the terminal fields
+                        if added.len() == 1 && !self.advance_terminals() {
+                            message.push_str(&format!(" {}", localizer.get("increment_error")));
+                        }
+                        message
+                    }
+                    Err(e) => e,
+                });
             }
             MainWindowMessage::SourceChanged(source) => {
                 // Handle the source text input changing
@@ -181,6 +605,47 @@ impl MainWindowState {
             MainWindowMessage::DestinationChanged(destination) => {
                 // Handle the destination text input changing
             }
+            MainWindowMessage::SrcComponentChanged(value) => {
+                self.edit_field(EntryField::SrcComponent, value)
+            }
+            MainWindowMessage::SrcTerminalBlockChanged(value) => {
+                self.edit_field(EntryField::SrcTerminalBlock, value)
+            }
+            MainWindowMessage::SrcTerminalChanged(value) => {
+                self.edit_field(EntryField::SrcTerminal, value)
+            }
+            MainWindowMessage::DstComponentChanged(value) => {
+                self.edit_field(EntryField::DstComponent, value)
+            }
+            MainWindowMessage::DstTerminalBlockChanged(value) => {
+                self.edit_field(EntryField::DstTerminalBlock, value)
+            }
+            MainWindowMessage::DstTerminalChanged(value) => {
+                self.edit_field(EntryField::DstTerminal, value)
+            }
+            MainWindowMessage::SuggestionPicked(field, value) => {
+                *self.field_value_mut(field) = value;
+                self.active_field = None;
+            }
+            MainWindowMessage::CrossSectionChanged(value) => self.cross_section_value = value,
+            MainWindowMessage::WireColorChanged(value) => self.wire_color_value = value,
+            MainWindowMessage::WireLengthChanged(value) => self.wire_length_value = value,
+            MainWindowMessage::WireTypeChanged(value) => self.wire_type_value = value,
+            MainWindowMessage::SignalNameChanged(value) => self.signal_name_value = value,
+            MainWindowMessage::QuickEntryChanged(value) => {
+                self.quick_entry_value = value;
+                self.quick_entry_error = None;
+            }
+            MainWindowMessage::QuickEntrySubmitted => self.submit_quick_entry(),
+            MainWindowMessage::ConnectionFilterChanged(value) => {
+                self.connection_filter_value = value
+            }
+            MainWindowMessage::UndoPressed if self.command_manager.can_undo() => {
+                self.status_message = self.command_manager.undo().err().map(|e| e.to_string());
+            }
+            MainWindowMessage::RedoPressed if self.command_manager.can_redo() => {
+                self.status_message = self.command_manager.redo().err().map(|e| e.to_string());
+            }
             MainWindowMessage::SaveFilePressed => {
                 // Handle the save file button press
             }
@@ -198,10 +663,8 @@ impl MainWindowState {
                     self.selected_connections.insert(uuid, true);
                 }
             }
-            MainWindowMessage::DeleteSelectedConnections => {
-                // Here you would create a DeleteConnectionCommand and execute it. This would
-                // involve removing connections from `self.connections` based on the UUIDs in
-                // `self.selected_connections`
+            MainWindowMessage::DeleteSelectedConnections | MainWindowMessage::DeletePressed => {
+                self.delete_selected()
             }
             MainWindowMessage::EditConnection(uuid) => {
                 // Here you would create an EditConnectionCommand and execute it. You need to
@@ -209,9 +672,13 @@ impl MainWindowState {
             }
             MainWindowMessage::QuitPressed => {}
             MainWindowMessage::EditPressed => {}
-            MainWindowMessage::DeletePressed => {}
-            MainWindowMessage::ExportWiresPressed => {}
-            MainWindowMessage::ExportCablesPressed => {}
+            MainWindowMessage::ExportWiresPressed => self.export_wires(),
+            MainWindowMessage::NumberWiresPressed => self.number_wires(false),
+            MainWindowMessage::RenumberWiresPressed => self.number_wires(true),
+            MainWindowMessage::ExportCablesPressed => self.export_cables(),
+            MainWindowMessage::ExportLabelsPressed => self.export_labels(),
+            MainWindowMessage::PrintLabelsPressed => self.print_labels(),
+            MainWindowMessage::ExportBridgesPressed => self.export_bridges(),
             MainWindowMessage::IncrementField1Changed(new_value) => {
                 self.increment_field1_checked = new_value;
             }
@@ -235,34 +702,83 @@ impl MainWindowState {
         let src_component_input: TextInput<MainWindowMessage> =
             TextInput::new(&component_string, &self.src_component_value)
                 .size(10)
-                .on_input(MainWindowMessage::SourceChanged)
+                .on_input(MainWindowMessage::SrcComponentChanged)
                 .padding(2);
         let src_terminal_block_input: TextInput<MainWindowMessage> =
             TextInput::new(&terminal_block_string, &self.src_terminal_block_value)
                 .size(10)
-                .on_input(MainWindowMessage::SourceChanged)
+                .on_input(MainWindowMessage::SrcTerminalBlockChanged)
                 .padding(2);
         let src_terminal_input: TextInput<MainWindowMessage> =
             TextInput::new(&terminal_string, &self.src_terminal_value)
                 .size(10)
-                .on_input(MainWindowMessage::SourceChanged)
+                .on_input(MainWindowMessage::SrcTerminalChanged)
                 .padding(2);
 
         // Destination input field with label
         let dst_component_input: TextInput<MainWindowMessage> =
             TextInput::new(&component_string, &self.dst_component_value)
                 .size(10)
-                .on_input(MainWindowMessage::SourceChanged)
+                .on_input(MainWindowMessage::DstComponentChanged)
                 .padding(2);
         let dst_terminal_block_input: TextInput<MainWindowMessage> =
             TextInput::new(&terminal_block_string, &self.dst_terminal_block_value)
                 .size(10)
-                .on_input(MainWindowMessage::SourceChanged)
+                .on_input(MainWindowMessage::DstTerminalBlockChanged)
                 .padding(2);
         let dst_terminal_input: TextInput<MainWindowMessage> =
             TextInput::new(&terminal_string, &self.dst_terminal_value)
                 .size(10)
-                .on_input(MainWindowMessage::SourceChanged)
+                .on_input(MainWindowMessage::DstTerminalChanged)
+                .padding(2);
+
+        // Optional wire attribute inputs
+        let cross_section_string = localizer.get("cross_section");
+        let cross_section_input: TextInput<MainWindowMessage> =
+            TextInput::new(&cross_section_string, &self.cross_section_value)
+                .size(10)
+                .on_input(MainWindowMessage::CrossSectionChanged)
+                .padding(2);
+        let wire_color_string = localizer.get("wire_color");
+        let wire_color_input: TextInput<MainWindowMessage> =
+            TextInput::new(&wire_color_string, &self.wire_color_value)
+                .size(10)
+                .on_input(MainWindowMessage::WireColorChanged)
+                .padding(2);
+        let wire_length_string = localizer.get("wire_length");
+        let wire_length_input: TextInput<MainWindowMessage> =
+            TextInput::new(&wire_length_string, &self.wire_length_value)
+                .size(10)
+                .on_input(MainWindowMessage::WireLengthChanged)
+                .padding(2);
+        let wire_type_string = localizer.get("wire_type");
+        let wire_type_input: TextInput<MainWindowMessage> =
+            TextInput::new(&wire_type_string, &self.wire_type_value)
+                .size(10)
+                .on_input(MainWindowMessage::WireTypeChanged)
+                .padding(2);
+        let signal_name_string = localizer.get("signal_name");
+        let signal_name_input: TextInput<MainWindowMessage> =
+            TextInput::new(&signal_name_string, &self.signal_name_value)
+                .size(10)
+                .on_input(MainWindowMessage::SignalNameChanged)
+                .padding(2);
+
+        // Quick entry for a whole connection on one line
+        let quick_entry_string = localizer.get("quick_entry");
+        let quick_entry_input: TextInput<MainWindowMessage> =
+            TextInput::new(&quick_entry_string, &self.quick_entry_value)
+                .size(10)
+                .on_input(MainWindowMessage::QuickEntryChanged)
+                .on_submit(MainWindowMessage::QuickEntrySubmitted)
+                .padding(2);
+
+        // Filter for the connection list by location or function
+        let connection_filter_string = localizer.get("connection_filter");
+        let connection_filter_input: TextInput<MainWindowMessage> =
+            TextInput::new(&connection_filter_string, &self.connection_filter_value)
+                .size(10)
+                .on_input(MainWindowMessage::ConnectionFilterChanged)
                 .padding(2);
 
         // Add connection button
@@ -286,12 +802,42 @@ impl MainWindowState {
                 .on_press(MainWindowMessage::ExportWiresPressed)
                 .padding(2);
 
-        let export_cables_string = localizer.get("export_labels");
+        let export_cables_string = localizer.get("export_cables");
         let export_cables_button: Button<MainWindowMessage> =
             Button::new(Text::new(export_cables_string).size(12))
                 .on_press(MainWindowMessage::ExportCablesPressed)
                 .padding(2);
 
+        let export_labels_string = localizer.get("export_labels");
+        let export_labels_button: Button<MainWindowMessage> =
+            Button::new(Text::new(export_labels_string).size(12))
+                .on_press(MainWindowMessage::ExportLabelsPressed)
+                .padding(2);
+
+        let print_labels_string = localizer.get("print_labels");
+        let print_labels_button: Button<MainWindowMessage> =
+            Button::new(Text::new(print_labels_string).size(12))
+                .on_press(MainWindowMessage::PrintLabelsPressed)
+                .padding(2);
+
+        let export_bridges_string = localizer.get("export_bridges");
+        let export_bridges_button: Button<MainWindowMessage> =
+            Button::new(Text::new(export_bridges_string).size(12))
+                .on_press(MainWindowMessage::ExportBridgesPressed)
+                .padding(2);
+
+        let number_wires_string = localizer.get("number_wires");
+        let number_wires_button: Button<MainWindowMessage> =
+            Button::new(Text::new(number_wires_string).size(12))
+                .on_press(MainWindowMessage::NumberWiresPressed)
+                .padding(2);
+
+        let renumber_wires_string = localizer.get("renumber_wires");
+        let renumber_wires_button: Button<MainWindowMessage> =
+            Button::new(Text::new(renumber_wires_string).size(12))
+                .on_press(MainWindowMessage::RenumberWiresPressed)
+                .padding(2);
+
         let quit_string = localizer.get("quit");
         let quit_button: Button<MainWindowMessage> = Button::new(Text::new(quit_string).size(12))
             .on_press(MainWindowMessage::QuitPressed)
@@ -329,48 +875,81 @@ impl MainWindowState {
                 .on_press(MainWindowMessage::DeletePressed)
                 .padding(2);
 
-        let connections_list = self.connections.iter().enumerate().fold(
+        // Undo and redo are disabled while there is nothing to undo or redo
+        let undo_string = localizer.get("undo");
+        let undo_button: Button<MainWindowMessage> = Button::new(Text::new(undo_string).size(12))
+            .on_press_maybe(
+                self.command_manager
+                    .can_undo()
+                    .then_some(MainWindowMessage::UndoPressed),
+            )
+            .padding(2);
+        let redo_string = localizer.get("redo");
+        let redo_button: Button<MainWindowMessage> = Button::new(Text::new(redo_string).size(12))
+            .on_press_maybe(
+                self.command_manager
+                    .can_redo()
+                    .then_some(MainWindowMessage::RedoPressed),
+            )
+            .padding(2);
+
+        let mgr = self.connection_manager.lock().unwrap();
+        let suggestions_row = self.active_field.map_or(Row::new(), |field| {
+            self.suggestions(&mgr, field).into_iter().fold(
+                Row::new().spacing(5).padding(2),
+                |row, suggestion| {
+                    row.push(
+                        Button::new(Text::new(suggestion.clone()).size(10))
+                            .on_press(MainWindowMessage::SuggestionPicked(field, suggestion))
+                            .padding(2),
+                    )
+                },
+            )
+        });
+        let formatter = mgr.designation_formatter();
+        // `+` or `=` alone groups the list, so each group gets a heading
+        let filter = self.connection_filter_value.trim();
+        let grouped = [LOCATION_PREFIX, FUNCTION_PREFIX]
+            .iter()
+            .any(|prefix| filter == prefix.to_string());
+        let connections_list = mgr.filter_connections(filter).into_iter().fold(
             Column::new().spacing(2),
-            |column: iced::widget::Column<MainWindowMessage>, (_i, connection)| {
-                // Format the source and destination strings
-                let source_str = format!(
-                    "{}-{}-{}",
-                    connection.src_component,
-                    connection.src_terminal_block,
-                    connection.src_terminal,
-                );
-                let destination_str = format!(
-                    "{}-{}-{}",
-                    connection.dst_component,
-                    connection.dst_terminal_block,
-                    connection.dst_terminal,
-                );
-
-                // Check if the current connection is selected
-                let is_selected = self
-                    .selected_connections
-                    .get(&connection.uuid)
-                    .unwrap_or(&false);
-
-                let button_style = if *is_selected {
-                    ConnectionStyle::Selected
+            |column: iced::widget::Column<MainWindowMessage>, (group, connections)| {
+                let column = if grouped {
+                    column.push(Text::new(format!("{}{}", filter, group)).size(10))
                 } else {
-                    ConnectionStyle::Unselected
+                    column
                 };
+                connections.into_iter().fold(column, |column, connection| {
+                    // Format the source and destination strings
+                    let (source_str, destination_str) = connection.to_tuple(&formatter);
 
-                // Create a button for the connection
-                let button: Button<MainWindowMessage> = Button::new(
-                    Text::new(format!("{} - {}", source_str, destination_str))
-                        .size(10)
-                        .horizontal_alignment(iced::alignment::Horizontal::Center),
-                )
-                .on_press(MainWindowMessage::ToggleConnectionSelected(connection.uuid))
-                .width(Length::FillPortion(1))
-                .padding(2)
-                .style(ThemeButton::Custom(Box::new(button_style)))
-                .into();
+                    // Check if the current connection is selected
+                    let is_selected = self
+                        .selected_connections
+                        .get(&connection.uuid)
+                        .unwrap_or(&false);
+
+                    let button_style = if *is_selected {
+                        ConnectionStyle::Selected
+                    } else {
+                        ConnectionStyle::Unselected
+                    };
+
+                    // Create a button for the connection
+                    let button: Button<MainWindowMessage> = Button::new(
+                        Text::new(format!("{} - {}", source_str, destination_str))
+                            .size(10)
+                            .horizontal_alignment(iced::alignment::Horizontal::Center),
+                    )
+                    .on_press(MainWindowMessage::ToggleConnectionSelected(connection.uuid))
+                    .width(Length::FillPortion(1))
+                    .padding(2)
+                    .style(ThemeButton::Custom(Box::new(button_style)))
+                    .into();
 
-                column.push(button)
+                    column.push(button)
+                })
             },
         );
 
@@ -400,10 +979,21 @@ impl MainWindowState {
             .push(increment_field2_checkbox)
             .padding(2);
 
+        let wire_attributes_row = Row::new()
+            .spacing(10)
+            .push(cross_section_input)
+            .push(wire_color_input)
+            .push(wire_length_input)
+            .push(wire_type_input)
+            .push(signal_name_input)
+            .padding(2);
+
         let lock_and_add_row = Row::new()
             .spacing(20)
             .push(add_connection_button)
             .push(lock_destination_checkbox)
+            .push(undo_button)
+            .push(redo_button)
             .padding(2);
 
         // Combine edit and delete buttons into a row
@@ -416,6 +1006,7 @@ impl MainWindowState {
         let connections_column = Column::new()
             .width(Length::FillPortion(1))
             .height(Length::Fill)
+            .push(connection_filter_input)
             .push(connections_scrollable)
             .push(edit_and_delete_row);
 
@@ -425,14 +1016,24 @@ impl MainWindowState {
             .push(save_file_button)
             .push(export_wires_button)
             .push(export_cables_button)
+            .push(export_labels_button)
+            .push(print_labels_button)
+            .push(export_bridges_button)
+            .push(number_wires_button)
+            .push(renumber_wires_button)
             .push(quit_button);
 
         let inputs_and_actions_column = Column::new()
             .width(Length::FillPortion(2))
             .height(Length::Fill)
+            .push(quick_entry_input)
+            .push(Text::new(self.quick_entry_error.clone().unwrap_or_default()).size(10))
             .push(source_inputs_row)
             .push(destination_inputs_row)
+            .push(suggestions_row)
+            .push(wire_attributes_row)
             .push(lock_and_add_row)
+            .push(Text::new(self.status_message.clone().unwrap_or_default()).size(10))
             .push(bottom_button_row);
 
         let main_row = Row::new()
@@ -453,3 +1054,64 @@ impl MainWindowState {
             .into()
     }
 }
+
+fn shortcut(key: Key, modifiers: Modifiers) -> Option<MainWindowMessage> {
+    if !modifiers.command() {
+        return None;
+    }
+    // Shift may turn `z` into `Z`
+    match key.as_ref() {
+        Key::Character(c) if c.eq_ignore_ascii_case("z") && modifiers.shift() => {
+            Some(MainWindowMessage::RedoPressed)
+        }
+        Key::Character(c) if c.eq_ignore_ascii_case("z") => Some(MainWindowMessage::UndoPressed),
+        Key::Character(c) if c.eq_ignore_ascii_case("y") => Some(MainWindowMessage::RedoPressed),
+        _ => None,
+    }
+}
+
+// Increments every checked terminal by `step`. Returns false if a checked terminal could not be
+// incremented; it then keeps its value.
+fn advance(step: u32, terminals: [(bool, &mut String); 2]) -> bool {
+    let mut incremented = true;
+    for (checked, terminal) in terminals {
+        if !checked {
+            continue;
+        }
+        match increment_terminal(terminal, step) {
+            Some(next) => *terminal = next,
+            None => incremented = false,
+        }
+    }
+    incremented
+}
+
+fn non_empty(value: &str) -> Option<&str> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
+// A component typed as `=PUMP1+CAB2-K1` fills in the function and location of that end
+fn with_reference_aspects(mut address: TerminalAddress) -> TerminalAddress {
+    if address
+        .component
+        .starts_with([FUNCTION_PREFIX, LOCATION_PREFIX])
+    {
+        if let Ok(parsed) = parse_reference_designation(&address.component) {
+            address.function = parsed.function;
+            address.location = parsed.location;
+            address.component = parsed.component;
+            if address.terminal_block.is_empty() {
+                address.terminal_block = parsed.terminal_block;
+            }
+            if address.terminal.is_empty() {
+                address.terminal = parsed.terminal;
+            }
+        }
+    }
+    address
+}
@@ -1,37 +0,0 @@
-trait Command {
-    fn execute(&mut self);
-    fn undo(&mut self);
-}
-pub struct CommandManager {
-    undo_stack: Vec<Box<dyn Command>>,
-    redo_stack: Vec<Box<dyn Command>>,
-}
-
-impl CommandManager {
-    pub fn new() -> CommandManager {
-        CommandManager {
-            undo_stack: Vec::new(),
-            redo_stack: Vec::new(),
-        }
-    }
-
-    pub fn execute(&mut self, mut command: Box<dyn Command>) {
-        command.execute();
-        self.undo_stack.push(command);
-        self.redo_stack.clear();
-    }
-
-    fn undo(&mut self) {
-        if let Some(mut command) = self.undo_stack.pop() {
-            command.undo();
-            self.redo_stack.push(command);
-        }
-    }
-
-    fn redo(&mut self) {
-        if let Some(mut command) = self.redo_stack.pop() {
-            command.execute();
-            self.undo_stack.push(command);
-        }
-    }
-}
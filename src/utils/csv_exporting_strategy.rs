@@ -1,47 +1,100 @@
+use crate::models::bridge::{bridge_accessory_report, Bridge};
+use crate::models::cable::Cable;
 use crate::models::connection::Connection;
+use crate::models::designation::DesignationFormatter;
+use crate::utils::export_options::{ExportColumn, ExportOptions};
+use crate::utils::label_exporting_strategy::{LabelRecord, LabelingMode};
 use std::error::Error;
-use std::fs::File;
 use std::path::PathBuf;
 
+// Layout of the wire list. `Cable` writes every wire with the cable designation pattern; the
+// labels for the cables themselves come from `ExportCableToCSVStrategy::cable_label_records`.
 pub enum ExportFormat {
     Wire,
     Cable,
 }
 
-trait ExportToCsvStrategy {
-    fn export_to_csv(
+impl ExportFormat {
+    // `designation` replaces the layout's own pattern when given, normally the pattern configured
+    // for the project (see `ConnectionManager::export_strategy`)
+    pub fn strategy(
         &self,
-        file_path: PathBuf,
-        connection_list: &[Connection],
-    ) -> Result<(), Box<dyn Error>>;
-    fn generate_csv_string(&self, connection_list: &[Connection]) -> String;
+        options: ExportOptions,
+        designation: Option<DesignationFormatter>,
+    ) -> Box<dyn ExportToCsvStrategy> {
+        match (self, designation) {
+            (ExportFormat::Wire, None) => Box::new(ExportWireToCSVStrategy::new(options)),
+            (ExportFormat::Wire, Some(designation)) => {
+                Box::new(ExportWireToCSVStrategy::new(options).with_designation(designation))
+            }
+            (ExportFormat::Cable, None) => Box::new(ExportCableToCSVStrategy::new(options)),
+            (ExportFormat::Cable, Some(designation)) => {
+                Box::new(ExportCableToCSVStrategy::new(options).with_designation(designation))
+            }
+        }
+    }
 }
 
-pub struct ExportCableToCSVStrategy;
-pub struct ExportWireToCSVStrategy;
+// Strategies only decide how source and destination are written (their designation
+// pattern); delimiter, quoting, header, line ending, BOM and columns come from their
+// `ExportOptions`
+pub trait ExportToCsvStrategy {
+    fn options(&self) -> &ExportOptions;
+    fn designation(&self) -> &DesignationFormatter;
+
+    fn format_source(&self, conn: &Connection) -> String {
+        self.designation().format(&conn.source_terminal())
+    }
+
+    fn format_destination(&self, conn: &Connection) -> String {
+        self.designation().format(&conn.destination_terminal())
+    }
+
+    // One row per label of `options().labeling_mode`; in `Single` mode that is the plain
+    // source and destination row
+    fn rows(&self, conn: &Connection) -> Vec<Vec<String>> {
+        self.options()
+            .labeling_mode
+            .labels(self.format_source(conn), self.format_destination(conn))
+            .into_iter()
+            .map(|label| {
+                self.options()
+                    .columns
+                    .iter()
+                    .map(|column| match column {
+                        ExportColumn::Source => label.line(0).to_string(),
+                        ExportColumn::Destination => label.line(1).to_string(),
+                        _ => column.value(conn),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn records(&self, connection_list: &[Connection]) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        if self.options().include_header {
+            records.push(
+                self.options()
+                    .header()
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            );
+        }
+        records.extend(connection_list.iter().flat_map(|conn| self.rows(conn)));
+        records
+    }
 
-impl ExportToCsvStrategy for ExportWireToCSVStrategy {
     fn export_to_csv(
         &self,
-        mut file_path: PathBuf,
+        file_path: PathBuf,
         connection_list: &[Connection],
     ) -> Result<(), Box<dyn Error>> {
-        if file_path.extension().unwrap_or_default() != "csv" {
-            file_path.set_extension("csv");
-        }
-        let file = File::create(file_path)?;
-        let mut wtr = csv::WriterBuilder::new().delimiter(b'|').from_writer(file);
-
-        for conn in connection_list {
-            let source = format!(
-                "{}-{}-{}",
-                conn.src_component, conn.src_terminal_block, conn.src_terminal
-            );
-            let destination = format!(
-                "{}-{}-{}",
-                conn.dst_component, conn.dst_terminal_block, conn.dst_terminal
-            );
-            wtr.write_record(&[&source, &destination])?;
+        let file = self.options().create_file(file_path)?;
+        let mut wtr = self.options().writer(file);
+        for record in self.records(connection_list) {
+            wtr.write_record(&record)?;
         }
         wtr.flush()?;
         println!("Successfully exported wires");
@@ -49,67 +102,201 @@ impl ExportToCsvStrategy for ExportWireToCSVStrategy {
     }
 
     fn generate_csv_string(&self, connection_list: &[Connection]) -> String {
+        records_to_string(self.options(), self.records(connection_list))
+    }
+
+    // Labels laid out with `options().labeling_mode`, showing wire numbers as set by
+    // `options().wire_number_display`
+    fn label_records(&self, connection_list: &[Connection]) -> Vec<LabelRecord> {
+        self.label_records_with(self.options().labeling_mode, connection_list)
+    }
+
+    fn label_records_with(
+        &self,
+        mode: LabelingMode,
+        connection_list: &[Connection],
+    ) -> Vec<LabelRecord> {
         connection_list
             .iter()
-            .map(|conn| {
-                let source = format!(
-                    "{}-{}-{}",
-                    conn.src_component, conn.src_terminal_block, conn.src_terminal
-                );
-                let destination = format!(
-                    "{}-{}-{}",
-                    conn.dst_component, conn.dst_terminal_block, conn.dst_terminal
-                );
-                format!("{}|{}", source, destination)
+            .flat_map(|conn| {
+                let labels = mode.labels(self.format_source(conn), self.format_destination(conn));
+                self.options()
+                    .wire_number_display
+                    .apply(conn.wire_number.as_deref(), labels)
             })
-            .collect::<Vec<_>>()
-            .join("\n")
+            .collect()
+    }
+}
+
+// `K1-X1-3` unless another designation pattern is set
+pub struct ExportWireToCSVStrategy {
+    pub options: ExportOptions,
+    pub designation: DesignationFormatter,
+}
+
+// `K1-X1 [3]` unless another designation pattern is set
+pub struct ExportCableToCSVStrategy {
+    pub options: ExportOptions,
+    pub designation: DesignationFormatter,
+}
+
+impl ExportWireToCSVStrategy {
+    pub fn new(options: ExportOptions) -> Self {
+        Self {
+            options,
+            designation: DesignationFormatter::default(),
+        }
+    }
+
+    pub fn with_designation(mut self, designation: DesignationFormatter) -> Self {
+        self.designation = designation;
+        self
+    }
+}
+
+impl Default for ExportWireToCSVStrategy {
+    fn default() -> Self {
+        Self::new(ExportOptions::default())
+    }
+}
+
+impl ExportCableToCSVStrategy {
+    pub fn new(options: ExportOptions) -> Self {
+        Self {
+            options,
+            designation: DesignationFormatter::cable(),
+        }
+    }
+
+    pub fn with_designation(mut self, designation: DesignationFormatter) -> Self {
+        self.designation = designation;
+        self
+    }
+}
+
+impl Default for ExportCableToCSVStrategy {
+    fn default() -> Self {
+        Self::new(ExportOptions::default())
+    }
+}
+
+impl ExportToCsvStrategy for ExportWireToCSVStrategy {
+    fn options(&self) -> &ExportOptions {
+        &self.options
+    }
+
+    fn designation(&self) -> &DesignationFormatter {
+        &self.designation
     }
 }
 
 impl ExportToCsvStrategy for ExportCableToCSVStrategy {
-    fn export_to_csv(
+    fn options(&self) -> &ExportOptions {
+        &self.options
+    }
+
+    fn designation(&self) -> &DesignationFormatter {
+        &self.designation
+    }
+}
+
+impl ExportCableToCSVStrategy {
+    // Two labels per cable, one for each end (tag, near end, far end), followed by one label per
+    // assigned core (tag:core, source, destination)
+    pub fn cable_label_records(
         &self,
-        mut file_path: PathBuf,
+        cables: &[Cable],
         connection_list: &[Connection],
-    ) -> Result<(), Box<dyn Error>> {
-        if file_path.extension().unwrap_or_default() != "csv" {
-            file_path.set_extension("csv");
+    ) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        for cable in cables {
+            let (source_end, destination_end) = cable.end_components(connection_list);
+            let source_end = source_end.join(", ");
+            let destination_end = destination_end.join(", ");
+            records.push(vec![
+                cable.tag.clone(),
+                source_end.clone(),
+                destination_end.clone(),
+            ]);
+            records.push(vec![cable.tag.clone(), destination_end, source_end]);
+
+            for (core, conn) in cable.assigned_cores(connection_list) {
+                records.push(vec![
+                    format!("{}:{}", cable.tag, core.designation),
+                    self.format_source(conn),
+                    self.format_destination(conn),
+                ]);
+            }
         }
-        let file = File::create(file_path)?;
-        let mut wtr = csv::WriterBuilder::new().delimiter(b'|').from_writer(file);
+        records
+    }
 
-        for conn in connection_list {
-            let source = format!(
-                "{}-{} [{}]",
-                conn.src_component, conn.src_terminal_block, conn.src_terminal
-            );
-            let destination = format!(
-                "{}-{} [{}]",
-                conn.dst_component, conn.dst_terminal_block, conn.dst_terminal
-            );
-            wtr.write_record(&[&source, &destination])?;
+    pub fn export_cable_labels_to_csv(
+        &self,
+        file_path: PathBuf,
+        cables: &[Cable],
+        connection_list: &[Connection],
+    ) -> Result<(), Box<dyn Error>> {
+        let file = self.options.create_file(file_path)?;
+        let mut wtr = self.options.writer(file);
+
+        for record in self.cable_label_records(cables, connection_list) {
+            wtr.write_record(&record)?;
         }
         wtr.flush()?;
-        println!("Successfully exported wires");
+        println!("Successfully exported cables");
         Ok(())
     }
 
-    fn generate_csv_string(&self, connection_list: &[Connection]) -> String {
-        connection_list
-            .iter()
-            .map(|conn| {
-                let source = format!(
-                    "{}-{} [{}]",
-                    conn.src_component, conn.src_terminal_block, conn.src_terminal
-                );
-                let destination = format!(
-                    "{}-{} [{}]",
-                    conn.dst_component, conn.dst_terminal_block, conn.dst_terminal
-                );
-                format!("{}|{}", source, destination)
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+    pub fn generate_cable_label_string(
+        &self,
+        cables: &[Cable],
+        connection_list: &[Connection],
+    ) -> String {
+        records_to_string(
+            &self.options,
+            self.cable_label_records(cables, connection_list),
+        )
+    }
+}
+
+// Bridge order list: kind, poles, part number, quantity. Delimiter, quoting, header, line ending
+// and BOM follow `options`; the wire columns do not apply. Bridges are never part of the wire or
+// cable label exports.
+pub fn export_bridge_report_to_csv(
+    file_path: PathBuf,
+    bridges: &[Bridge],
+    options: &ExportOptions,
+) -> Result<(), Box<dyn Error>> {
+    let file = options.create_file(file_path)?;
+    let mut wtr = options.writer(file);
+
+    if options.include_header {
+        wtr.write_record(["kind", "poles", "part_number", "quantity"])?;
+    }
+    for accessory in bridge_accessory_report(bridges) {
+        wtr.write_record(&[
+            accessory.kind.to_string(),
+            accessory.pole_count.to_string(),
+            accessory.part_number.unwrap_or_default(),
+            accessory.quantity.to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    println!("Successfully exported bridge report");
+    Ok(())
+}
+
+// Rows joined with the configured delimiter and line ending, without a trailing line ending
+fn records_to_string(options: &ExportOptions, records: Vec<Vec<String>>) -> String {
+    let mut wtr = options.writer(Vec::new());
+    for record in &records {
+        // Writing to memory cannot fail
+        wtr.write_record(record).expect("in-memory CSV write");
     }
+    let data = wtr.into_inner().expect("in-memory CSV flush");
+    let text = String::from_utf8_lossy(&data);
+    text.strip_suffix(options.line_ending.as_str())
+        .unwrap_or(&text)
+        .to_string()
 }
@@ -0,0 +1,358 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::command::command::{AddConnectionsCommand, CommandError};
+use crate::command::command_manager::CommandManager;
+use crate::models::catalog::CatalogPolicy;
+use crate::models::connection::Connection;
+use crate::models::connection_manager::{ConnectionManager, ConnectionManagerError};
+use crate::models::designation::DesignationFormatter;
+use crate::utils::export_options::{ExportColumn, ExportOptions};
+use crate::utils::range_expansion::expand_connection;
+
+// The three layouts written by the exporters
+pub enum ImportFormat {
+    // `K1-X1-3|K2-X2-4[|attributes...]` from `ExportWireToCSVStrategy`
+    Wire,
+    // `K1-X1 [3]|K2-X2 [4][|attributes...]` from `ExportCableToCSVStrategy`
+    Cable,
+    // Headed, comma separated rows from `ConnectionManager::generate_csv_string`
+    Serde,
+}
+
+impl ImportFormat {
+    pub fn strategy(&self, options: ImportOptions) -> Box<dyn ImportFromCsvStrategy> {
+        match self {
+            ImportFormat::Wire => Box::new(ImportWireFromCSVStrategy { options }),
+            ImportFormat::Cable => Box::new(ImportCableFromCSVStrategy { options }),
+            ImportFormat::Serde => Box::new(ImportSerdeFromCSVStrategy { options }),
+        }
+    }
+}
+
+// How the file to import is laid out, normally the export layout (see
+// `ConnectionManager::import_options`)
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub delimiter: u8,
+    // The first row then names the columns, in any order
+    pub has_header: bool,
+    // Column order of files without a header
+    pub columns: Vec<ExportColumn>,
+    // Pattern of the source and destination columns; `None` keeps the layout's own pattern
+    pub designation: Option<DesignationFormatter>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b'|',
+            has_header: false,
+            columns: ExportColumn::DEFAULT.to_vec(),
+            designation: None,
+        }
+    }
+}
+
+impl ImportOptions {
+    // Reads back files written with `options` and `designation`. Files written with a two-label
+    // labeling mode hold every wire twice, once per end.
+    pub fn from_export(options: &ExportOptions, designation: Option<DesignationFormatter>) -> Self {
+        Self {
+            delimiter: options.delimiter,
+            has_header: options.include_header,
+            columns: options.columns.clone(),
+            designation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRowError {
+    pub line: u64,
+    pub message: String,
+}
+
+impl fmt::Display for ImportRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: Vec<Connection>,
+    pub errors: Vec<ImportRowError>,
+}
+
+// A successfully parsed row together with its line number
+pub type ParsedRow = (u64, Connection);
+
+pub trait ImportFromCsvStrategy {
+    fn parse_csv_str(&self, contents: &str) -> (Vec<ParsedRow>, Vec<ImportRowError>);
+
+    fn import_from_csv(
+        &self,
+        file_path: PathBuf,
+        connection_manager: &Arc<Mutex<ConnectionManager>>,
+        command_manager: &mut CommandManager,
+    ) -> Result<ImportReport, Box<dyn Error>> {
+        let contents = fs::read_to_string(file_path)?;
+        Ok(self.import_from_csv_str(&contents, connection_manager, command_manager)?)
+    }
+
+    // Adds the wires `prepare_import` accepts as one undo step of `command_manager`. Rows it
+    // rejects are reported; if adding fails anyway (e.g. the project cannot be saved), nothing
+    // is imported.
+    fn import_from_csv_str(
+        &self,
+        contents: &str,
+        connection_manager: &Arc<Mutex<ConnectionManager>>,
+        command_manager: &mut CommandManager,
+    ) -> Result<ImportReport, CommandError> {
+        let mut report = self.prepare_import(contents, &connection_manager.lock().unwrap());
+        if report.imported.is_empty() {
+            return Ok(report);
+        }
+        let uuids: Vec<Uuid> = report.imported.iter().map(|c| c.uuid).collect();
+        command_manager.execute_command(Box::new(AddConnectionsCommand::new(
+            connection_manager.clone(),
+            std::mem::take(&mut report.imported),
+        )))?;
+        // As stored, with their wire numbers if numbered automatically
+        let mgr = connection_manager.lock().unwrap();
+        report.imported = uuids
+            .iter()
+            .filter_map(|uuid| mgr.find_connection(*uuid).cloned())
+            .collect();
+        Ok(report)
+    }
+
+    // Parses every row and expands terminal ranges (`X1-XT-1..16` in the wire layout, where `-`
+    // already separates the parts). Rows that cannot be read, that duplicate a wire of the
+    // project or of an earlier row, or that the catalog rejects are reported; the others are
+    // returned in `imported`, ready to be added.
+    fn prepare_import(
+        &self,
+        contents: &str,
+        connection_manager: &ConnectionManager,
+    ) -> ImportReport {
+        let (rows, errors) = self.parse_csv_str(contents);
+        let mut report = ImportReport {
+            imported: Vec::new(),
+            errors,
+        };
+        let mut expanded = Vec::new();
+        for (line, connection) in rows {
+            match expand_connection(&connection) {
+                Ok(connections) => expanded.extend(connections.into_iter().map(|c| (line, c))),
+                Err(e) => report.errors.push(ImportRowError {
+                    line,
+                    message: e.to_string(),
+                }),
+            }
+        }
+        for (line, connection) in expanded {
+            let message = if connection_manager.connections.contains(&connection)
+                || report.imported.contains(&connection)
+            {
+                Some("duplicate connection".to_string())
+            } else if connection_manager.get_catalog_policy() == CatalogPolicy::Reject {
+                connection_manager
+                    .catalog_violations(&connection)
+                    .into_iter()
+                    .next()
+                    .map(|violation| ConnectionManagerError::from(violation).to_string())
+            } else {
+                None
+            };
+            match message {
+                Some(message) => report.errors.push(ImportRowError { line, message }),
+                None => report.imported.push(connection),
+            }
+        }
+        report.errors.sort_by_key(|e| e.line);
+        report
+    }
+}
+
+pub struct ImportWireFromCSVStrategy {
+    pub options: ImportOptions,
+}
+
+pub struct ImportCableFromCSVStrategy {
+    pub options: ImportOptions,
+}
+
+pub struct ImportSerdeFromCSVStrategy {
+    pub options: ImportOptions,
+}
+
+impl ImportFromCsvStrategy for ImportWireFromCSVStrategy {
+    // `K1-X1-3` unless another designation pattern is set. The default pattern writes an end
+    // without a terminal, `K1-X1`, like one without a terminal block, so two-part ends are read
+    // as component and terminal; the cable pattern keeps them apart.
+    fn parse_csv_str(&self, contents: &str) -> (Vec<ParsedRow>, Vec<ImportRowError>) {
+        let designation = self.options.designation.clone().unwrap_or_default();
+        parse_designation_rows(&self.options, &designation, contents)
+    }
+}
+
+impl ImportFromCsvStrategy for ImportCableFromCSVStrategy {
+    // `K1-X1 [3]` unless another designation pattern is set
+    fn parse_csv_str(&self, contents: &str) -> (Vec<ParsedRow>, Vec<ImportRowError>) {
+        let designation = self
+            .options
+            .designation
+            .clone()
+            .unwrap_or_else(DesignationFormatter::cable);
+        parse_designation_rows(&self.options, &designation, contents)
+    }
+}
+
+impl ImportFromCsvStrategy for ImportSerdeFromCSVStrategy {
+    fn parse_csv_str(&self, contents: &str) -> (Vec<ParsedRow>, Vec<ImportRowError>) {
+        let mut rows = Vec::new();
+        let mut errors = Vec::new();
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(self.options.delimiter)
+            .from_reader(without_bom(contents).as_bytes());
+        let headers = match rdr.headers() {
+            Ok(headers) => headers.clone(),
+            Err(e) => {
+                errors.push(ImportRowError {
+                    line: 1,
+                    message: e.to_string(),
+                });
+                return (rows, errors);
+            }
+        };
+        for result in rdr.records() {
+            let parsed = result.and_then(|record| {
+                let line = record.position().map(|p| p.line()).unwrap_or_default();
+                record
+                    .deserialize::<Connection>(Some(&headers))
+                    .map(|connection| (line, connection))
+            });
+            match parsed {
+                Ok((line, mut connection)) => {
+                    // Imported wires are new wires in this project
+                    connection.uuid = Uuid::new_v4();
+                    rows.push((line, connection));
+                }
+                Err(e) => errors.push(ImportRowError {
+                    line: e.position().map(|p| p.line()).unwrap_or_default(),
+                    message: e.to_string(),
+                }),
+            }
+        }
+        (rows, errors)
+    }
+}
+
+// Shared reader for the wire and cable layouts, whose columns are those of `ExportColumn`
+fn parse_designation_rows(
+    options: &ImportOptions,
+    designation: &DesignationFormatter,
+    contents: &str,
+) -> (Vec<ParsedRow>, Vec<ImportRowError>) {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_header)
+        .flexible(true)
+        .from_reader(without_bom(contents).as_bytes());
+
+    let columns = if options.has_header {
+        let header = rdr.headers().map_err(|e| e.to_string()).and_then(|header| {
+            header
+                .iter()
+                .map(|name| name.parse::<ExportColumn>().map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        match header {
+            Ok(columns) => columns,
+            Err(message) => {
+                errors.push(ImportRowError { line: 1, message });
+                return (rows, errors);
+            }
+        }
+    } else {
+        options.columns.clone()
+    };
+
+    for result in rdr.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(ImportRowError {
+                    line: e.position().map(|p| p.line()).unwrap_or_default(),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        match parse_record(&record, &columns, designation) {
+            Ok(connection) => rows.push((line, connection)),
+            Err(message) => errors.push(ImportRowError { line, message }),
+        }
+    }
+    (rows, errors)
+}
+
+fn parse_record(
+    record: &csv::StringRecord,
+    columns: &[ExportColumn],
+    designation: &DesignationFormatter,
+) -> Result<Connection, String> {
+    if record.len() > columns.len() {
+        return Err(format!(
+            "expected at most {} columns, found {}",
+            columns.len(),
+            record.len()
+        ));
+    }
+    let mut connection = Connection::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    );
+    let values = || columns.iter().zip(record.iter().map(str::trim));
+    // Source and destination first, so separate part columns can refine them
+    for (column, value) in values().filter(|(_, value)| !value.is_empty()) {
+        match column {
+            ExportColumn::Source => {
+                connection
+                    .set_source_terminal(designation.parse(value).map_err(|e| e.to_string())?);
+            }
+            ExportColumn::Destination => {
+                connection
+                    .set_destination_terminal(designation.parse(value).map_err(|e| e.to_string())?);
+            }
+            _ => {}
+        }
+    }
+    for (column, value) in values() {
+        column
+            .set_value(&mut connection, value)
+            .map_err(|e| e.to_string())?;
+    }
+    if connection.is_empty() {
+        return Err("empty connection".to_string());
+    }
+    Ok(connection)
+}
+
+// Excel and `ExportOptions::utf8_bom` start files with a byte order mark
+fn without_bom(contents: &str) -> &str {
+    contents.strip_prefix('\u{feff}').unwrap_or(contents)
+}
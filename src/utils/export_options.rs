@@ -0,0 +1,386 @@
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use thiserror::Error;
+
+use crate::config::settings::Settings;
+use crate::models::connection::Connection;
+use crate::models::wire_attributes::{parse_length_mm, WireAttributeError};
+use crate::utils::label_exporting_strategy::{LabelingMode, WireNumberDisplay};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ExportOptionsError {
+    #[error("Invalid delimiter: {0:?}")]
+    InvalidDelimiter(String),
+    #[error("Invalid quote style: {0}")]
+    InvalidQuoteStyle(String),
+    #[error("Invalid line ending: {0}")]
+    InvalidLineEnding(String),
+    #[error("Invalid column: {0}")]
+    InvalidColumn(String),
+    #[error("Invalid boolean value: {0}")]
+    InvalidBool(String),
+    #[error("Invalid labeling mode: {0}")]
+    InvalidLabelingMode(String),
+    #[error("Invalid wire number display: {0}")]
+    InvalidWireNumberDisplay(String),
+    #[error("Unknown label exporter: {0}")]
+    InvalidLabelExporter(String),
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+    #[error("Invalid ZPL font: {0}")]
+    InvalidZplFont(String),
+    #[error("Invalid ZPL orientation: {0}")]
+    InvalidZplOrientation(String),
+    #[error("No label printer address is set")]
+    MissingPrinterAddress,
+    #[error("Unknown label sheet template: {0}")]
+    InvalidSheetTemplate(String),
+    #[error("Minimum font size {0} is larger than the maximum {1}")]
+    InvalidFontSizeRange(f32, f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum QuoteStyle {
+    // Quote only fields containing the delimiter, quotes or line breaks
+    #[default]
+    Necessary,
+    Always,
+    // Quote every field that is not a number (what many label printer programs expect)
+    NonNumeric,
+    // Never quote; fields are written as they are
+    Never,
+}
+
+impl FromStr for QuoteStyle {
+    type Err = ExportOptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "necessary" => Ok(QuoteStyle::Necessary),
+            "always" => Ok(QuoteStyle::Always),
+            "non_numeric" | "nonnumeric" => Ok(QuoteStyle::NonNumeric),
+            "never" => Ok(QuoteStyle::Never),
+            other => Err(ExportOptionsError::InvalidQuoteStyle(other.to_string())),
+        }
+    }
+}
+
+impl From<QuoteStyle> for csv::QuoteStyle {
+    fn from(value: QuoteStyle) -> Self {
+        match value {
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    // Windows line endings, for Excel
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    pub fn terminator(&self) -> csv::Terminator {
+        match self {
+            LineEnding::Lf => csv::Terminator::Any(b'\n'),
+            LineEnding::CrLf => csv::Terminator::CRLF,
+        }
+    }
+}
+
+impl FromStr for LineEnding {
+    type Err = ExportOptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "lf" | "unix" => Ok(LineEnding::Lf),
+            "crlf" | "windows" => Ok(LineEnding::CrLf),
+            other => Err(ExportOptionsError::InvalidLineEnding(other.to_string())),
+        }
+    }
+}
+
+// A column of the wire and cable exports
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportColumn {
+    // Full source designation, formatted by the export strategy
+    Source,
+    // Full destination designation, formatted by the export strategy
+    Destination,
+    SourceFunction,
+    SourceLocation,
+    SourceComponent,
+    SourceTerminalBlock,
+    SourceTerminal,
+    DestinationFunction,
+    DestinationLocation,
+    DestinationComponent,
+    DestinationTerminalBlock,
+    DestinationTerminal,
+    CrossSection,
+    Color,
+    LengthMm,
+    WireType,
+    SignalName,
+    WireNumber,
+}
+
+impl ExportColumn {
+    // Source and destination only, so projects without wire attributes get no empty columns;
+    // attribute columns are added through the `csv_columns` setting
+    pub const DEFAULT: [ExportColumn; 2] = [ExportColumn::Source, ExportColumn::Destination];
+
+    // Name used in the settings file and the header row
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportColumn::Source => "source",
+            ExportColumn::Destination => "destination",
+            ExportColumn::SourceFunction => "src_function",
+            ExportColumn::SourceLocation => "src_location",
+            ExportColumn::SourceComponent => "src_component",
+            ExportColumn::SourceTerminalBlock => "src_terminal_block",
+            ExportColumn::SourceTerminal => "src_terminal",
+            ExportColumn::DestinationFunction => "dst_function",
+            ExportColumn::DestinationLocation => "dst_location",
+            ExportColumn::DestinationComponent => "dst_component",
+            ExportColumn::DestinationTerminalBlock => "dst_terminal_block",
+            ExportColumn::DestinationTerminal => "dst_terminal",
+            ExportColumn::CrossSection => "cross_section",
+            ExportColumn::Color => "color",
+            ExportColumn::LengthMm => "length_mm",
+            ExportColumn::WireType => "wire_type",
+            ExportColumn::SignalName => "signal_name",
+            ExportColumn::WireNumber => "wire_number",
+        }
+    }
+
+    // Value of every column except `Source` and `Destination`, which depend on the strategy
+    pub fn value(&self, conn: &Connection) -> String {
+        let [cross_section, color, length_mm, wire_type, signal_name] = conn.attribute_columns();
+        match self {
+            ExportColumn::Source | ExportColumn::Destination => String::new(),
+            ExportColumn::SourceFunction => conn.src_function.clone(),
+            ExportColumn::SourceLocation => conn.src_location.clone(),
+            ExportColumn::SourceComponent => conn.src_component.clone(),
+            ExportColumn::SourceTerminalBlock => conn.src_terminal_block.clone(),
+            ExportColumn::SourceTerminal => conn.src_terminal.clone(),
+            ExportColumn::DestinationFunction => conn.dst_function.clone(),
+            ExportColumn::DestinationLocation => conn.dst_location.clone(),
+            ExportColumn::DestinationComponent => conn.dst_component.clone(),
+            ExportColumn::DestinationTerminalBlock => conn.dst_terminal_block.clone(),
+            ExportColumn::DestinationTerminal => conn.dst_terminal.clone(),
+            ExportColumn::CrossSection => cross_section,
+            ExportColumn::Color => color,
+            ExportColumn::LengthMm => length_mm,
+            ExportColumn::WireType => wire_type,
+            ExportColumn::SignalName => signal_name,
+            ExportColumn::WireNumber => conn.wire_number.clone().unwrap_or_default(),
+        }
+    }
+
+    // The inverse of `value`, used when importing. `Source` and `Destination` are left to the
+    // importer and empty values clear the attribute.
+    pub fn set_value(&self, conn: &mut Connection, value: &str) -> Result<(), WireAttributeError> {
+        let text = || (!value.is_empty()).then(|| value.to_string());
+        match self {
+            ExportColumn::Source | ExportColumn::Destination => {}
+            ExportColumn::SourceFunction => conn.src_function = value.to_string(),
+            ExportColumn::SourceLocation => conn.src_location = value.to_string(),
+            ExportColumn::SourceComponent => conn.src_component = value.to_string(),
+            ExportColumn::SourceTerminalBlock => conn.src_terminal_block = value.to_string(),
+            ExportColumn::SourceTerminal => conn.src_terminal = value.to_string(),
+            ExportColumn::DestinationFunction => conn.dst_function = value.to_string(),
+            ExportColumn::DestinationLocation => conn.dst_location = value.to_string(),
+            ExportColumn::DestinationComponent => conn.dst_component = value.to_string(),
+            ExportColumn::DestinationTerminalBlock => conn.dst_terminal_block = value.to_string(),
+            ExportColumn::DestinationTerminal => conn.dst_terminal = value.to_string(),
+            ExportColumn::CrossSection => {
+                conn.cross_section = text().map(|v| v.parse()).transpose()?
+            }
+            ExportColumn::Color => conn.color = text().map(|v| v.parse()).transpose()?,
+            ExportColumn::LengthMm => {
+                conn.length_mm = text().map(|v| parse_length_mm(&v)).transpose()?
+            }
+            ExportColumn::WireType => conn.wire_type = text(),
+            ExportColumn::SignalName => conn.signal_name = text(),
+            ExportColumn::WireNumber => conn.wire_number = text(),
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ExportColumn {
+    type Err = ExportOptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        [
+            ExportColumn::Source,
+            ExportColumn::Destination,
+            ExportColumn::SourceFunction,
+            ExportColumn::SourceLocation,
+            ExportColumn::SourceComponent,
+            ExportColumn::SourceTerminalBlock,
+            ExportColumn::SourceTerminal,
+            ExportColumn::DestinationFunction,
+            ExportColumn::DestinationLocation,
+            ExportColumn::DestinationComponent,
+            ExportColumn::DestinationTerminalBlock,
+            ExportColumn::DestinationTerminal,
+            ExportColumn::CrossSection,
+            ExportColumn::Color,
+            ExportColumn::LengthMm,
+            ExportColumn::WireType,
+            ExportColumn::SignalName,
+            ExportColumn::WireNumber,
+        ]
+        .into_iter()
+        .find(|column| column.name() == name)
+        .ok_or(ExportOptionsError::InvalidColumn(name))
+    }
+}
+
+// How the CSV exporters lay out their files
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    pub delimiter: u8,
+    pub quote_style: QuoteStyle,
+    pub include_header: bool,
+    pub line_ending: LineEnding,
+    // Start files with a UTF-8 byte order mark so Excel detects the encoding
+    pub utf8_bom: bool,
+    // Columns to write, in order
+    pub columns: Vec<ExportColumn>,
+    // With a two-label mode every connection gives two rows; `Source` and `Destination` then
+    // hold the first and second line of each label
+    pub labeling_mode: LabelingMode,
+    // Applies to labels only; CSV files get the number through the `WireNumber` column
+    pub wire_number_display: WireNumberDisplay,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b'|',
+            quote_style: QuoteStyle::default(),
+            include_header: false,
+            line_ending: LineEnding::default(),
+            utf8_bom: false,
+            columns: ExportColumn::DEFAULT.to_vec(),
+            labeling_mode: LabelingMode::default(),
+            wire_number_display: WireNumberDisplay::default(),
+        }
+    }
+}
+
+impl ExportOptions {
+    // Reads `default_csv_delimiter`, `csv_quote_style`, `csv_include_header`,
+    // `csv_line_ending`, `csv_utf8_bom`, `csv_columns`, `labeling_mode` and
+    // `wire_number_display`. Empty settings keep their defaults.
+    pub fn from_settings(settings: &Settings) -> Result<Self, ExportOptionsError> {
+        let setting = |key: &str| settings.get(key).unwrap_or_default().trim();
+        let mut options = Self::default();
+
+        // Not trimmed, so a literal tab or space can be used
+        let delimiter = settings.get("default_csv_delimiter").unwrap_or_default();
+        if !delimiter.is_empty() {
+            options.delimiter = parse_delimiter(delimiter)?;
+        }
+        options.quote_style = setting("csv_quote_style").parse()?;
+        options.include_header = parse_bool(setting("csv_include_header"))?;
+        options.line_ending = setting("csv_line_ending").parse()?;
+        options.utf8_bom = parse_bool(setting("csv_utf8_bom"))?;
+        if !setting("csv_columns").is_empty() {
+            options.columns = setting("csv_columns")
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<_, _>>()?;
+        }
+        options.labeling_mode = setting("labeling_mode").parse()?;
+        options.wire_number_display = setting("wire_number_display").parse()?;
+        Ok(options)
+    }
+
+    pub fn header(&self) -> Vec<&'static str> {
+        self.columns.iter().map(|column| column.name()).collect()
+    }
+
+    pub fn writer<W: Write>(&self, wtr: W) -> csv::Writer<W> {
+        csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .quote_style(self.quote_style.into())
+            .terminator(self.line_ending.terminator())
+            .flexible(true)
+            .from_writer(wtr)
+    }
+
+    // Creates the export file, adding a `.csv` extension when missing and writing the BOM if
+    // requested
+    pub fn create_file(&self, mut file_path: PathBuf) -> io::Result<File> {
+        if file_path.extension().unwrap_or_default() != "csv" {
+            file_path.set_extension("csv");
+        }
+        let mut file = File::create(file_path)?;
+        if self.utf8_bom {
+            file.write_all(UTF8_BOM)?;
+        }
+        Ok(file)
+    }
+}
+
+// A single character, or `tab`/`\t` for tab separated files
+fn parse_delimiter(value: &str) -> Result<u8, ExportOptionsError> {
+    match value {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        _ if value.len() == 1 => Ok(value.as_bytes()[0]),
+        _ => Err(ExportOptionsError::InvalidDelimiter(value.to_string())),
+    }
+}
+
+pub(crate) fn parse_bool(value: &str) -> Result<bool, ExportOptionsError> {
+    match value.to_lowercase().as_str() {
+        "" | "false" | "no" | "0" => Ok(false),
+        "true" | "yes" | "1" => Ok(true),
+        other => Err(ExportOptionsError::InvalidBool(other.to_string())),
+    }
+}
+
+// An empty value keeps the default and gives `None`
+pub(crate) fn parse_number<T: FromStr>(value: &str) -> Result<Option<T>, ExportOptionsError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| ExportOptionsError::InvalidNumber(value.to_string()))
+}
+
+// Like `parse_number`, but zero and negative values are rejected too
+pub(crate) fn parse_positive<T: FromStr + PartialOrd + Default>(
+    value: &str,
+) -> Result<Option<T>, ExportOptionsError> {
+    match parse_number::<T>(value)? {
+        Some(number) if number.partial_cmp(&T::default()) != Some(Ordering::Greater) => {
+            Err(ExportOptionsError::InvalidNumber(value.to_string()))
+        }
+        number => Ok(number),
+    }
+}
@@ -6,6 +6,8 @@ use thiserror::Error;
 use tracing::{debug, error, info};
 
 use crate::models::connection::Connection;
+use crate::models::project::Project;
+use crate::utils::migrations;
 
 // Define a custom error type that can represent errors from different sources
 #[derive(Debug, Error)]
@@ -16,6 +18,10 @@ pub enum FileHandlerError {
     Json(#[from] serde_json::Error),
     #[error("Path not set")]
     PathNotSet,
+    #[error("Unsupported project format version {0}")]
+    UnsupportedFormatVersion(u32),
+    #[error("Malformed project file: {0}")]
+    MalformedProject(String),
 }
 
 pub struct FileHandler {
@@ -28,6 +34,12 @@ impl FileHandler {
     }
 
     pub fn load_wires(&self) -> Result<Vec<Connection>, FileHandlerError> {
+        Ok(self.load_project()?.connections)
+    }
+
+    // Loads a project file of any known format version, migrating it to the current one.
+    // Legacy files holding a bare array of connections are accepted as well.
+    pub fn load_project(&self) -> Result<Project, FileHandlerError> {
         let file_path = self
             .file_path
             .as_ref()
@@ -44,7 +56,7 @@ impl FileHandler {
 
         info!(contents);
 
-        let connections: Vec<Connection> = serde_json::from_str(&contents).map_err(|e| {
+        let document: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
             error!(
                 "Error deserializing JSON: {}. Contents were: {}",
                 e, contents
@@ -52,7 +64,13 @@ impl FileHandler {
             FileHandlerError::from(e)
         })?;
 
-        Ok(connections)
+        let project: Project = serde_json::from_value(migrations::migrate_to_current(document)?)
+            .map_err(|e| {
+                error!("Error deserializing project: {}", e);
+                FileHandlerError::from(e)
+            })?;
+
+        Ok(project)
     }
 
     pub fn save(&self, data: &serde_json::Value) -> Result<(), FileHandlerError> {
@@ -74,4 +92,12 @@ impl FileHandler {
         serde_json::to_writer_pretty(file, data)?;
         Ok(())
     }
+
+    pub fn save_project_to_path(
+        &self,
+        project: &Project,
+        file_path: &PathBuf,
+    ) -> Result<(), FileHandlerError> {
+        self.save_to_path(&serde_json::to_value(project)?, file_path)
+    }
 }
@@ -0,0 +1,70 @@
+// Advances a terminal designation by `step`, for entering consecutive terminals quickly:
+//
+// - a trailing number is counted up, keeping its zero padding: `3` → `4`, `03` → `04`,
+//   `A9` → `A10`, `L1.09` → `L1.10`
+// - otherwise trailing letters are counted like spreadsheet columns, keeping their case:
+//   `A` → `B`, `Z` → `AA`, `Xb` → `Xc`
+//
+// Returns `None` for values that cannot be counted, e.g. empty ones or ones ending in `+`, and
+// when the count no longer fits in 64 bits.
+pub fn increment_terminal(value: &str, step: u32) -> Option<String> {
+    let value = value.trim();
+    let digits = trailing_len(value, |c| c.is_ascii_digit());
+    if digits > 0 {
+        let (prefix, number) = value.split_at(value.len() - digits);
+        let next = number.parse::<u64>().ok()?.checked_add(step as u64)?;
+        return Some(format!("{}{:0width$}", prefix, next, width = digits));
+    }
+
+    let letters = trailing_len(value, |c| c.is_ascii_alphabetic());
+    if letters > 0 {
+        let (prefix, sequence) = value.split_at(value.len() - letters);
+        return Some(format!("{}{}", prefix, add_to_letters(sequence, step)?));
+    }
+    None
+}
+
+// Number of bytes in the run of matching ASCII characters at the end of `value`
+fn trailing_len(value: &str, matches: impl Fn(char) -> bool) -> usize {
+    value.chars().rev().take_while(|c| matches(*c)).count()
+}
+
+// Counts `sequence` up by `step` in bijective base 26 (`A` = 1, `Z` = 26, `AA` = 27), so large
+// steps cost no more than small ones: `A` + 1 → `B`, `Z` + 1 → `AA`, `AZ` + 1 → `BA`. Each
+// letter keeps the case it had; letters added in front take the case of the first one.
+fn add_to_letters(sequence: &str, step: u32) -> Option<String> {
+    let mut count: u64 = 0;
+    for c in sequence.bytes() {
+        let digit = u64::from(c.to_ascii_uppercase() - b'A' + 1);
+        count = count.checked_mul(26)?.checked_add(digit)?;
+    }
+    let mut count = count.checked_add(u64::from(step))?;
+
+    // Least significant letter first
+    let mut letters = Vec::new();
+    while count > 0 {
+        count -= 1;
+        letters.push(b'A' + (count % 26) as u8);
+        count /= 26;
+    }
+    let lowercase: Vec<bool> = sequence
+        .bytes()
+        .rev()
+        .map(|c| c.is_ascii_lowercase())
+        .collect();
+    let first_lowercase = sequence.as_bytes()[0].is_ascii_lowercase();
+    Some(
+        letters
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, letter)| {
+                if lowercase.get(i).copied().unwrap_or(first_lowercase) {
+                    letter.to_ascii_lowercase() as char
+                } else {
+                    *letter as char
+                }
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::models::connection::Connection;
+use crate::utils::csv_exporting_strategy::ExportToCsvStrategy;
+use crate::utils::export_options::ExportOptionsError;
+
+use crate::utils::marking_system_exporting_strategy::MarkingSystemLayout;
+use crate::utils::pdf_exporting_strategy::PdfExporter;
+use crate::utils::zpl_exporting_strategy::ZplExporter;
+
+// The text printed on one label, one entry per line. Produced by the wire and cable export
+// strategies and shared by every label exporter.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LabelRecord {
+    pub lines: Vec<String>,
+    // Set by `WireNumberDisplay::NumberAndAddress`. Kept apart from the address lines so that
+    // column layouts can give it a field of its own.
+    pub wire_number: Option<String>,
+}
+
+impl LabelRecord {
+    pub fn new(lines: Vec<String>) -> Self {
+        Self {
+            lines,
+            wire_number: None,
+        }
+    }
+
+    // What a free-form label prints: the wire number, if any, above the address lines
+    pub fn printed_lines(&self) -> Vec<&str> {
+        self.wire_number
+            .iter()
+            .chain(self.lines.iter())
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn line(&self, index: usize) -> &str {
+        self.lines
+            .get(index)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+// Separator between the two addresses in `LabelingMode::BothAddresses`
+pub const BOTH_ADDRESSES_SEPARATOR: &str = " / ";
+
+// Which labels a wire gets and what they show
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LabelingMode {
+    // One label: source on the first line, destination on the second
+    #[default]
+    Single,
+    // One label per wire end, showing the near address first and the far address second
+    NearFar,
+    // One label per wire end, showing only the far address
+    FarOnly,
+    // One label per wire end, both showing "source / destination"
+    BothAddresses,
+}
+
+impl LabelingMode {
+    // Label records for a wire, source end first
+    pub fn labels(&self, source: String, destination: String) -> Vec<LabelRecord> {
+        match self {
+            LabelingMode::Single => vec![LabelRecord::new(vec![source, destination])],
+            LabelingMode::NearFar => vec![
+                LabelRecord::new(vec![source.clone(), destination.clone()]),
+                LabelRecord::new(vec![destination, source]),
+            ],
+            LabelingMode::FarOnly => vec![
+                LabelRecord::new(vec![destination]),
+                LabelRecord::new(vec![source]),
+            ],
+            LabelingMode::BothAddresses => {
+                let text = format!("{}{}{}", source, BOTH_ADDRESSES_SEPARATOR, destination);
+                vec![
+                    LabelRecord::new(vec![text.clone()]),
+                    LabelRecord::new(vec![text]),
+                ]
+            }
+        }
+    }
+}
+
+impl FromStr for LabelingMode {
+    type Err = ExportOptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "single" => Ok(LabelingMode::Single),
+            "near_far" => Ok(LabelingMode::NearFar),
+            "far_only" => Ok(LabelingMode::FarOnly),
+            "both_addresses" => Ok(LabelingMode::BothAddresses),
+            other => Err(ExportOptionsError::InvalidLabelingMode(other.to_string())),
+        }
+    }
+}
+
+// Whether labels show the wire number instead of or alongside the addresses. Wires without a
+// number always show their addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WireNumberDisplay {
+    #[default]
+    AddressOnly,
+    // Every label of the wire holds just its number
+    NumberOnly,
+    // Every label keeps its addresses and carries the number as `LabelRecord::wire_number`
+    NumberAndAddress,
+}
+
+impl WireNumberDisplay {
+    pub fn apply(&self, wire_number: Option<&str>, labels: Vec<LabelRecord>) -> Vec<LabelRecord> {
+        let Some(number) = wire_number.filter(|n| !n.is_empty()) else {
+            return labels;
+        };
+        match self {
+            WireNumberDisplay::AddressOnly => labels,
+            WireNumberDisplay::NumberOnly => labels
+                .into_iter()
+                .map(|_| LabelRecord::new(vec![number.to_string()]))
+                .collect(),
+            WireNumberDisplay::NumberAndAddress => labels
+                .into_iter()
+                .map(|mut label| {
+                    label.wire_number = Some(number.to_string());
+                    label
+                })
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for WireNumberDisplay {
+    type Err = ExportOptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "address_only" => Ok(WireNumberDisplay::AddressOnly),
+            "number_only" => Ok(WireNumberDisplay::NumberOnly),
+            "number_and_address" => Ok(WireNumberDisplay::NumberAndAddress),
+            other => Err(ExportOptionsError::InvalidWireNumberDisplay(
+                other.to_string(),
+            )),
+        }
+    }
+}
+
+// Writes label records in the import format of a marking system or printer
+pub trait LabelExporter {
+    // Name used to select the exporter, e.g. in the settings file
+    fn name(&self) -> &str;
+
+    // File extension without the dot
+    fn extension(&self) -> &str;
+
+    fn labeling_mode(&self) -> LabelingMode;
+
+    fn set_labeling_mode(&mut self, mode: LabelingMode);
+
+    fn render(&self, labels: &[LabelRecord]) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    // Label text from `strategy`, laid out with this exporter's labeling mode
+    fn connection_labels(
+        &self,
+        strategy: &dyn ExportToCsvStrategy,
+        connection_list: &[Connection],
+    ) -> Vec<LabelRecord> {
+        strategy.label_records_with(self.labeling_mode(), connection_list)
+    }
+
+    fn export_labels(
+        &self,
+        mut file_path: PathBuf,
+        labels: &[LabelRecord],
+    ) -> Result<(), Box<dyn Error>> {
+        if file_path.extension().unwrap_or_default() != self.extension() {
+            file_path.set_extension(self.extension());
+        }
+        fs::write(file_path, self.render(labels)?)?;
+        println!("Successfully exported labels for {}", self.name());
+        Ok(())
+    }
+}
+
+// Names accepted by `label_exporter_by_name`
+pub fn label_exporter_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = MarkingSystemLayout::all()
+        .into_iter()
+        .map(|layout| layout.name)
+        .collect();
+    names.extend(["zpl", "pdf"]);
+    names
+}
+
+// Exporters returned here use their default options; `ConnectionManager::label_exporter` builds
+// them from the settings
+pub fn label_exporter_by_name(name: &str) -> Option<Box<dyn LabelExporter>> {
+    let name = name.trim().to_lowercase();
+    match name.as_str() {
+        "zpl" => return Some(Box::new(ZplExporter::default())),
+        "pdf" => return Some(Box::new(PdfExporter::default())),
+        _ => {}
+    }
+    MarkingSystemLayout::all()
+        .into_iter()
+        .find(|layout| layout.name == name)
+        .map(|layout| Box::new(layout) as Box<dyn LabelExporter>)
+}
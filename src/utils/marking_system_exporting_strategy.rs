@@ -0,0 +1,176 @@
+use std::error::Error;
+
+use crate::utils::export_options::{LineEnding, QuoteStyle};
+use crate::utils::label_exporting_strategy::{LabelExporter, LabelRecord, LabelingMode};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextEncoding {
+    Utf8,
+    // UTF-8 with a byte order mark
+    Utf8Bom,
+    // UTF-16 little endian with a byte order mark, as written by Windows "Unicode text"
+    Utf16Le,
+}
+
+impl TextEncoding {
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        match self {
+            TextEncoding::Utf8 => text.as_bytes().to_vec(),
+            TextEncoding::Utf8Bom => {
+                let mut bytes = b"\xEF\xBB\xBF".to_vec();
+                bytes.extend_from_slice(text.as_bytes());
+                bytes
+            }
+            TextEncoding::Utf16Le => {
+                let mut bytes = vec![0xFF, 0xFE];
+                for unit in text.encode_utf16() {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                }
+                bytes
+            }
+        }
+    }
+}
+
+// Column-mapped CSV/TXT import layout of a marking system. Label line N goes into column N;
+// missing lines are left empty and lines beyond the last column are dropped. When any label
+// carries a wire number, it gets a leading column of its own headed `number_header`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkingSystemLayout {
+    pub name: &'static str,
+    pub extension: &'static str,
+    pub headers: &'static [&'static str],
+    pub number_header: &'static str,
+    pub delimiter: u8,
+    pub quote_style: QuoteStyle,
+    pub line_ending: LineEnding,
+    pub encoding: TextEncoding,
+    // Longest text (in characters) one field can hold; longer text is cut off
+    pub max_field_length: Option<usize>,
+    pub labeling_mode: LabelingMode,
+}
+
+impl MarkingSystemLayout {
+    // Weidmüller M-Print PRO: semicolon separated Unicode text, one column per marker line
+    pub const MPRINT_PRO: MarkingSystemLayout = MarkingSystemLayout {
+        name: "mprint_pro",
+        extension: "txt",
+        headers: &["Text 1", "Text 2", "Text 3"],
+        number_header: "Number",
+        delimiter: b';',
+        quote_style: QuoteStyle::Necessary,
+        line_ending: LineEnding::CrLf,
+        encoding: TextEncoding::Utf16Le,
+        max_field_length: Some(30),
+        labeling_mode: LabelingMode::Single,
+    };
+
+    // Phoenix Contact CLIP PROJECT marking: tab separated Unicode text
+    pub const CLIP_PROJECT: MarkingSystemLayout = MarkingSystemLayout {
+        name: "clip_project",
+        extension: "txt",
+        headers: &["Line 1", "Line 2"],
+        number_header: "Number",
+        delimiter: b'\t',
+        quote_style: QuoteStyle::Never,
+        line_ending: LineEnding::CrLf,
+        encoding: TextEncoding::Utf16Le,
+        max_field_length: Some(40),
+        labeling_mode: LabelingMode::Single,
+    };
+
+    // Brady Workstation data import: comma separated UTF-8 with BOM, every field quoted
+    pub const BRADY_WORKSTATION: MarkingSystemLayout = MarkingSystemLayout {
+        name: "brady_workstation",
+        extension: "csv",
+        headers: &["Line1", "Line2"],
+        number_header: "Number",
+        delimiter: b',',
+        quote_style: QuoteStyle::Always,
+        line_ending: LineEnding::CrLf,
+        encoding: TextEncoding::Utf8Bom,
+        max_field_length: Some(50),
+        labeling_mode: LabelingMode::Single,
+    };
+
+    // Plain semicolon separated UTF-8 for everything else
+    pub const GENERIC_CSV: MarkingSystemLayout = MarkingSystemLayout {
+        name: "generic_csv",
+        extension: "csv",
+        headers: &["source", "destination"],
+        number_header: "wire_number",
+        delimiter: b';',
+        quote_style: QuoteStyle::Necessary,
+        line_ending: LineEnding::Lf,
+        encoding: TextEncoding::Utf8,
+        max_field_length: None,
+        labeling_mode: LabelingMode::Single,
+    };
+
+    pub fn all() -> Vec<MarkingSystemLayout> {
+        vec![
+            Self::MPRINT_PRO,
+            Self::CLIP_PROJECT,
+            Self::BRADY_WORKSTATION,
+            Self::GENERIC_CSV,
+        ]
+    }
+
+    fn field(&self, text: &str) -> String {
+        let text = text.replace(['\r', '\n', '\t'], " ");
+        match self.max_field_length {
+            Some(max) => text.chars().take(max).collect(),
+            None => text,
+        }
+    }
+
+    pub fn render_text(&self, labels: &[LabelRecord]) -> Result<String, Box<dyn Error>> {
+        let mut wtr = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .quote_style(self.quote_style.into())
+            .terminator(self.line_ending.terminator())
+            .from_writer(Vec::new());
+
+        let numbered = labels.iter().any(|label| label.wire_number.is_some());
+        let mut headers = self.headers.to_vec();
+        if numbered {
+            headers.insert(0, self.number_header);
+        }
+        wtr.write_record(&headers)?;
+        for label in labels {
+            let mut record: Vec<String> = (0..self.headers.len())
+                .map(|i| self.field(label.line(i)))
+                .collect();
+            if numbered {
+                record.insert(
+                    0,
+                    self.field(label.wire_number.as_deref().unwrap_or_default()),
+                );
+            }
+            wtr.write_record(&record)?;
+        }
+        Ok(String::from_utf8(wtr.into_inner()?)?)
+    }
+}
+
+impl LabelExporter for MarkingSystemLayout {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn extension(&self) -> &str {
+        self.extension
+    }
+
+    fn labeling_mode(&self) -> LabelingMode {
+        self.labeling_mode
+    }
+
+    fn set_labeling_mode(&mut self, mode: LabelingMode) {
+        self.labeling_mode = mode;
+    }
+
+    fn render(&self, labels: &[LabelRecord]) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.encoding.encode(&self.render_text(labels)?))
+    }
+}
@@ -0,0 +1,53 @@
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::models::project::CURRENT_FORMAT_VERSION;
+use crate::utils::file_handler::FileHandlerError;
+
+type Migration = fn(Value) -> Result<Value, FileHandlerError>;
+
+// `MIGRATIONS[n]` upgrades a version `n` document to version `n + 1`
+const MIGRATIONS: [Migration; CURRENT_FORMAT_VERSION as usize] = [migrate_v0_to_v1];
+
+// Legacy files are a bare JSON array of connections and count as version 0
+pub fn detect_version(document: &Value) -> Result<u32, FileHandlerError> {
+    match document {
+        Value::Array(_) => Ok(0),
+        Value::Object(map) => map
+            .get("format_version")
+            .and_then(Value::as_u64)
+            .map(|version| version as u32)
+            .ok_or_else(|| {
+                FileHandlerError::MalformedProject("missing format_version".to_string())
+            }),
+        _ => Err(FileHandlerError::MalformedProject(
+            "expected a JSON object or array".to_string(),
+        )),
+    }
+}
+
+// Runs every migration between the document's version and `CURRENT_FORMAT_VERSION`
+pub fn migrate_to_current(mut document: Value) -> Result<Value, FileHandlerError> {
+    let mut version = detect_version(&document)?;
+    if version > CURRENT_FORMAT_VERSION {
+        return Err(FileHandlerError::UnsupportedFormatVersion(version));
+    }
+    while version < CURRENT_FORMAT_VERSION {
+        info!(
+            "Migrating project file from version {} to {}",
+            version,
+            version + 1
+        );
+        document = MIGRATIONS[version as usize](document)?;
+        version += 1;
+    }
+    Ok(document)
+}
+
+fn migrate_v0_to_v1(document: Value) -> Result<Value, FileHandlerError> {
+    Ok(json!({
+        "format_version": 1,
+        "metadata": {},
+        "connections": document,
+    }))
+}
@@ -1,4 +1,14 @@
-pub mod command_manager;
 pub mod csv_exporting_strategy;
+pub mod csv_importing_strategy;
 pub mod file_handler;
 pub mod localizer;
+pub mod migrations;
+pub mod timestamp;
+pub mod export_options;
+pub mod label_exporting_strategy;
+pub mod marking_system_exporting_strategy;
+pub mod zpl_exporting_strategy;
+pub mod pdf_exporting_strategy;
+pub mod increment;
+pub mod range_expansion;
+pub mod quick_entry;
@@ -0,0 +1,351 @@
+use std::error::Error;
+
+use crate::config::settings::Settings;
+use crate::utils::export_options::{parse_number, parse_positive, ExportOptionsError};
+use crate::utils::label_exporting_strategy::{LabelExporter, LabelRecord, LabelingMode};
+
+const POINTS_PER_MM: f32 = 72.0 / 25.4;
+// Distance between text lines, relative to the font size
+const LINE_SPACING: f32 = 1.2;
+
+// Helvetica advance widths (1/1000 em) for the printable ASCII range, from the standard AFM
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+// Layout of an adhesive label sheet. All lengths in millimetres, measured from the top left
+// corner of the page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetTemplate {
+    pub name: &'static str,
+    pub page_width: f32,
+    pub page_height: f32,
+    pub rows: usize,
+    pub columns: usize,
+    pub top_margin: f32,
+    pub left_margin: f32,
+    // Distance between the left edges of neighbouring labels
+    pub horizontal_pitch: f32,
+    // Distance between the top edges of neighbouring labels
+    pub vertical_pitch: f32,
+    pub label_width: f32,
+    pub label_height: f32,
+}
+
+impl SheetTemplate {
+    // A4, 65 labels of 38.1 x 21.2 mm
+    pub const AVERY_L7651: SheetTemplate = SheetTemplate {
+        name: "avery_l7651",
+        page_width: 210.0,
+        page_height: 297.0,
+        rows: 13,
+        columns: 5,
+        top_margin: 10.7,
+        left_margin: 4.7,
+        horizontal_pitch: 40.6,
+        vertical_pitch: 21.2,
+        label_width: 38.1,
+        label_height: 21.2,
+    };
+
+    // US Letter, 80 labels of 1.75 x 0.5 in
+    pub const AVERY_5167: SheetTemplate = SheetTemplate {
+        name: "avery_5167",
+        page_width: 215.9,
+        page_height: 279.4,
+        rows: 20,
+        columns: 4,
+        top_margin: 12.7,
+        left_margin: 7.62,
+        horizontal_pitch: 52.07,
+        vertical_pitch: 12.7,
+        label_width: 44.45,
+        label_height: 12.7,
+    };
+
+    pub fn all() -> Vec<SheetTemplate> {
+        vec![Self::AVERY_L7651, Self::AVERY_5167]
+    }
+
+    pub fn by_name(name: &str) -> Option<SheetTemplate> {
+        let name = name.trim().to_lowercase();
+        Self::all().into_iter().find(|t| t.name == name)
+    }
+
+    pub fn labels_per_sheet(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    // Top left corner of the label at `position` on a sheet, filled row by row
+    pub fn label_origin(&self, position: usize) -> (f32, f32) {
+        let row = position / self.columns;
+        let column = position % self.columns;
+        (
+            self.left_margin + column as f32 * self.horizontal_pitch,
+            self.top_margin + row as f32 * self.vertical_pitch,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfOptions {
+    pub template: SheetTemplate,
+    // Labels already used on the first sheet; printing starts at this position
+    pub start_offset: usize,
+    // Text is set at `max_font_size` and shrunk down to `min_font_size` (points) until the
+    // longest line fits. Text that still does not fit is clipped at the label edge.
+    pub max_font_size: f32,
+    pub min_font_size: f32,
+    // Space kept free around the text, in millimetres
+    pub padding: f32,
+    pub labeling_mode: LabelingMode,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            template: SheetTemplate::AVERY_L7651,
+            start_offset: 0,
+            max_font_size: 10.0,
+            min_font_size: 4.0,
+            padding: 1.5,
+            labeling_mode: LabelingMode::Single,
+        }
+    }
+}
+
+impl PdfOptions {
+    // Reads the `pdf_*` settings and the labeling mode; empty settings keep the defaults
+    pub fn from_settings(settings: &Settings) -> Result<Self, ExportOptionsError> {
+        let setting = |key: &str| settings.get(key).unwrap_or_default().trim();
+        let mut options = Self::default();
+
+        let template = setting("pdf_sheet_template");
+        if !template.is_empty() {
+            options.template = SheetTemplate::by_name(template)
+                .ok_or_else(|| ExportOptionsError::InvalidSheetTemplate(template.to_string()))?;
+        }
+        if let Some(start_offset) = parse_number(setting("pdf_start_offset"))? {
+            // Past the first sheet is a mistake, not a request for blank pages
+            if start_offset >= options.template.labels_per_sheet() {
+                return Err(ExportOptionsError::InvalidNumber(
+                    setting("pdf_start_offset").to_string(),
+                ));
+            }
+            options.start_offset = start_offset;
+        }
+        if let Some(size) = parse_positive(setting("pdf_max_font_size"))? {
+            options.max_font_size = size;
+        }
+        if let Some(size) = parse_positive(setting("pdf_min_font_size"))? {
+            options.min_font_size = size;
+        }
+        if options.min_font_size > options.max_font_size {
+            return Err(ExportOptionsError::InvalidFontSizeRange(
+                options.min_font_size,
+                options.max_font_size,
+            ));
+        }
+        options.labeling_mode = setting("labeling_mode").parse()?;
+        Ok(options)
+    }
+}
+
+// Writes label sheets as a self-contained PDF using the built-in Helvetica font
+#[derive(Debug, Clone, Default)]
+pub struct PdfExporter {
+    pub options: PdfOptions,
+}
+
+impl PdfExporter {
+    pub fn new(options: PdfOptions) -> Self {
+        Self { options }
+    }
+
+    // Largest size between the configured limits at which every line fits the label
+    pub fn font_size_for(&self, label: &LabelRecord) -> f32 {
+        let options = &self.options;
+        let template = &options.template;
+        let available_width = (template.label_width - 2.0 * options.padding) * POINTS_PER_MM;
+        let available_height = (template.label_height - 2.0 * options.padding) * POINTS_PER_MM;
+
+        let lines = label.printed_lines();
+        let widest = lines
+            .iter()
+            .map(|line| text_width(line))
+            .fold(0.0, f32::max);
+        let line_count = lines.len().max(1) as f32;
+
+        let mut size = options.max_font_size;
+        if widest > 0.0 {
+            size = size.min(available_width / widest);
+        }
+        size = size.min(available_height / (line_count * LINE_SPACING));
+        size.max(options.min_font_size)
+    }
+
+    // Content stream of one page: each label is clipped to its own rectangle, with the lines
+    // centred horizontally and vertically
+    fn page_content(&self, labels: &[(usize, &LabelRecord)]) -> String {
+        let template = &self.options.template;
+        let page_height = template.page_height * POINTS_PER_MM;
+        let width = template.label_width * POINTS_PER_MM;
+        let height = template.label_height * POINTS_PER_MM;
+
+        let mut content = String::new();
+        for (position, label) in labels {
+            let (left, top) = template.label_origin(*position);
+            let x = left * POINTS_PER_MM;
+            let y = page_height - top * POINTS_PER_MM - height;
+            let size = self.font_size_for(label);
+            let lines = label.printed_lines();
+            let block_height = lines.len() as f32 * size * LINE_SPACING;
+
+            content.push_str(&format!(
+                "q\n{:.2} {:.2} {:.2} {:.2} re W n\nBT\n/F1 {:.2} Tf\n",
+                x, y, width, height, size
+            ));
+            let block_top = y + (height + block_height) / 2.0;
+            for (i, line) in lines.iter().enumerate() {
+                let line_x = x + (width - text_width(line) * size) / 2.0;
+                let baseline = block_top - size - i as f32 * size * LINE_SPACING;
+                content.push_str(&format!(
+                    "1 0 0 1 {:.2} {:.2} Tm\n({}) Tj\n",
+                    line_x,
+                    baseline,
+                    pdf_string(line)
+                ));
+            }
+            content.push_str("ET\nQ\n");
+        }
+        content
+    }
+
+    pub fn render_pdf(&self, labels: &[LabelRecord]) -> Vec<u8> {
+        let template = &self.options.template;
+        let per_sheet = template.labels_per_sheet().max(1);
+        let start = self.options.start_offset.min(per_sheet - 1);
+
+        // `start` lies on the first sheet, so slot / per_sheet is the page index
+        let mut pages: Vec<Vec<(usize, &LabelRecord)>> = vec![Vec::new()];
+        for (i, label) in labels.iter().enumerate() {
+            let slot = start + i;
+            let page = slot / per_sheet;
+            if page == pages.len() {
+                pages.push(Vec::new());
+            }
+            pages[page].push((slot % per_sheet, label));
+        }
+
+        // Objects: 1 catalog, 2 page tree, 3 font, then a page and its content per sheet
+        let page_ids: Vec<usize> = (0..pages.len()).map(|i| 4 + 2 * i).collect();
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids
+                    .iter()
+                    .map(|id| format!("{} 0 R", id))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                pages.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        ];
+        for (page, id) in pages.iter().zip(&page_ids) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                 /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                template.page_width * POINTS_PER_MM,
+                template.page_height * POINTS_PER_MM,
+                id + 1
+            ));
+            objects.push(self.page_content(page));
+        }
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n", i + 1).bytes());
+            // Every second object from the first page on is a content stream
+            if i >= 4 && i % 2 == 0 {
+                let content = encode_win_ansi(object);
+                pdf.extend(format!("<< /Length {} >>\nstream\n", content.len()).bytes());
+                pdf.extend(content);
+                pdf.extend(b"endstream");
+            } else {
+                pdf.extend(object.bytes());
+            }
+            pdf.extend(b"\nendobj\n");
+        }
+        let xref = pdf.len();
+        pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+        for offset in offsets {
+            pdf.extend(format!("{:010} 00000 n \n", offset).bytes());
+        }
+        pdf.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .bytes(),
+        );
+        pdf
+    }
+}
+
+impl LabelExporter for PdfExporter {
+    fn name(&self) -> &str {
+        "pdf"
+    }
+
+    fn extension(&self) -> &str {
+        "pdf"
+    }
+
+    fn labeling_mode(&self) -> LabelingMode {
+        self.options.labeling_mode
+    }
+
+    fn set_labeling_mode(&mut self, mode: LabelingMode) {
+        self.options.labeling_mode = mode;
+    }
+
+    fn render(&self, labels: &[LabelRecord]) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.render_pdf(labels))
+    }
+}
+
+// Width of `text` in Helvetica at a font size of 1
+fn text_width(text: &str) -> f32 {
+    text.chars()
+        .map(|c| match c as u32 {
+            code @ 32..=126 => HELVETICA_WIDTHS[(code - 32) as usize],
+            _ => 556,
+        } as f32)
+        .sum::<f32>()
+        / 1000.0
+}
+
+fn pdf_string(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('(', "\\(")
+        .replace(')', "\\)")
+}
+
+// WinAnsiEncoding matches Latin-1 for the characters labels use; anything else becomes `?`
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c as u32 {
+            code @ (0..=0x7F | 0xA0..=0xFF) => code as u8,
+            _ => b'?',
+        })
+        .collect()
+}
@@ -0,0 +1,196 @@
+use thiserror::Error;
+
+use crate::models::connection::Connection;
+use crate::models::designation::{DesignationError, DesignationFormatter};
+use crate::models::reference_designation::{
+    format_reference_designation, parse_reference_designation, ReferenceDesignationError,
+    FUNCTION_PREFIX, LOCATION_PREFIX, PRODUCT_PREFIX, TERMINAL_PREFIX,
+};
+use crate::models::terminal::TerminalAddress;
+use crate::utils::range_expansion::{expand_connection, expand_terminals, RangeError};
+
+// Accepted between source and destination, in the order they are looked for (`->` before `>`,
+// which it contains)
+pub const SEPARATORS: [&str; 3] = ["->", ">", "\t"];
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum QuickEntryError {
+    #[error("Separate source and destination with '>', '->' or a tab")]
+    MissingSeparator,
+    #[error("Only one '{0}' is allowed")]
+    TooManySeparators(String),
+    #[error("The {0} is empty")]
+    EmptySide(&'static str),
+    #[error("{side}: {source}")]
+    Designation {
+        side: &'static str,
+        source: DesignationError,
+    },
+    #[error("{side}: {source}")]
+    ReferenceDesignation {
+        side: &'static str,
+        source: ReferenceDesignationError,
+    },
+    #[error("{0} could be a terminal block or a range, write the range as {1}")]
+    AmbiguousRange(String, String),
+    #[error(transparent)]
+    Range(#[from] RangeError),
+}
+
+// One line of the quick-entry bar, e.g. `K1-A1 > X1-3`, `K1:A1 -> X1:3` or `K1-A1<TAB>X1-3`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickEntry {
+    pub source: TerminalAddress,
+    pub destination: TerminalAddress,
+    pub separator: &'static str,
+    // Whether the ends were written as IEC 81346 reference designations (`K1:A1`)
+    pub reference_designation: bool,
+}
+
+impl QuickEntry {
+    // Sides are read with `formatter`. A side it cannot read, or one holding `:`, `=` or `+`
+    // that the pattern does not use itself (`K1:A1` with the default pattern), is read as a
+    // reference designation.
+    //
+    // Terminals may hold ranges such as `1..16`; they are expanded by `connections`. Patterns
+    // joining parts with `-` read `X1-1-16` as terminal block 1, terminal 16, so an entry where
+    // both sides could also end in a `-` range of the same length is rejected rather than
+    // guessed; `X1-1..16` is never ambiguous.
+    pub fn parse(line: &str, formatter: &DesignationFormatter) -> Result<Self, QuickEntryError> {
+        let separator = SEPARATORS
+            .into_iter()
+            .find(|separator| line.contains(separator))
+            .ok_or(QuickEntryError::MissingSeparator)?;
+        let (source, destination) = line.split_once(separator).unwrap_or_default();
+        if destination.contains(separator) {
+            return Err(QuickEntryError::TooManySeparators(
+                separator.trim().to_string(),
+            ));
+        }
+        let (source_address, source_iec) = parse_side(source, "source", formatter)?;
+        let (destination_address, destination_iec) =
+            parse_side(destination, "destination", formatter)?;
+        if !source_iec && !destination_iec {
+            check_ambiguous_range(source.trim(), destination.trim(), formatter)?;
+        }
+        Ok(Self {
+            source: source_address,
+            destination: destination_address,
+            separator,
+            reference_designation: source_iec || destination_iec,
+        })
+    }
+
+    pub fn to_connection(&self) -> Connection {
+        let mut connection = Connection::new(
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        );
+        connection.set_source_terminal(self.source.clone());
+        connection.set_destination_terminal(self.destination.clone());
+        connection
+    }
+
+    // One connection per terminal pair of the ranges in this entry
+    pub fn connections(&self) -> Result<Vec<Connection>, QuickEntryError> {
+        Ok(expand_connection(&self.to_connection())?)
+    }
+
+    // Writes the entry back as a line in the style it was typed in, e.g. after the terminals
+    // were incremented
+    pub fn to_line(&self, formatter: &DesignationFormatter) -> String {
+        let format = |address: &TerminalAddress| {
+            if self.reference_designation {
+                // `K1:A1` rather than `-K1:A1`, as it is usually typed
+                let designation = format_reference_designation(address);
+                match designation.strip_prefix(PRODUCT_PREFIX) {
+                    Some(designation) => designation.to_string(),
+                    None => designation,
+                }
+            } else {
+                formatter.format(address)
+            }
+        };
+        let separator = match self.separator {
+            "\t" => "\t".to_string(),
+            separator => format!(" {} ", separator),
+        };
+        format!(
+            "{}{}{}",
+            format(&self.source),
+            separator,
+            format(&self.destination)
+        )
+    }
+}
+
+// Whether `side` uses reference designation prefixes that `formatter` does not write itself
+fn is_reference_designation(side: &str, formatter: &DesignationFormatter) -> bool {
+    [FUNCTION_PREFIX, LOCATION_PREFIX, TERMINAL_PREFIX]
+        .into_iter()
+        .any(|prefix| side.contains(prefix) && !formatter.uses(prefix))
+}
+
+// The address and whether it was read as a reference designation
+fn parse_side(
+    side: &str,
+    name: &'static str,
+    formatter: &DesignationFormatter,
+) -> Result<(TerminalAddress, bool), QuickEntryError> {
+    let side = side.trim();
+    if side.is_empty() {
+        return Err(QuickEntryError::EmptySide(name));
+    }
+    let reference_designation = |error: Option<DesignationError>| {
+        parse_reference_designation(side)
+            .map(|address| (address, true))
+            .map_err(|source| match error {
+                Some(source) => QuickEntryError::Designation { side: name, source },
+                None => QuickEntryError::ReferenceDesignation { side: name, source },
+            })
+    };
+    if is_reference_designation(side, formatter) {
+        return reference_designation(None);
+    }
+    match formatter.parse(side) {
+        Ok(address) => Ok((address, false)),
+        // Reported as the pattern error, since that is how the side was meant to be read
+        Err(e) if side.contains([FUNCTION_PREFIX, LOCATION_PREFIX, TERMINAL_PREFIX]) => {
+            reference_designation(Some(e))
+        }
+        Err(source) => Err(QuickEntryError::Designation { side: name, source }),
+    }
+}
+
+fn check_ambiguous_range(
+    source: &str,
+    destination: &str,
+    formatter: &DesignationFormatter,
+) -> Result<(), QuickEntryError> {
+    match (
+        range_reading(source, formatter),
+        range_reading(destination, formatter),
+    ) {
+        (Some((length, dotted)), Some((other_length, _))) if length == other_length => {
+            Err(QuickEntryError::AmbiguousRange(source.to_string(), dotted))
+        }
+        _ => Ok(()),
+    }
+}
+
+// If `side` could also end in a `-` range of terminals, e.g. `X1-1-16` as terminals 1 to 16 of
+// `X1`, the number of terminals and the side written with `..` instead
+fn range_reading(side: &str, formatter: &DesignationFormatter) -> Option<(usize, String)> {
+    side.char_indices().find_map(|(start, _)| {
+        let (first, last) = side[start..].split_once('-')?;
+        let length = expand_terminals(&side[start..]).ok()?.len();
+        let terminal = format!("{}..{}", first, last);
+        let dotted = format!("{}{}", &side[..start], terminal);
+        let address = formatter.parse(&dotted).ok()?;
+        (length > 1 && address.terminal == terminal).then_some((length, dotted))
+    })
+}
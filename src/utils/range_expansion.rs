@@ -0,0 +1,127 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::models::connection::Connection;
+
+// Upper bound for a single expansion, so a typo like `1-10000` does not add ten thousand wires
+pub const MAX_RANGE_LEN: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum RangeError {
+    #[error("Empty entry in terminal list {0}")]
+    EmptyEntry(String),
+    #[error("Invalid step in range {0}")]
+    InvalidStep(String),
+    #[error("Range {0} has more than {MAX_RANGE_LEN} terminals")]
+    TooLong(String),
+    #[error("Source has {source_len} terminals but destination has {destination_len}")]
+    LengthMismatch {
+        source_len: usize,
+        destination_len: usize,
+    },
+}
+
+// Expands a terminal specification into the terminals it names:
+//
+// - `1-16` or `1..16`, optionally with a step: `1-8/2` gives 1, 3, 5, 7
+// - a common prefix and zero padding are kept: `A1-A4`, `01..03`
+// - `16-1` counts down
+// - comma separated lists of the above: `A1,A2,13,14`, `1-4,9-12`
+//
+// Anything else is a single terminal, so `L1-1`, `PE` or `A-B` come back unchanged.
+pub fn expand_terminals(spec: &str) -> Result<Vec<String>, RangeError> {
+    let spec = spec.trim();
+    if !spec.contains([',', '-', '.', '/']) {
+        return Ok(vec![spec.to_string()]);
+    }
+
+    let mut terminals = Vec::new();
+    for entry in spec.split(',').map(str::trim) {
+        if entry.is_empty() {
+            return Err(RangeError::EmptyEntry(spec.to_string()));
+        }
+        match expand_range(entry)? {
+            Some(range) => terminals.extend(range),
+            None => terminals.push(entry.to_string()),
+        }
+        if terminals.len() > MAX_RANGE_LEN {
+            return Err(RangeError::TooLong(spec.to_string()));
+        }
+    }
+    Ok(terminals)
+}
+
+// `None` when `entry` is not a range
+fn expand_range(entry: &str) -> Result<Option<Vec<String>>, RangeError> {
+    let (bounds, step) = entry.rsplit_once('/').unwrap_or((entry, "1"));
+    let Some((start, end)) = bounds.split_once("..").or_else(|| bounds.split_once('-')) else {
+        return Ok(None);
+    };
+    let (Some((prefix, first, width)), Some((end_prefix, last, _))) =
+        (split_number(start.trim()), split_number(end.trim()))
+    else {
+        return Ok(None);
+    };
+    if prefix != end_prefix {
+        return Ok(None);
+    }
+    let step = match step.trim().parse::<usize>() {
+        Ok(step) if step > 0 => step,
+        _ => return Err(RangeError::InvalidStep(entry.to_string())),
+    };
+    if first.abs_diff(last) as usize / step >= MAX_RANGE_LEN {
+        return Err(RangeError::TooLong(entry.to_string()));
+    }
+
+    let numbers: Vec<u64> = if first <= last {
+        (first..=last).step_by(step).collect()
+    } else {
+        (last..=first).rev().step_by(step).collect()
+    };
+    Ok(Some(
+        numbers
+            .into_iter()
+            .map(|n| format!("{}{:0width$}", prefix, n, width = width))
+            .collect(),
+    ))
+}
+
+// `A07` → (`A`, 7, 2); the width is only kept for zero-padded numbers
+fn split_number(value: &str) -> Option<(&str, u64, usize)> {
+    let digits = value.chars().rev().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let (prefix, number) = value.split_at(value.len() - digits);
+    let width = if number.starts_with('0') { digits } else { 0 };
+    Some((prefix, number.parse().ok()?, width))
+}
+
+// Expands ranges in the source and destination terminals into one connection per terminal pair,
+// e.g. `X1:1-16 → K2:1-16` into sixteen wires. Both sides must name the same number of
+// terminals. Every connection gets its own UUID and the wire attributes of `connection`.
+pub fn expand_connection(connection: &Connection) -> Result<Vec<Connection>, RangeError> {
+    let sources = expand_terminals(&connection.src_terminal)?;
+    let destinations = expand_terminals(&connection.dst_terminal)?;
+    if sources.len() != destinations.len() {
+        return Err(RangeError::LengthMismatch {
+            source_len: sources.len(),
+            destination_len: destinations.len(),
+        });
+    }
+    if sources.len() == 1 {
+        return Ok(vec![connection.clone()]);
+    }
+    Ok(sources
+        .into_iter()
+        .zip(destinations)
+        .map(|(source, destination)| {
+            let mut expanded = connection.clone();
+            expanded.uuid = Uuid::new_v4();
+            expanded.wire_number = None;
+            expanded.src_terminal = source;
+            expanded.dst_terminal = destination;
+            expanded
+        })
+        .collect())
+}
@@ -0,0 +1,307 @@
+use std::error::Error;
+use std::io::{self, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::info;
+
+use crate::config::settings::Settings;
+use crate::models::connection::Connection;
+use crate::utils::csv_exporting_strategy::ExportToCsvStrategy;
+use crate::utils::export_options::{parse_bool, parse_positive, ExportOptionsError};
+use crate::utils::label_exporting_strategy::{LabelExporter, LabelRecord, LabelingMode};
+
+// Raw printing port of Zebra (and most other network label) printers
+pub const ZPL_RAW_PORT: u16 = 9100;
+
+// How long to wait for a printer to accept the connection or the label data
+pub const PRINTER_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Gap between the label edge and the text, in dots
+const MARGIN_DOTS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ZplOrientation {
+    #[default]
+    Normal,
+    // 90 degrees clockwise
+    Rotated,
+    // 180 degrees
+    Inverted,
+    // 270 degrees, read from the bottom up
+    Bottom,
+}
+
+impl ZplOrientation {
+    fn code(&self) -> char {
+        match self {
+            ZplOrientation::Normal => 'N',
+            ZplOrientation::Rotated => 'R',
+            ZplOrientation::Inverted => 'I',
+            ZplOrientation::Bottom => 'B',
+        }
+    }
+
+    fn is_vertical(&self) -> bool {
+        matches!(self, ZplOrientation::Rotated | ZplOrientation::Bottom)
+    }
+}
+
+impl FromStr for ZplOrientation {
+    type Err = ExportOptionsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "normal" | "n" | "0" => Ok(ZplOrientation::Normal),
+            "rotated" | "r" | "90" => Ok(ZplOrientation::Rotated),
+            "inverted" | "i" | "180" => Ok(ZplOrientation::Inverted),
+            "bottom" | "b" | "270" => Ok(ZplOrientation::Bottom),
+            other => Err(ExportOptionsError::InvalidZplOrientation(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZplOptions {
+    pub label_width_mm: f32,
+    pub label_height_mm: f32,
+    // Print head resolution: 203, 300 or 600
+    pub dpi: u32,
+    // Printer font name (`0` is the scalable font)
+    pub font: char,
+    // Character height and width in dots
+    pub font_height: u32,
+    pub font_width: u32,
+    pub orientation: ZplOrientation,
+    // Copies of every label
+    pub repeat: u32,
+    // Print the text a second time on the far half of the label, so a sleeve can be read from
+    // both sides of the marking
+    pub both_ends: bool,
+    pub labeling_mode: LabelingMode,
+}
+
+impl Default for ZplOptions {
+    fn default() -> Self {
+        Self {
+            label_width_mm: 50.0,
+            label_height_mm: 12.0,
+            dpi: 300,
+            font: '0',
+            font_height: 30,
+            font_width: 30,
+            orientation: ZplOrientation::Normal,
+            repeat: 1,
+            both_ends: false,
+            labeling_mode: LabelingMode::Single,
+        }
+    }
+}
+
+impl ZplOptions {
+    // Reads the `zpl_*` settings and the labeling mode; empty settings keep the defaults
+    pub fn from_settings(settings: &Settings) -> Result<Self, ExportOptionsError> {
+        let setting = |key: &str| settings.get(key).unwrap_or_default().trim();
+        let mut options = Self::default();
+
+        if let Some(width) = parse_positive(setting("zpl_label_width_mm"))? {
+            options.label_width_mm = width;
+        }
+        if let Some(height) = parse_positive(setting("zpl_label_height_mm"))? {
+            options.label_height_mm = height;
+        }
+        if let Some(dpi) = parse_positive(setting("zpl_dpi"))? {
+            options.dpi = dpi;
+        }
+        let mut font = setting("zpl_font").chars();
+        match (font.next(), font.next()) {
+            (None, _) => {}
+            (Some(name), None) if name.is_ascii_alphanumeric() => {
+                options.font = name.to_ascii_uppercase()
+            }
+            _ => {
+                return Err(ExportOptionsError::InvalidZplFont(
+                    setting("zpl_font").to_string(),
+                ))
+            }
+        }
+        if let Some(height) = parse_positive(setting("zpl_font_height"))? {
+            options.font_height = height;
+        }
+        if let Some(width) = parse_positive(setting("zpl_font_width"))? {
+            options.font_width = width;
+        }
+        options.orientation = setting("zpl_orientation").parse()?;
+        if let Some(repeat) = parse_positive(setting("zpl_repeat"))? {
+            options.repeat = repeat;
+        }
+        options.both_ends = parse_bool(setting("zpl_both_ends"))?;
+        options.labeling_mode = setting("labeling_mode").parse()?;
+        Ok(options)
+    }
+
+    fn dots(&self, mm: f32) -> u32 {
+        (mm * self.dpi as f32 / 25.4).round() as u32
+    }
+}
+
+// Renders labels as ZPL II programs, one `^XA ... ^XZ` block per label
+#[derive(Debug, Clone, Default)]
+pub struct ZplExporter {
+    pub options: ZplOptions,
+}
+
+impl ZplExporter {
+    pub fn new(options: ZplOptions) -> Self {
+        Self { options }
+    }
+
+    // Labels showing source and destination as `strategy` writes them, laid out with
+    // `options.labeling_mode`
+    pub fn render_connections(
+        &self,
+        strategy: &dyn ExportToCsvStrategy,
+        connection_list: &[Connection],
+    ) -> String {
+        self.render_zpl(&self.connection_labels(strategy, connection_list))
+    }
+
+    pub fn render_label(&self, label: &LabelRecord) -> String {
+        let options = &self.options;
+        let width = options.dots(options.label_width_mm);
+        let height = options.dots(options.label_height_mm);
+        let line_pitch = options.font_height + options.font_height / 4;
+
+        let mut origins = vec![(MARGIN_DOTS, MARGIN_DOTS)];
+        if options.both_ends {
+            if options.orientation.is_vertical() {
+                origins.push((MARGIN_DOTS, height / 2 + MARGIN_DOTS));
+            } else {
+                origins.push((width / 2 + MARGIN_DOTS, MARGIN_DOTS));
+            }
+        }
+
+        let mut zpl = String::new();
+        zpl.push_str("^XA\n^CI28\n");
+        zpl.push_str(&format!("^PW{}\n^LL{}\n", width, height));
+        for (x, y) in origins {
+            for (i, line) in label.printed_lines().iter().enumerate() {
+                let offset = i as u32 * line_pitch;
+                let (x, y) = if options.orientation.is_vertical() {
+                    (x + offset, y)
+                } else {
+                    (x, y + offset)
+                };
+                zpl.push_str(&format!(
+                    "^FO{},{}^A{}{},{},{}{}^FS\n",
+                    x,
+                    y,
+                    options.font,
+                    options.orientation.code(),
+                    options.font_height,
+                    options.font_width,
+                    field_data(line),
+                ));
+            }
+        }
+        zpl.push_str(&format!("^PQ{}\n^XZ\n", options.repeat.max(1)));
+        zpl
+    }
+
+    pub fn render_zpl(&self, labels: &[LabelRecord]) -> String {
+        labels
+            .iter()
+            .map(|label| self.render_label(label))
+            .collect()
+    }
+
+    // Streams the labels to a printer's raw port, see `printer_addresses` for the address forms.
+    // Each resolved address is tried in turn, waiting at most `PRINTER_TIMEOUT` for each.
+    pub fn send_to_printer(
+        &self,
+        address: &str,
+        labels: &[LabelRecord],
+    ) -> Result<(), Box<dyn Error>> {
+        let mut last_error = None;
+        for socket_address in printer_addresses(address)? {
+            match TcpStream::connect_timeout(&socket_address, PRINTER_TIMEOUT) {
+                Ok(mut stream) => {
+                    stream.set_write_timeout(Some(PRINTER_TIMEOUT))?;
+                    stream.write_all(self.render_zpl(labels).as_bytes())?;
+                    stream.flush()?;
+                    info!(
+                        "Successfully sent {} labels to {}",
+                        labels.len(),
+                        socket_address
+                    );
+                    return Ok(());
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, address.to_string()))
+            .into())
+    }
+}
+
+// Resolves a printer address: `host`, `host:port`, `192.0.2.7[:port]`, a bare IPv6 address such
+// as `fe80::1`, or `[fe80::1][:port]`. The port defaults to `ZPL_RAW_PORT`.
+pub fn printer_addresses(address: &str) -> io::Result<Vec<SocketAddr>> {
+    let address = address.trim();
+    if let Ok(socket_address) = address.parse::<SocketAddr>() {
+        return Ok(vec![socket_address]);
+    }
+    let bare = address.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = bare.parse::<IpAddr>() {
+        return Ok(vec![SocketAddr::new(ip, ZPL_RAW_PORT)]);
+    }
+    let addresses = match address.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port.parse::<u16>().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid printer port in {}", address),
+                )
+            })?;
+            (host, port).to_socket_addrs()?
+        }
+        None => (address, ZPL_RAW_PORT).to_socket_addrs()?,
+    };
+    Ok(addresses.collect())
+}
+
+impl LabelExporter for ZplExporter {
+    fn name(&self) -> &str {
+        "zpl"
+    }
+
+    fn extension(&self) -> &str {
+        "zpl"
+    }
+
+    fn labeling_mode(&self) -> LabelingMode {
+        self.options.labeling_mode
+    }
+
+    fn set_labeling_mode(&mut self, mode: LabelingMode) {
+        self.options.labeling_mode = mode;
+    }
+
+    fn render(&self, labels: &[LabelRecord]) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(self.render_zpl(labels).into_bytes())
+    }
+}
+
+// `^FD` data; control characters are hex escaped with `^FH`
+fn field_data(text: &str) -> String {
+    if text.contains(['^', '~', '_']) {
+        let escaped = text
+            .replace('_', "_5F")
+            .replace('^', "_5E")
+            .replace('~', "_7E");
+        format!("^FH^FD{}", escaped)
+    } else {
+        format!("^FD{}", text)
+    }
+}
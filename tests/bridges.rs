@@ -0,0 +1,181 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::bridge::{Bridge, BridgeAccessory, BridgeKind};
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::utils::csv_exporting_strategy::ExportFormat;
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn reopen(dir: &Path) -> ConnectionManager {
+    let project = dir.join("connections.json");
+    ConnectionManager::new(Some(project.clone()), project, dir.join("settings.json")).unwrap()
+}
+
+// A manager with one wire, K1-A1 to X1-1, and the shipped settings
+fn manager(dir: &Path) -> ConnectionManager {
+    fs::copy(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/config/settings.json"
+        ),
+        dir.join("settings.json"),
+    )
+    .unwrap();
+    let mut manager = ConnectionManager::new(
+        None,
+        dir.join("connections.json"),
+        dir.join("settings.json"),
+    )
+    .unwrap();
+    manager
+        .add_connection(
+            "K1".to_string(),
+            String::new(),
+            "A1".to_string(),
+            "X1".to_string(),
+            String::new(),
+            "1".to_string(),
+        )
+        .unwrap();
+    manager
+}
+
+fn bridge(first: u32, last: u32, kind: BridgeKind, part_number: Option<&str>) -> Bridge {
+    let mut bridge = Bridge::spanning("X1".to_string(), String::new(), first, last, kind);
+    bridge.part_number = part_number.map(str::to_string);
+    bridge
+}
+
+#[test]
+fn bridges_are_saved_with_the_project() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    let mut zero_volt = bridge(1, 5, BridgeKind::PlugIn, Some("FBS 5-5"));
+    zero_volt.function = "PUMP1".to_string();
+    zero_volt.location = "CAB2".to_string();
+    manager.add_bridge(zero_volt.clone()).unwrap();
+    manager
+        .add_bridge(bridge(7, 8, BridgeKind::Screw, None))
+        .unwrap();
+
+    let reopened = reopen(&dir);
+    assert_eq!(reopened.get_bridges(), manager.get_bridges());
+    assert_eq!(reopened.get_bridges()[0], zero_volt);
+    assert_eq!(reopened.connections.len(), 1);
+
+    let mut reopened = reopened;
+    reopened.remove_bridge(zero_volt.uuid).unwrap();
+    assert_eq!(reopen(&dir).get_bridges().len(), 1);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bridges_need_two_distinct_terminals() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+
+    assert!(manager
+        .add_bridge(bridge(3, 3, BridgeKind::PlugIn, None))
+        .is_err());
+    let twice = Bridge::new(
+        "X1".to_string(),
+        String::new(),
+        vec!["1".to_string(), "2".to_string(), "1".to_string()],
+        BridgeKind::PlugIn,
+    );
+    assert!(manager.add_bridge(twice).is_err());
+    assert!(manager.get_bridges().is_empty());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bridges_join_nets_but_print_no_labels() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    let labels_before = manager.connection_labels().unwrap();
+    manager
+        .add_bridge(bridge(1, 3, BridgeKind::PlugIn, None))
+        .unwrap();
+
+    assert_eq!(manager.connection_labels().unwrap(), labels_before);
+    let export_path = dir.join("wires.csv");
+    manager
+        .export_to_csv(ExportFormat::Wire, export_path.clone())
+        .unwrap();
+    let exported = fs::read_to_string(export_path).unwrap();
+    assert_eq!(exported.lines().count(), 1);
+    assert!(!exported.contains("X1-3"));
+
+    assert_eq!(manager.connectivity_graph().nets().len(), 1);
+    assert_eq!(manager.connectivity_graph().nets()[0].len(), 4);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn the_accessory_report_groups_bridges_to_order() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    for bridge in [
+        bridge(1, 2, BridgeKind::PlugIn, Some("FBS 2-5")),
+        bridge(3, 7, BridgeKind::Screw, None),
+        bridge(8, 9, BridgeKind::PlugIn, Some("FBS 2-5")),
+        bridge(10, 11, BridgeKind::PlugIn, None),
+    ] {
+        manager.add_bridge(bridge).unwrap();
+    }
+
+    let accessory = |kind, pole_count, part_number: Option<&str>, quantity| BridgeAccessory {
+        kind,
+        pole_count,
+        part_number: part_number.map(str::to_string),
+        quantity,
+    };
+    assert_eq!(
+        manager.bridge_accessory_report(),
+        vec![
+            accessory(BridgeKind::PlugIn, 2, None, 1),
+            accessory(BridgeKind::PlugIn, 2, Some("FBS 2-5"), 2),
+            accessory(BridgeKind::Screw, 5, None, 1),
+        ]
+    );
+
+    let report_path = dir.join("connections.bridges.csv");
+    manager.export_bridge_report(report_path.clone()).unwrap();
+    assert_eq!(
+        fs::read_to_string(&report_path).unwrap(),
+        "Plug-in|2||1\n\
+         Plug-in|2|FBS 2-5|2\n\
+         Screw|5||1\n"
+    );
+
+    // The report follows the CSV export settings, header included
+    let settings_file = dir.join("settings.json");
+    let mut settings: Value =
+        serde_json::from_str(&fs::read_to_string(&settings_file).unwrap()).unwrap();
+    settings["csv_include_header"] = Value::String("true".to_string());
+    settings["default_csv_delimiter"] = Value::String(";".to_string());
+    settings["csv_line_ending"] = Value::String("crlf".to_string());
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    reopen(&dir)
+        .export_bridge_report(report_path.clone())
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(report_path).unwrap(),
+        "kind;poles;part_number;quantity\r\n\
+         Plug-in;2;;1\r\n\
+         Plug-in;2;FBS 2-5;2\r\n\
+         Screw;5;;1\r\n"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,237 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use label_wires::command::command::{Command, DeleteConnectionCommand};
+use label_wires::command::command_manager::CommandManager;
+use label_wires::command::operation_log::OperationLog;
+use label_wires::models::cable::Cable;
+use label_wires::models::connection_manager::{ConnectionManager, ConnectionManagerError};
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager with two connections and a three-core cable W1 carrying the first on core "1"
+fn manager(dir: &Path) -> ConnectionManager {
+    let settings_file = dir.join("settings.json");
+    fs::copy(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/config/settings.json"
+        ),
+        &settings_file,
+    )
+    .unwrap();
+    let mut manager =
+        ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap();
+    for terminal in ["1", "2"] {
+        manager
+            .add_connection(
+                "K1".to_string(),
+                String::new(),
+                terminal.to_string(),
+                "X1".to_string(),
+                String::new(),
+                terminal.to_string(),
+            )
+            .unwrap();
+    }
+    manager
+        .add_cable(Cable::with_numbered_cores(
+            "W1".to_string(),
+            "ÖLFLEX 3G1.5".to_string(),
+            3,
+        ))
+        .unwrap();
+    let uuid = manager.connections[0].uuid;
+    manager.assign_core("W1", "1", uuid).unwrap();
+    manager
+}
+
+#[test]
+fn deleting_a_connection_frees_its_core_until_undone() {
+    let dir = temp_dir();
+    let manager = manager(&dir);
+    let uuid = manager.connections[0].uuid;
+    let manager = Arc::new(Mutex::new(manager));
+    let mut commands = CommandManager::new();
+
+    commands
+        .execute_command(Box::new(DeleteConnectionCommand::new(
+            vec![uuid],
+            manager.clone(),
+        )))
+        .unwrap();
+    {
+        let manager = manager.lock().unwrap();
+        assert_eq!(manager.core_assignment(uuid), None);
+        assert_eq!(manager.get_cables()[0].core("1").unwrap().connection, None);
+    }
+    // The freed core can carry another connection
+    let other = manager.lock().unwrap().connections[0].uuid;
+    manager
+        .lock()
+        .unwrap()
+        .assign_core("W1", "2", other)
+        .unwrap();
+
+    commands.undo().unwrap();
+    assert_eq!(
+        manager.lock().unwrap().core_assignment(uuid),
+        Some(("W1".to_string(), "1".to_string()))
+    );
+    commands.redo().unwrap();
+    assert_eq!(manager.lock().unwrap().core_assignment(uuid), None);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cables_cannot_carry_unknown_connections() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    let mut cable = Cable::with_numbered_cores("W2".to_string(), String::new(), 2);
+    cable.cores[0].connection = Some(Uuid::new_v4());
+
+    assert!(matches!(
+        manager.add_cable(cable.clone()),
+        Err(ConnectionManagerError::ConnectionNotFoundError)
+    ));
+    assert_eq!(manager.get_cables().len(), 1);
+
+    cable.cores[0].connection = Some(manager.connections[1].uuid);
+    manager.add_cable(cable).unwrap();
+    assert_eq!(
+        manager.core_assignment(manager.connections[1].uuid),
+        Some(("W2".to_string(), "1".to_string()))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cable_labels_cover_both_ends_and_every_assigned_core() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    let second = manager.connections[1].uuid;
+    manager.assign_core("W1", "3", second).unwrap();
+    manager
+        .add_cable(Cable::new(
+            "W2".to_string(),
+            String::new(),
+            2,
+            vec!["BN".to_string(), "BU".to_string()],
+        ))
+        .unwrap();
+
+    let file_path = dir.join("connections.cables.csv");
+    manager.export_cable_labels(file_path.clone()).unwrap();
+
+    // One label per end (tag, this end, far end), then one per assigned core. W2 carries
+    // nothing yet, so its end labels stay blank.
+    assert_eq!(
+        fs::read_to_string(file_path).unwrap(),
+        "W1|K1|X1\n\
+         W1|X1|K1\n\
+         W1:1|K1 [1]|X1 [1]\n\
+         W1:3|K1 [2]|X1 [2]\n\
+         W2||\n\
+         W2||\n"
+    );
+    let labels = manager
+        .cable_export_strategy()
+        .unwrap()
+        .generate_cable_label_string(manager.get_cables(), &manager.connections);
+    assert!(labels.starts_with("W1|K1|X1\nW1|X1|K1\nW1:1|K1 [1]|X1 [1]\n"));
+    assert!(labels.ends_with("W2||"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn a_failed_delete_puts_freed_cores_back() {
+    let dir = temp_dir();
+    let manager = manager(&dir);
+    let uuid = manager.connections[0].uuid;
+    let manager = Arc::new(Mutex::new(manager));
+
+    // The first connection and its core go, then the unknown UUID fails the whole step
+    let mut command = DeleteConnectionCommand::new(vec![uuid, Uuid::new_v4()], manager.clone());
+
+    assert!(command.execute().is_err());
+    let manager = manager.lock().unwrap();
+    assert_eq!(manager.connections.len(), 2);
+    assert_eq!(manager.connections[0].uuid, uuid);
+    assert_eq!(
+        manager.core_assignment(uuid),
+        Some(("W1".to_string(), "1".to_string()))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn reopen(dir: &Path) -> Arc<Mutex<ConnectionManager>> {
+    let project = dir.join("connections.json");
+    Arc::new(Mutex::new(
+        ConnectionManager::new(Some(project.clone()), project, dir.join("settings.json")).unwrap(),
+    ))
+}
+
+fn history(dir: &Path) -> OperationLog {
+    OperationLog::new(
+        OperationLog::path_for_project(&dir.join("connections.json")),
+        "alice".to_string(),
+    )
+}
+
+// Deletes the wire on W1 core 1 with the history recorded, and returns its UUID
+fn delete_carried_wire(dir: &Path) -> Uuid {
+    let manager = manager(dir);
+    let uuid = manager.connections[0].uuid;
+    let manager = Arc::new(Mutex::new(manager));
+    let mut commands = CommandManager::with_log(history(dir), &manager);
+    commands
+        .execute_command(Box::new(DeleteConnectionCommand::new(
+            vec![uuid],
+            manager.clone(),
+        )))
+        .unwrap();
+    uuid
+}
+
+#[test]
+fn undoing_a_reopened_delete_reassigns_its_core() {
+    let dir = temp_dir();
+    let uuid = delete_carried_wire(&dir);
+
+    let manager = reopen(&dir);
+    let mut commands = CommandManager::restore(history(&dir), &manager).unwrap();
+    commands.undo().unwrap();
+
+    assert_eq!(
+        manager.lock().unwrap().core_assignment(uuid),
+        Some(("W1".to_string(), "1".to_string()))
+    );
+    commands.redo().unwrap();
+    assert_eq!(manager.lock().unwrap().core_assignment(uuid), None);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cable_changes_outside_the_history_clear_it_on_reopen() {
+    let dir = temp_dir();
+    delete_carried_wire(&dir);
+    reopen(&dir).lock().unwrap().remove_cable("W1").unwrap();
+
+    let manager = reopen(&dir);
+    let commands = CommandManager::restore(history(&dir), &manager).unwrap();
+
+    assert!(!commands.can_undo());
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::catalog::{CatalogError, CatalogPolicy, ComponentCatalog};
+use label_wires::models::connection_manager::{ConnectionManager, ConnectionManagerError};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager in `dir` with a two-component catalog and the shipped settings with `changes` applied
+fn manager(dir: &Path, changes: &[(&str, &str)]) -> ConnectionManager {
+    let catalog_file = dir.join("catalog.csv");
+    fs::write(
+        &catalog_file,
+        "component,terminal_block,terminals\nK1,,A1;A2;13;14\nX1,XT,1-4\n",
+    )
+    .unwrap();
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    settings["catalog_file"] = Value::String(catalog_file.to_string_lossy().to_string());
+    for (key, value) in changes {
+        settings[key] = Value::String(value.to_string());
+    }
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap()
+}
+
+fn add(manager: &mut ConnectionManager, terminal: &str) -> Result<(), ConnectionManagerError> {
+    manager
+        .add_connection(
+            "K1".to_string(),
+            String::new(),
+            "A1".to_string(),
+            "X1".to_string(),
+            "XT".to_string(),
+            terminal.to_string(),
+        )
+        .map(|_| ())
+}
+
+#[test]
+fn policies_parse() {
+    assert_eq!("".parse::<CatalogPolicy>().unwrap(), CatalogPolicy::Warn);
+    assert_eq!(
+        " Reject ".parse::<CatalogPolicy>().unwrap(),
+        CatalogPolicy::Reject
+    );
+    assert!(matches!(
+        "strict".parse::<CatalogPolicy>(),
+        Err(CatalogError::InvalidPolicy(policy)) if policy == "strict"
+    ));
+}
+
+#[test]
+fn reject_refuses_terminals_outside_the_catalog() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir, &[("catalog_validation", "reject")]);
+
+    assert_eq!(manager.get_catalog_policy(), CatalogPolicy::Reject);
+    add(&mut manager, "4").unwrap();
+    assert!(matches!(
+        add(&mut manager, "5"),
+        Err(ConnectionManagerError::CatalogViolation(_))
+    ));
+    assert_eq!(manager.get_connections().len(), 1);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unknown_policies_fall_back_to_warn() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir, &[("catalog_validation", "strict")]);
+
+    assert_eq!(manager.get_catalog_policy(), CatalogPolicy::Warn);
+    add(&mut manager, "5").unwrap();
+    assert_eq!(
+        manager
+            .catalog_violations(&manager.get_connections()[0])
+            .len(),
+        1
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unknown_terminals_are_named_without_empty_parts() {
+    let catalog = ComponentCatalog::from_csv_str(
+        "component,terminal_block,terminals\nK1,,A1;A2\nX1,XT,1-4\n",
+    )
+    .unwrap();
+
+    let message = |component: &str, terminal_block: &str, terminal: &str| {
+        catalog
+            .check_terminal(component, terminal_block, terminal)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(message("K1", "", "B9"), "Terminal K1-B9 does not exist");
+    assert_eq!(message("X1", "XT", "5"), "Terminal X1-XT-5 does not exist");
+}
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use label_wires::command::command::{
+    AddConnectionCommand, AddConnectionsCommand, Command, CommandError, CompositeCommand,
+    DeleteConnectionCommand, EditConnectionCommand,
+};
+use label_wires::command::command_manager::CommandManager;
+use label_wires::command::operation_log::{Action, Operation, OperationLog};
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::utils::file_handler::FileHandler;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn shared_manager(dir: &Path) -> Arc<Mutex<ConnectionManager>> {
+    let mut manager = ConnectionManager::new(
+        None,
+        dir.join("connections.json"),
+        dir.join("settings.json"),
+    )
+    .unwrap();
+    for (component, terminal) in [("K1", "A1"), ("K2", "A2"), ("K3", "A3")] {
+        manager
+            .add_connection(
+                component.to_string(),
+                "".to_string(),
+                terminal.to_string(),
+                "X1".to_string(),
+                "XT".to_string(),
+                terminal.trim_start_matches('A').to_string(),
+            )
+            .unwrap();
+    }
+    Arc::new(Mutex::new(manager))
+}
+
+// (uuid, source component, source terminal) of every connection in the saved file
+fn saved(dir: &Path) -> Vec<(Uuid, String, String)> {
+    FileHandler::new(Some(dir.join("connections.json")))
+        .load_wires()
+        .unwrap()
+        .into_iter()
+        .map(|c| (c.uuid, c.src_component, c.src_terminal))
+        .collect()
+}
+
+fn in_memory(manager: &Arc<Mutex<ConnectionManager>>) -> Vec<(Uuid, String, String)> {
+    manager
+        .lock()
+        .unwrap()
+        .connections
+        .iter()
+        .map(|c: &Connection| (c.uuid, c.src_component.clone(), c.src_terminal.clone()))
+        .collect()
+}
+
+fn end(component: &str, terminal_block: &str, terminal: &str) -> HashMap<String, String> {
+    HashMap::from([
+        ("component".to_string(), component.to_string()),
+        ("terminal_block".to_string(), terminal_block.to_string()),
+        ("terminal".to_string(), terminal.to_string()),
+    ])
+}
+
+#[test]
+fn add_undo_redo_keeps_the_file_in_step() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+
+    let mut command =
+        AddConnectionCommand::new(manager.clone(), end("K4", "", "A4"), end("X1", "XT", "4"));
+    command.execute().unwrap();
+    let added = command.connection().unwrap().uuid;
+    assert_eq!(saved(&dir).len(), 4);
+    assert_eq!(saved(&dir), in_memory(&manager));
+
+    command.undo().unwrap();
+    assert_eq!(saved(&dir), before);
+
+    command.redo().unwrap();
+    assert_eq!(saved(&dir).last().unwrap().0, added);
+    assert_eq!(saved(&dir), in_memory(&manager));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn edit_keeps_the_uuid_and_position() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+    let uuid = before[1].0;
+
+    let mut command = EditConnectionCommand::new(
+        manager.clone(),
+        uuid,
+        HashMap::from([("src_terminal".to_string(), "B2".to_string())]),
+    );
+    command.execute().unwrap();
+    let after = saved(&dir);
+    assert_eq!(after[1], (uuid, "K2".to_string(), "B2".to_string()));
+    assert_eq!(after, in_memory(&manager));
+    // Suggestions follow the edit
+    let suggestions = manager
+        .lock()
+        .unwrap()
+        .suggestions()
+        .suggest_terminals("K2", "", "", 5);
+    assert_eq!(suggestions, vec!["B2".to_string()]);
+
+    command.undo().unwrap();
+    assert_eq!(saved(&dir), before);
+
+    command.redo().unwrap();
+    assert_eq!(saved(&dir), after);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn edit_into_a_duplicate_changes_nothing() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+
+    let mut command = EditConnectionCommand::new(
+        manager.clone(),
+        before[1].0,
+        HashMap::from([
+            ("src_component".to_string(), "K1".to_string()),
+            ("src_terminal".to_string(), "A1".to_string()),
+            ("dst_terminal".to_string(), "1".to_string()),
+        ]),
+    );
+
+    assert!(matches!(
+        command.execute(),
+        Err(CommandError::DuplicateConnection)
+    ));
+    assert_eq!(saved(&dir), before);
+    assert_eq!(in_memory(&manager), before);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn delete_undo_restores_uuids_and_order() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+
+    let mut command = DeleteConnectionCommand::new(vec![before[0].0, before[2].0], manager.clone());
+    command.execute().unwrap();
+    assert_eq!(saved(&dir), vec![before[1].clone()]);
+
+    command.undo().unwrap();
+    assert_eq!(saved(&dir), before);
+    assert_eq!(in_memory(&manager), before);
+
+    command.redo().unwrap();
+    assert_eq!(saved(&dir), vec![before[1].clone()]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn delete_is_all_or_nothing() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+
+    let mut command =
+        DeleteConnectionCommand::new(vec![before[0].0, Uuid::new_v4()], manager.clone());
+
+    assert!(matches!(
+        command.execute(),
+        Err(CommandError::ConnectionNotFoundError)
+    ));
+    assert_eq!(saved(&dir), before);
+    assert_eq!(in_memory(&manager), before);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn adding_several_is_all_or_nothing() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+    let wire = |component: &str, terminal: &str| {
+        Connection::new(
+            component.to_string(),
+            "".to_string(),
+            terminal.to_string(),
+            "X1".to_string(),
+            "XT".to_string(),
+            terminal.trim_start_matches('A').to_string(),
+        )
+    };
+
+    // The third wire duplicates one of the project, after two were already added
+    let mut command = AddConnectionsCommand::new(
+        manager.clone(),
+        vec![
+            wire("K4", "A4"),
+            wire("K5", "A5"),
+            wire("K1", "A1"),
+            wire("K6", "A6"),
+        ],
+    );
+
+    assert!(matches!(
+        command.execute(),
+        Err(CommandError::DuplicateConnection)
+    ));
+    assert_eq!(saved(&dir), before);
+    assert_eq!(in_memory(&manager), before);
+
+    let mut command =
+        AddConnectionsCommand::new(manager.clone(), vec![wire("K4", "A4"), wire("K5", "A5")]);
+    command.execute().unwrap();
+    let added: Vec<Uuid> = command.added().iter().map(|c| c.uuid).collect();
+    assert_eq!(added, vec![saved(&dir)[3].0, saved(&dir)[4].0]);
+    command.undo().unwrap();
+    assert_eq!(saved(&dir), before);
+    command.redo().unwrap();
+    assert_eq!(saved(&dir)[3].0, added[0]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn bulk_edit(manager: &Arc<Mutex<ConnectionManager>>, uuids: Vec<Uuid>) -> CompositeCommand {
+    let mut composite = CompositeCommand::new(vec![Box::new(AddConnectionCommand::new(
+        manager.clone(),
+        end("K4", "", "A4"),
+        end("X1", "XT", "4"),
+    ))]);
+    composite.push(Box::new(EditConnectionCommand::new(
+        manager.clone(),
+        uuids[0],
+        HashMap::from([("src_terminal".to_string(), "B1".to_string())]),
+    )));
+    composite.push(Box::new(DeleteConnectionCommand::new(
+        uuids[1..].to_vec(),
+        manager.clone(),
+    )));
+    composite
+}
+
+#[test]
+fn composite_undoes_and_redoes_as_one_step() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+
+    let mut composite = bulk_edit(&manager, vec![before[0].0, before[2].0]);
+    composite.execute().unwrap();
+    let after = saved(&dir);
+    assert_eq!(after.len(), 3);
+    assert_eq!(after[0], (before[0].0, "K1".to_string(), "B1".to_string()));
+    assert_eq!(after[2].1, "K4");
+
+    composite.undo().unwrap();
+    assert_eq!(saved(&dir), before);
+
+    composite.redo().unwrap();
+    assert_eq!(saved(&dir), after);
+    assert_eq!(in_memory(&manager), after);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn failing_child_rolls_back_the_composite() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let before = saved(&dir);
+
+    // The add and the edit succeed, then the delete fails on the unknown UUID
+    let mut composite = bulk_edit(&manager, vec![before[0].0, before[2].0, Uuid::new_v4()]);
+
+    assert!(composite.execute().is_err());
+    assert_eq!(saved(&dir), before);
+    assert_eq!(in_memory(&manager), before);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn reopen(dir: &Path) -> Arc<Mutex<ConnectionManager>> {
+    let project = dir.join("connections.json");
+    Arc::new(Mutex::new(
+        ConnectionManager::new(Some(project.clone()), project, dir.join("settings.json")).unwrap(),
+    ))
+}
+
+fn history(dir: &Path) -> OperationLog {
+    OperationLog::new(
+        OperationLog::path_for_project(&dir.join("connections.json")),
+        "alice".to_string(),
+    )
+}
+
+#[test]
+fn reopening_restores_undo_and_redo() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let original = saved(&dir);
+    let mut commands = CommandManager::with_log(history(&dir), &manager);
+
+    commands
+        .execute_command(Box::new(AddConnectionCommand::new(
+            manager.clone(),
+            end("K4", "", "A4"),
+            end("X1", "XT", "4"),
+        )))
+        .unwrap();
+    let added = saved(&dir);
+    commands
+        .execute_command(Box::new(DeleteConnectionCommand::new(
+            vec![original[0].0],
+            manager.clone(),
+        )))
+        .unwrap();
+    commands.undo().unwrap();
+    drop(commands);
+    drop(manager);
+
+    let manager = reopen(&dir);
+    let mut commands = CommandManager::restore(history(&dir), &manager).unwrap();
+    assert!(commands.can_undo());
+    assert!(commands.can_redo());
+    assert_eq!(saved(&dir), added);
+
+    commands.redo().unwrap();
+    assert_eq!(saved(&dir), added[1..].to_vec());
+    commands.undo().unwrap();
+    commands.undo().unwrap();
+    assert_eq!(saved(&dir), original);
+    assert!(!commands.can_undo());
+
+    // The restored steps were logged as well, so a second reopen ends up in the same place
+    let manager = reopen(&dir);
+    let commands = CommandManager::restore(history(&dir), &manager).unwrap();
+    assert!(!commands.can_undo());
+    assert!(commands.can_redo());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn changes_outside_the_history_clear_it_on_reopen() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let original = saved(&dir);
+    let mut commands = CommandManager::with_log(history(&dir), &manager);
+    commands
+        .execute_command(Box::new(DeleteConnectionCommand::new(
+            vec![original[0].0],
+            manager.clone(),
+        )))
+        .unwrap();
+    drop(commands);
+
+    // Edited without the history, as an import or another program would
+    reopen(&dir)
+        .lock()
+        .unwrap()
+        .add_connection(
+            "K5".to_string(),
+            "".to_string(),
+            "A5".to_string(),
+            "X1".to_string(),
+            "XT".to_string(),
+            "5".to_string(),
+        )
+        .unwrap();
+    let edited = saved(&dir);
+
+    let manager = reopen(&dir);
+    let mut commands = CommandManager::restore(history(&dir), &manager).unwrap();
+    assert!(!commands.can_undo());
+    assert!(!commands.can_redo());
+    assert_eq!(saved(&dir), edited);
+    let entries = history(&dir).entries().unwrap();
+    assert_eq!(entries.last().unwrap().action, Action::Reset);
+
+    // Steps made after the reset are restored on their own
+    commands
+        .execute_command(Box::new(DeleteConnectionCommand::new(
+            vec![edited[0].0],
+            manager.clone(),
+        )))
+        .unwrap();
+    let manager = reopen(&dir);
+    let mut commands = CommandManager::restore(history(&dir), &manager).unwrap();
+    commands.undo().unwrap();
+    assert_eq!(saved(&dir), edited);
+    assert!(!commands.can_undo());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn history_reads_as_an_audit_trail() {
+    let dir = temp_dir();
+    let manager = shared_manager(&dir);
+    let uuid = saved(&dir)[1].0;
+    let mut commands = CommandManager::with_log(history(&dir), &manager);
+
+    commands
+        .execute_command(Box::new(EditConnectionCommand::new(
+            manager.clone(),
+            uuid,
+            HashMap::from([("src_terminal".to_string(), "B2".to_string())]),
+        )))
+        .unwrap();
+    commands.undo().unwrap();
+
+    let entries = history(&dir).history_of(uuid).unwrap();
+    let actions: Vec<Action> = entries.iter().map(|entry| entry.action).collect();
+    assert_eq!(actions, vec![Action::Execute, Action::Undo]);
+    assert!(entries.iter().all(|entry| entry.author == "alice"));
+    match &entries[0].operations[..] {
+        [Operation::Edit { before, after }] => {
+            assert_eq!(before.src_terminal, "A2");
+            assert_eq!(after.src_terminal, "B2");
+            assert_eq!(after.uuid, uuid);
+        }
+        operations => panic!("expected one edit, got {:?}", operations),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,144 @@
+use std::sync::{Arc, Mutex};
+
+use label_wires::command::command::{Command, CommandError};
+use label_wires::command::command_manager::CommandManager;
+
+// Adds `amount` to a shared total; fails the steps listed in `fail`
+struct AddAmount {
+    total: Arc<Mutex<i32>>,
+    amount: i32,
+    fail: &'static [&'static str],
+}
+
+impl AddAmount {
+    fn boxed(total: &Arc<Mutex<i32>>, amount: i32) -> Box<dyn Command> {
+        Self::failing(total, amount, &[])
+    }
+
+    fn failing(
+        total: &Arc<Mutex<i32>>,
+        amount: i32,
+        fail: &'static [&'static str],
+    ) -> Box<dyn Command> {
+        Box::new(Self {
+            total: total.clone(),
+            amount,
+            fail,
+        })
+    }
+
+    fn apply(&self, step: &str, amount: i32) -> Result<(), CommandError> {
+        if self.fail.contains(&step) {
+            return Err(CommandError::Other(format!("{} failed", step)));
+        }
+        *self.total.lock().unwrap() += amount;
+        Ok(())
+    }
+}
+
+impl Command for AddAmount {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        self.apply("execute", self.amount)
+    }
+
+    fn undo(&mut self) -> Result<(), CommandError> {
+        self.apply("undo", -self.amount)
+    }
+
+    fn redo(&mut self) -> Result<(), CommandError> {
+        self.apply("redo", self.amount)
+    }
+}
+
+fn total(total: &Arc<Mutex<i32>>) -> i32 {
+    *total.lock().unwrap()
+}
+
+#[test]
+fn undo_and_redo_walk_the_history() {
+    let sum = Arc::new(Mutex::new(0));
+    let mut commands = CommandManager::new();
+    assert!(!commands.can_undo());
+    assert!(!commands.can_redo());
+
+    commands.execute_command(AddAmount::boxed(&sum, 1)).unwrap();
+    commands
+        .execute_command(AddAmount::boxed(&sum, 10))
+        .unwrap();
+    assert_eq!(total(&sum), 11);
+    assert!(commands.can_undo());
+
+    commands.undo().unwrap();
+    assert_eq!(total(&sum), 1);
+    assert!(commands.can_redo());
+    commands.undo().unwrap();
+    assert_eq!(total(&sum), 0);
+    assert!(!commands.can_undo());
+
+    commands.redo().unwrap();
+    commands.redo().unwrap();
+    assert_eq!(total(&sum), 11);
+    assert!(!commands.can_redo());
+}
+
+#[test]
+fn a_new_command_clears_redo() {
+    let sum = Arc::new(Mutex::new(0));
+    let mut commands = CommandManager::new();
+
+    commands.execute_command(AddAmount::boxed(&sum, 1)).unwrap();
+    commands.undo().unwrap();
+    commands.execute_command(AddAmount::boxed(&sum, 5)).unwrap();
+
+    assert!(!commands.can_redo());
+    assert!(commands.redo().is_err());
+    assert_eq!(total(&sum), 5);
+}
+
+#[test]
+fn empty_stacks_are_errors() {
+    let mut commands = CommandManager::new();
+
+    assert!(matches!(commands.undo(), Err(CommandError::Other(_))));
+    assert!(matches!(commands.redo(), Err(CommandError::Other(_))));
+}
+
+#[test]
+fn failed_commands_are_not_recorded() {
+    let sum = Arc::new(Mutex::new(0));
+    let mut commands = CommandManager::new();
+    commands.execute_command(AddAmount::boxed(&sum, 1)).unwrap();
+    commands.undo().unwrap();
+
+    assert!(commands
+        .execute_command(AddAmount::failing(&sum, 5, &["execute"]))
+        .is_err());
+
+    // The failed command neither became undoable nor cleared the redo stack
+    assert_eq!(total(&sum), 0);
+    assert!(!commands.can_undo());
+    commands.redo().unwrap();
+    assert_eq!(total(&sum), 1);
+}
+
+#[test]
+fn failed_undo_and_redo_keep_their_command() {
+    let sum = Arc::new(Mutex::new(0));
+    let mut commands = CommandManager::new();
+    commands
+        .execute_command(AddAmount::failing(&sum, 1, &["undo"]))
+        .unwrap();
+
+    assert!(commands.undo().is_err());
+    assert!(commands.can_undo());
+    assert!(!commands.can_redo());
+    assert_eq!(total(&sum), 1);
+
+    commands
+        .execute_command(AddAmount::failing(&sum, 10, &["redo"]))
+        .unwrap();
+    commands.undo().unwrap();
+    assert!(commands.redo().is_err());
+    assert!(commands.can_redo());
+    assert_eq!(total(&sum), 1);
+}
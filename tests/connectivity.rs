@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::PathBuf;
+
+use label_wires::models::bridge::{Bridge, BridgeKind};
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::connectivity::{ConnectivityGraph, EdgeKind};
+use label_wires::models::terminal::TerminalAddress;
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn terminal(designation: &str) -> TerminalAddress {
+    let (component, terminal) = designation.split_once(':').unwrap();
+    TerminalAddress::new(component, "", terminal)
+}
+
+fn terminals(designations: &[&str]) -> Vec<TerminalAddress> {
+    designations.iter().map(|d| terminal(d)).collect()
+}
+
+fn wire(source: &str, destination: &str) -> Connection {
+    let mut connection = Connection::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    );
+    connection.set_source_terminal(terminal(source));
+    connection.set_destination_terminal(terminal(destination));
+    connection
+}
+
+#[test]
+fn nets_follow_wires_and_jumpers() {
+    let mut graph = ConnectivityGraph::from_connections(&[
+        wire("K1:13", "X1:1"),
+        wire("X1:1", "K2:13"),
+        wire("K1:A1", "X1:2"),
+    ]);
+    graph.add_jumper(terminal("K1:13"), terminal("K1:14"));
+
+    assert_eq!(
+        graph.nets(),
+        vec![
+            terminals(&["K1:13", "K1:14", "K2:13", "X1:1"]),
+            terminals(&["K1:A1", "X1:2"]),
+        ]
+    );
+    assert_eq!(
+        graph.connected_to(&terminal("K2:13")),
+        terminals(&["K1:13", "K1:14", "X1:1"])
+    );
+    assert!(graph.connected_to(&terminal("K9:1")).is_empty());
+    assert_eq!(graph.net_of(&terminal("K9:1")), None);
+}
+
+#[test]
+fn loops_report_the_closing_edge() {
+    let first = wire("K1:1", "X1:1");
+    let parallel = wire("X1:1", "K1:1");
+    let graph = ConnectivityGraph::from_connections(&[
+        first,
+        wire("X1:1", "X1:2"),
+        parallel.clone(),
+        wire("X1:2", "K1:1"),
+    ]);
+
+    let loops = graph.loops();
+
+    assert_eq!(loops.len(), 2);
+    assert_eq!(loops[0].from, terminal("X1:1"));
+    assert_eq!(loops[0].to, terminal("K1:1"));
+    assert_eq!(loops[0].kind, EdgeKind::Wire(parallel.uuid));
+    assert_eq!(loops[1].from, terminal("X1:2"));
+}
+
+#[test]
+fn islands_and_open_ends_ignore_jumpers_and_bridges() {
+    let mut graph = ConnectivityGraph::from_connections(&[
+        wire("K1:1", "X1:1"),
+        wire("K2:1", "X2:1"),
+        wire("K3:1", "X3:1"),
+    ]);
+    // A bridge carries X2:1 on to X2:2 and X2:3, a jumper carries K3:1 to an unused K3:2
+    graph.add_bridge(&Bridge::spanning(
+        "X2".to_string(),
+        String::new(),
+        1,
+        3,
+        BridgeKind::PlugIn,
+    ));
+    graph.add_jumper(terminal("K3:1"), terminal("K3:2"));
+    // Two terminals joined by nothing but a bridge
+    graph.add_bridge(&Bridge::spanning(
+        "X4".to_string(),
+        String::new(),
+        1,
+        2,
+        BridgeKind::Screw,
+    ));
+
+    assert_eq!(graph.islands(), vec![terminals(&["K1:1", "X1:1"])]);
+    assert_eq!(
+        graph.open_ends(),
+        terminals(&["K1:1", "K2:1", "X1:1", "X3:1"])
+    );
+}
+
+#[test]
+fn catalog_jumpers_to_unused_terminals_are_not_open_ends() {
+    let dir = temp_dir();
+    let catalog_file = dir.join("catalog.json");
+    fs::write(
+        &catalog_file,
+        r#"{"components": [{"name": "K1", "terminal_blocks": [
+            {"terminals": "13/14/21/22", "internal_jumpers": [["13", "21"]]}
+        ]}]}"#,
+    )
+    .unwrap();
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    settings["catalog_file"] = Value::String(catalog_file.to_string_lossy().to_string());
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    let mut manager =
+        ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap();
+    manager.insert_connection(wire("K1:13", "X1:1")).unwrap();
+
+    let graph = manager.connectivity_graph();
+
+    assert_eq!(
+        graph.connected_to(&terminal("X1:1")),
+        terminals(&["K1:13", "K1:21"])
+    );
+    assert_eq!(graph.open_ends(), terminals(&["X1:1"]));
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::utils::csv_exporting_strategy::ExportFormat;
+use label_wires::utils::label_exporting_strategy::LabelingMode;
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// The shipped settings file with `changes` applied, written to `dir`
+fn write_settings(dir: &Path, changes: &[(&str, &str)]) -> PathBuf {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    for (key, value) in changes {
+        settings[key] = Value::String(value.to_string());
+    }
+    let path = dir.join("settings.json");
+    fs::write(&path, settings.to_string()).unwrap();
+    path
+}
+
+fn manager(dir: &Path, changes: &[(&str, &str)]) -> ConnectionManager {
+    let settings_file = write_settings(dir, changes);
+    let mut manager =
+        ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap();
+    for (component, terminal) in [("K1", "A1"), ("K2", "A2")] {
+        manager
+            .add_connection(
+                component.to_string(),
+                "".to_string(),
+                terminal.to_string(),
+                "X1".to_string(),
+                "".to_string(),
+                terminal.trim_start_matches('A').to_string(),
+            )
+            .unwrap();
+    }
+    manager
+}
+
+#[test]
+fn default_settings_export_source_and_destination_only() {
+    let dir = temp_dir();
+    let manager = manager(&dir, &[]);
+
+    let file_path = manager.default_export_path();
+    manager
+        .export_to_csv(ExportFormat::Wire, file_path.clone())
+        .unwrap();
+
+    assert_eq!(file_path, dir.join("connections.csv"));
+    assert_eq!(
+        fs::read_to_string(file_path).unwrap(),
+        "K1-A1|X1-1\nK2-A2|X1-2\n"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn export_follows_the_csv_settings() {
+    let dir = temp_dir();
+    let mut manager = manager(
+        &dir,
+        &[
+            ("default_csv_delimiter", ";"),
+            ("csv_include_header", "true"),
+            ("csv_line_ending", "crlf"),
+            ("csv_utf8_bom", "true"),
+            ("csv_columns", "destination,source,signal_name"),
+        ],
+    );
+    manager.connections[0].signal_name = Some("24V; fused".to_string());
+
+    let file_path = manager.default_export_path();
+    manager
+        .export_to_csv(ExportFormat::Wire, file_path.clone())
+        .unwrap();
+
+    assert_eq!(
+        fs::read(file_path).unwrap(),
+        "\u{feff}destination;source;signal_name\r\nX1-1;K1-A1;\"24V; fused\"\r\nX1-2;K2-A2;\r\n"
+            .as_bytes()
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn labels_follow_the_labeling_settings() {
+    let dir = temp_dir();
+    let mut manager = manager(
+        &dir,
+        &[
+            ("labeling_mode", "near_far"),
+            ("wire_number_display", "number_and_address"),
+        ],
+    );
+    manager.connections[0].wire_number = Some("101".to_string());
+
+    let records = manager.connection_labels().unwrap();
+    let labels: Vec<Vec<&str>> = records.iter().map(|label| label.printed_lines()).collect();
+
+    assert_eq!(
+        labels,
+        vec![
+            vec!["101", "K1-A1", "X1-1"],
+            vec!["101", "X1-1", "K1-A1"],
+            vec!["K2-A2", "X1-2"],
+            vec!["X1-2", "K2-A2"],
+        ]
+    );
+    // The CSV export writes one row per label as well
+    let csv = manager
+        .export_strategy(ExportFormat::Wire)
+        .unwrap()
+        .generate_csv_string(manager.get_connections());
+    assert_eq!(csv.lines().count(), 4);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn invalid_export_settings_are_reported() {
+    let dir = temp_dir();
+    let manager = manager(&dir, &[("csv_quote_style", "sometimes")]);
+
+    let error = manager
+        .export_to_csv(ExportFormat::Wire, manager.default_export_path())
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "Invalid quote style: sometimes");
+    assert!(manager.connection_labels().is_err());
+    assert!(!manager.default_export_path().exists());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn labels_use_the_configured_label_exporter() {
+    let dir = temp_dir();
+    let manager = manager(
+        &dir,
+        &[
+            ("label_exporter", "brady_workstation"),
+            ("labeling_mode", "far_only"),
+        ],
+    );
+
+    let file_path = manager.export_labels(dir.join("labels.txt")).unwrap();
+
+    assert_eq!(file_path, dir.join("labels.csv"));
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "\u{feff}\"Line1\",\"Line2\"\r\n\"X1-1\",\"\"\r\n\"K1-A1\",\"\"\r\n\
+         \"X1-2\",\"\"\r\n\"K2-A2\",\"\"\r\n"
+    );
+
+    // The exporter's own labeling mode decides the layout
+    let mut exporter = manager.label_exporter().unwrap();
+    exporter.set_labeling_mode(LabelingMode::Single);
+    let file_path = manager
+        .export_labels_with(exporter.as_ref(), dir.join("single.csv"))
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "\u{feff}\"Line1\",\"Line2\"\r\n\"K1-A1\",\"X1-1\"\r\n\"K2-A2\",\"X1-2\"\r\n"
+    );
+
+    let unknown = self::manager(&dir, &[("label_exporter", "dymo")]);
+    assert_eq!(
+        unknown.label_exporter().err().unwrap().to_string(),
+        "Unknown label exporter: dymo"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use label_wires::command::command_manager::CommandManager;
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::wire_attributes::WireColor;
+use label_wires::utils::csv_exporting_strategy::ExportFormat;
+use label_wires::utils::csv_importing_strategy::{ImportFormat, ImportOptions, ImportRowError};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager in `dir` using the shipped settings with `changes` applied
+fn manager(dir: &Path, changes: &[(&str, &str)]) -> ConnectionManager {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    for (key, value) in changes {
+        settings[key] = Value::String(value.to_string());
+    }
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap()
+}
+
+fn add(manager: &mut ConnectionManager, source: [&str; 3], destination: [&str; 3]) {
+    let [component, block, terminal] = source.map(String::from);
+    let [dst_component, dst_block, dst_terminal] = destination.map(String::from);
+    manager
+        .add_connection(
+            component,
+            block,
+            terminal,
+            dst_component,
+            dst_block,
+            dst_terminal,
+        )
+        .unwrap();
+}
+
+// Every field except the UUID, which is new for imported wires
+fn fields(connection: &Connection) -> Vec<String> {
+    let mut fields = vec![
+        connection.src_function.clone(),
+        connection.src_location.clone(),
+        connection.src_component.clone(),
+        connection.src_terminal_block.clone(),
+        connection.src_terminal.clone(),
+        connection.dst_function.clone(),
+        connection.dst_location.clone(),
+        connection.dst_component.clone(),
+        connection.dst_terminal_block.clone(),
+        connection.dst_terminal.clone(),
+        connection.wire_number.clone().unwrap_or_default(),
+    ];
+    fields.extend(connection.attribute_columns());
+    fields
+}
+
+// Exports `source` as `format` with its settings, imports the file into a fresh project with the
+// same settings and returns the imported wires
+fn round_trip(
+    source: &ConnectionManager,
+    dir: &Path,
+    changes: &[(&str, &str)],
+    export: ExportFormat,
+    import: ImportFormat,
+) -> Vec<Connection> {
+    let file_path = dir.join("export.csv");
+    source.export_to_csv(export, file_path.clone()).unwrap();
+
+    let target_dir = dir.join("target");
+    fs::create_dir_all(&target_dir).unwrap();
+    let target = Arc::new(Mutex::new(manager(&target_dir, changes)));
+    let options = target.lock().unwrap().import_options().unwrap();
+    let report = import
+        .strategy(options)
+        .import_from_csv(file_path, &target, &mut CommandManager::new())
+        .unwrap();
+    assert_eq!(report.errors, vec![]);
+    report.imported
+}
+
+fn assert_same_wires(imported: &[Connection], original: &[Connection]) {
+    let imported: Vec<_> = imported.iter().map(fields).collect();
+    let original: Vec<_> = original.iter().map(fields).collect();
+    assert_eq!(imported, original);
+}
+
+#[test]
+fn wire_layout_round_trips() {
+    let dir = temp_dir();
+    let mut source = manager(&dir, &[]);
+    add(&mut source, ["K1", "X1", "3"], ["M1", "", "U1"]);
+    add(&mut source, ["K2", "", "A1"], ["X2", "XT", "PE"]);
+
+    let imported = round_trip(&source, &dir, &[], ExportFormat::Wire, ImportFormat::Wire);
+
+    assert_same_wires(&imported, source.get_connections());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cable_layout_round_trips_block_only_ends() {
+    let dir = temp_dir();
+    let mut source = manager(&dir, &[]);
+    add(&mut source, ["K1", "X1", ""], ["M1", "", "U1"]);
+    add(&mut source, ["K2", "X2", "4"], ["X2", "XT", "PE"]);
+
+    let imported = round_trip(&source, &dir, &[], ExportFormat::Cable, ImportFormat::Cable);
+
+    assert_eq!(imported[0].src_terminal_block, "X1");
+    assert_same_wires(&imported, source.get_connections());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn configured_columns_header_bom_and_pattern_round_trip() {
+    let dir = temp_dir();
+    let changes = [
+        ("default_csv_delimiter", ";"),
+        ("csv_include_header", "true"),
+        ("csv_utf8_bom", "true"),
+        (
+            "csv_columns",
+            "signal_name,destination,wire_number,source,color,length_mm",
+        ),
+        (
+            "designation_pattern",
+            "{=component}{+terminal_block}{:terminal}",
+        ),
+    ];
+    let mut source = manager(&dir, &changes);
+    add(&mut source, ["K1", "X1", ""], ["M1", "", "U1"]);
+    add(&mut source, ["K2", "", "A1"], ["X2", "XT", "PE"]);
+    source.connections[0].signal_name = Some("Motor; fused".to_string());
+    source.connections[0].wire_number = Some("101".to_string());
+    source.connections[1].color = Some("BK".parse::<WireColor>().unwrap());
+    source.connections[1].length_mm = Some(1500);
+
+    let imported = round_trip(
+        &source,
+        &dir,
+        &changes,
+        ExportFormat::Wire,
+        ImportFormat::Wire,
+    );
+
+    assert_eq!(imported[0].src_terminal_block, "X1");
+    assert_same_wires(&imported, source.get_connections());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn serde_layout_round_trips() {
+    let dir = temp_dir();
+    let mut source = manager(&dir, &[]);
+    add(&mut source, ["K1", "X1", "3"], ["M1", "", "U1"]);
+    source.connections[0].wire_type = Some("H07V-K".to_string());
+
+    let csv = source.generate_csv_string().unwrap();
+    let target_dir = dir.join("target");
+    fs::create_dir_all(&target_dir).unwrap();
+    let target = Arc::new(Mutex::new(manager(&target_dir, &[])));
+    let report = ImportFormat::Serde
+        .strategy(ImportOptions {
+            delimiter: b',',
+            has_header: true,
+            ..ImportOptions::default()
+        })
+        .import_from_csv_str(&csv, &target, &mut CommandManager::new())
+        .unwrap();
+
+    assert_eq!(report.errors, vec![]);
+    assert_same_wires(&report.imported, source.get_connections());
+    assert_ne!(report.imported[0].uuid, source.get_connections()[0].uuid);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn row_errors_name_their_line() {
+    let dir = temp_dir();
+    let target = Arc::new(Mutex::new(manager(&dir, &[])));
+    let mut commands = CommandManager::new();
+    let options = ImportOptions {
+        has_header: true,
+        ..ImportOptions::default()
+    };
+    let contents = "\u{feff}source|destination|length_mm\n\
+                    K1-X1-1|M1-U1|1500\n\
+                    K1-X1-2|M1-V1|long\n\
+                    |\n\
+                    K1-X1-1|M1-U1\n\
+                    K1-X1-3|M1-W1|200|extra\n\
+                    K1-X1-1..4|M2-1..3\n";
+
+    let report = ImportFormat::Wire
+        .strategy(options)
+        .import_from_csv_str(contents, &target, &mut commands)
+        .unwrap();
+
+    let lines: Vec<u64> = report.errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, vec![3, 4, 5, 6, 7]);
+    assert_eq!(report.imported.len(), 1);
+    assert_eq!(
+        report.errors[2],
+        ImportRowError {
+            line: 5,
+            message: "duplicate connection".to_string()
+        }
+    );
+
+    let report = ImportFormat::Wire
+        .strategy(ImportOptions {
+            has_header: true,
+            ..ImportOptions::default()
+        })
+        .import_from_csv_str("source|pin\nK1-X1-1|M1-U1\n", &target, &mut commands)
+        .unwrap();
+    assert_eq!(report.errors[0].line, 1);
+    assert!(report.imported.is_empty());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn an_import_is_undone_as_one_step() {
+    let dir = temp_dir();
+    let mut existing = manager(&dir, &[]);
+    add(&mut existing, ["K1", "", "A1"], ["X1", "", "1"]);
+    let target = Arc::new(Mutex::new(existing));
+    let mut commands = CommandManager::new();
+
+    let report = ImportFormat::Wire
+        .strategy(ImportOptions::default())
+        .import_from_csv_str(
+            "K1-A1|X1-1\nK2-1..3|X2-1..3\nK3-A1|X3-1\n",
+            &target,
+            &mut commands,
+        )
+        .unwrap();
+
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].line, 1);
+    assert_eq!(report.imported.len(), 4);
+    assert_eq!(target.lock().unwrap().connections.len(), 5);
+
+    commands.undo().unwrap();
+    assert_eq!(target.lock().unwrap().connections.len(), 1);
+    assert!(!commands.can_undo());
+    commands.redo().unwrap();
+    let connections = target.lock().unwrap().connections.clone();
+    assert_same_wires(&connections[1..], &report.imported);
+    assert_eq!(connections[1].uuid, report.imported[0].uuid);
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,94 @@
+use label_wires::models::designation::{DesignationError, DesignationFormatter};
+use label_wires::models::terminal::TerminalAddress;
+
+fn parts(address: &TerminalAddress) -> [&str; 5] {
+    [
+        &address.function,
+        &address.location,
+        &address.component,
+        &address.terminal_block,
+        &address.terminal,
+    ]
+}
+
+fn parse(pattern: &str, text: &str) -> [String; 5] {
+    let address = pattern
+        .parse::<DesignationFormatter>()
+        .unwrap()
+        .parse(text)
+        .unwrap();
+    parts(&address).map(String::from)
+}
+
+#[test]
+fn default_pattern_keeps_the_first_and_last_parts_when_one_is_missing() {
+    let pattern = DesignationFormatter::DEFAULT_PATTERN;
+
+    assert_eq!(parse(pattern, "K1-X1-3"), ["", "", "K1", "X1", "3"]);
+    assert_eq!(parse(pattern, "K1-A1"), ["", "", "K1", "", "A1"]);
+    assert_eq!(parse(pattern, "K1"), ["", "", "K1", "", ""]);
+    // Extra separators end up in the last part
+    assert_eq!(parse(pattern, "K1-X1-L1-1"), ["", "", "K1", "X1", "L1-1"]);
+}
+
+#[test]
+fn prefixes_and_suffixes_mark_their_parts() {
+    let pattern = "{=component}{+terminal_block}{:terminal}";
+
+    assert_eq!(parse(pattern, "=A1+X1:3"), ["", "", "A1", "X1", "3"]);
+    assert_eq!(parse(pattern, "=A1:3"), ["", "", "A1", "", "3"]);
+    assert_eq!(parse(pattern, "+X1:3"), ["", "", "", "X1", "3"]);
+
+    let cable = DesignationFormatter::CABLE_PATTERN;
+    assert_eq!(parse(cable, "K1-X1 [3]"), ["", "", "K1", "X1", "3"]);
+    assert_eq!(parse(cable, "K1 [3]"), ["", "", "K1", "", "3"]);
+}
+
+#[test]
+fn iec_preset_reads_every_aspect() {
+    assert_eq!(
+        parse("iec_81346", "=PUMP1+CAB2-K1-X1:A1"),
+        ["PUMP1", "CAB2", "K1", "X1", "A1"]
+    );
+    assert_eq!(parse("iec_81346", "-K1:A1"), ["", "", "K1", "", "A1"]);
+}
+
+#[test]
+fn parse_inverts_format() {
+    let address = TerminalAddress::new("K1", "X1", "3").with_aspects("PUMP1", "CAB2");
+    for (_, pattern) in DesignationFormatter::PRESETS {
+        let formatter = DesignationFormatter::new(pattern).unwrap();
+        let parsed = formatter.parse(&formatter.format(&address)).unwrap();
+        assert_eq!(formatter.format(&parsed), formatter.format(&address));
+    }
+}
+
+#[test]
+fn text_outside_the_pattern_is_an_error() {
+    let formatter = DesignationFormatter::new("{=component}{:terminal}").unwrap();
+
+    assert_eq!(
+        formatter.parse("K1-A1"),
+        Err(DesignationError::NoMatch {
+            pattern: "{=component}{:terminal}".to_string(),
+            text: "K1-A1".to_string(),
+        })
+    );
+    assert!(DesignationFormatter::default().parse("").is_err());
+}
+
+#[test]
+fn invalid_patterns_are_rejected() {
+    assert!(matches!(
+        DesignationFormatter::new("{component}-{pin}"),
+        Err(DesignationError::UnknownPlaceholder(_))
+    ));
+    assert!(matches!(
+        DesignationFormatter::new("{component"),
+        Err(DesignationError::UnclosedBrace(_))
+    ));
+    assert!(matches!(
+        DesignationFormatter::new("K1-"),
+        Err(DesignationError::NoPlaceholder(_))
+    ));
+}
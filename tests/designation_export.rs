@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::utils::csv_exporting_strategy::{ExportFormat, ExportToCsvStrategy};
+use label_wires::utils::zpl_exporting_strategy::ZplExporter;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn manager(dir: &Path, pattern: &str) -> ConnectionManager {
+    let mut manager = ConnectionManager::new(
+        None,
+        dir.join("connections.json"),
+        dir.join("settings.json"),
+    )
+    .unwrap();
+    manager.metadata.designation_pattern = pattern.to_string();
+    for (component, block, terminal) in [("K1", "X1", "3"), ("K2", "", "A2")] {
+        manager
+            .add_connection(
+                component.to_string(),
+                block.to_string(),
+                terminal.to_string(),
+                "M1".to_string(),
+                "".to_string(),
+                "U1".to_string(),
+            )
+            .unwrap();
+    }
+    manager
+}
+
+#[test]
+fn exports_use_the_project_designation_pattern() {
+    let dir = temp_dir();
+    let manager = manager(&dir, "{=component}{+terminal_block}{:terminal}");
+
+    let csv = manager
+        .export_strategy(ExportFormat::Wire)
+        .unwrap()
+        .generate_csv_string(manager.get_connections());
+    assert_eq!(csv, "=K1+X1:3|=M1:U1\n=K2:A2|=M1:U1");
+
+    // The cable layout follows the configured pattern as well
+    let csv = manager
+        .cable_export_strategy()
+        .unwrap()
+        .generate_csv_string(manager.get_connections());
+    assert_eq!(csv, "=K1+X1:3|=M1:U1\n=K2:A2|=M1:U1");
+
+    let exporter = ZplExporter::default();
+    let labels = manager.connection_labels().unwrap();
+    assert_eq!(labels[0].lines, vec!["=K1+X1:3", "=M1:U1"]);
+    let zpl = exporter.render_zpl(&labels);
+    assert!(zpl.contains("^FD=K1+X1:3^FS"));
+    assert!(!zpl.contains("K1-X1-3"));
+
+    assert_eq!(
+        manager.get_connections()[1].to_tuple(&manager.designation_formatter()),
+        ("=K2:A2".to_string(), "=M1:U1".to_string())
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn layouts_keep_their_own_pattern_when_none_is_configured() {
+    let dir = temp_dir();
+    let manager = manager(&dir, "");
+
+    let csv = manager
+        .export_strategy(ExportFormat::Wire)
+        .unwrap()
+        .generate_csv_string(manager.get_connections());
+    assert_eq!(csv, "K1-X1-3|M1-U1\nK2-A2|M1-U1");
+
+    let csv = manager
+        .export_strategy(ExportFormat::Cable)
+        .unwrap()
+        .generate_csv_string(manager.get_connections());
+    assert_eq!(csv, "K1-X1 [3]|M1 [U1]\nK2 [A2]|M1 [U1]");
+
+    fs::remove_dir_all(dir).unwrap();
+}
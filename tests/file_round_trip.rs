@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::project::CURRENT_FORMAT_VERSION;
+use label_wires::models::wire_attributes::{CrossSection, WireColor};
+use label_wires::utils::file_handler::{FileHandler, FileHandlerError};
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn assert_same_fields(loaded: &Connection, original: &Connection) {
+    assert_eq!(loaded.uuid, original.uuid);
+    assert_eq!(loaded.src_component, original.src_component);
+    assert_eq!(loaded.src_terminal_block, original.src_terminal_block);
+    assert_eq!(loaded.src_terminal, original.src_terminal);
+    assert_eq!(loaded.dst_component, original.dst_component);
+    assert_eq!(loaded.dst_terminal_block, original.dst_terminal_block);
+    assert_eq!(loaded.dst_terminal, original.dst_terminal);
+    // Catches any field added to `Connection` later on
+    assert_eq!(loaded.to_dict().unwrap(), original.to_dict().unwrap());
+}
+
+// A connection with every optional attribute set
+fn fully_populated() -> Connection {
+    let mut connection = Connection::new(
+        "Q1".to_string(),
+        "X3".to_string(),
+        "2".to_string(),
+        "M1".to_string(),
+        "".to_string(),
+        "U1".to_string(),
+    );
+    connection.cross_section = Some(CrossSection::SquareMillimetres(2.5));
+    connection.color = Some(WireColor::Other("Silver".to_string()));
+    connection.length_mm = Some(1250);
+    connection.wire_type = Some("H07V-K".to_string());
+    connection.signal_name = Some("Motor; phase U".to_string());
+    connection.src_function = "PUMP1".to_string();
+    connection.src_location = "CAB1".to_string();
+    connection.dst_function = "PUMP1".to_string();
+    connection.dst_location = "FIELD".to_string();
+    connection.wire_number = Some("U-101".to_string());
+    connection.page = Some(12);
+    connection.column = Some(4);
+    connection
+}
+
+fn populated_manager(output_file: &Path, settings_file: &Path) -> ConnectionManager {
+    let mut manager =
+        ConnectionManager::new(None, output_file.to_path_buf(), settings_file.to_path_buf())
+            .unwrap();
+    manager
+        .add_connection(
+            "K1".to_string(),
+            "".to_string(),
+            "A1".to_string(),
+            "X1".to_string(),
+            "XT".to_string(),
+            "3".to_string(),
+        )
+        .unwrap();
+    manager
+        .add_connection(
+            "K2".to_string(),
+            "X2".to_string(),
+            "14".to_string(),
+            "X1".to_string(),
+            "XT".to_string(),
+            "4".to_string(),
+        )
+        .unwrap();
+    manager.insert_connection(fully_populated()).unwrap();
+    manager
+}
+
+#[test]
+fn save_then_load_keeps_every_field() {
+    let dir = temp_dir();
+    let output_file = dir.join("connections.json");
+    let settings_file = dir.join("settings.json");
+
+    let manager = populated_manager(&output_file, &settings_file);
+    let loaded = FileHandler::new(Some(output_file)).load_wires().unwrap();
+
+    assert_eq!(loaded.len(), manager.connections.len());
+    for (loaded, original) in loaded.iter().zip(manager.connections.iter()) {
+        assert_same_fields(loaded, original);
+    }
+    assert_eq!(loaded[2].wire_number.as_deref(), Some("U-101"));
+    assert_eq!(
+        loaded[2].color,
+        Some(WireColor::Other("Silver".to_string()))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn reopening_a_project_keeps_uuids() {
+    let dir = temp_dir();
+    let output_file = dir.join("connections.json");
+    let settings_file = dir.join("settings.json");
+
+    let manager = populated_manager(&output_file, &settings_file);
+    let reopened =
+        ConnectionManager::new(Some(output_file.clone()), output_file, settings_file).unwrap();
+
+    assert_eq!(reopened.connections.len(), manager.connections.len());
+    for (loaded, original) in reopened.connections.iter().zip(manager.connections.iter()) {
+        assert_same_fields(loaded, original);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn json_value_round_trip_keeps_every_field() {
+    let original = Connection::new(
+        "K1".to_string(),
+        "X1".to_string(),
+        "13".to_string(),
+        "M1".to_string(),
+        "".to_string(),
+        "U1".to_string(),
+    );
+    let restored = Connection::from_json_value(original.to_dict().unwrap()).unwrap();
+    assert_same_fields(&restored, &original);
+
+    let original = fully_populated();
+    let restored = Connection::from_json_value(original.to_dict().unwrap()).unwrap();
+    assert_same_fields(&restored, &original);
+}
+
+#[test]
+fn legacy_bare_array_files_still_open() {
+    let dir = temp_dir();
+    let legacy_file = dir.join("legacy.json");
+    fs::write(
+        &legacy_file,
+        r#"[{"src_component": "K1", "src_terminal_block": "", "src_terminal": "A1",
+             "dst_component": "X1", "dst_terminal_block": "", "dst_terminal": "3"}]"#,
+    )
+    .unwrap();
+
+    let project = FileHandler::new(Some(legacy_file)).load_project().unwrap();
+
+    assert_eq!(project.format_version, CURRENT_FORMAT_VERSION);
+    assert_eq!(project.connections.len(), 1);
+    assert_eq!(project.connections[0].src_terminal, "A1");
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn newer_format_versions_are_rejected() {
+    let dir = temp_dir();
+    let future_file = dir.join("future.json");
+    fs::write(
+        &future_file,
+        format!(r#"{{"format_version": {}}}"#, CURRENT_FORMAT_VERSION + 1),
+    )
+    .unwrap();
+
+    let result = FileHandler::new(Some(future_file)).load_project();
+
+    assert!(matches!(
+        result,
+        Err(FileHandlerError::UnsupportedFormatVersion(_))
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,56 @@
+use label_wires::utils::increment::increment_terminal;
+
+#[test]
+fn trailing_numbers_count_up_and_keep_their_padding() {
+    assert_eq!(increment_terminal("3", 1), Some("4".to_string()));
+    assert_eq!(increment_terminal("03", 1), Some("04".to_string()));
+    assert_eq!(increment_terminal("09", 1), Some("10".to_string()));
+    assert_eq!(increment_terminal("99", 1), Some("100".to_string()));
+    assert_eq!(increment_terminal("A9", 1), Some("A10".to_string()));
+    assert_eq!(increment_terminal("L1.09", 1), Some("L1.10".to_string()));
+    assert_eq!(increment_terminal(" 13 ", 2), Some("15".to_string()));
+    assert_eq!(increment_terminal("A1", 0), Some("A1".to_string()));
+}
+
+#[test]
+fn trailing_letters_count_like_spreadsheet_columns() {
+    assert_eq!(increment_terminal("A", 1), Some("B".to_string()));
+    assert_eq!(increment_terminal("Z", 1), Some("AA".to_string()));
+    assert_eq!(increment_terminal("AZ", 1), Some("BA".to_string()));
+    assert_eq!(increment_terminal("ZZ", 1), Some("AAA".to_string()));
+    assert_eq!(increment_terminal("A", 26), Some("AA".to_string()));
+    assert_eq!(increment_terminal("PE", 3), Some("PH".to_string()));
+    assert_eq!(increment_terminal("1.A", 1), Some("1.B".to_string()));
+}
+
+#[test]
+fn letters_keep_their_case() {
+    assert_eq!(increment_terminal("a", 1), Some("b".to_string()));
+    assert_eq!(increment_terminal("z", 1), Some("aa".to_string()));
+    assert_eq!(increment_terminal("Xb", 1), Some("Xc".to_string()));
+    assert_eq!(increment_terminal("Xz", 1), Some("Ya".to_string()));
+    assert_eq!(increment_terminal("zZ", 1), Some("aaA".to_string()));
+}
+
+#[test]
+fn large_steps_are_computed_directly() {
+    assert_eq!(
+        increment_terminal("A", u32::MAX),
+        Some("MWLQKWV".to_string())
+    );
+    assert_eq!(
+        increment_terminal("7", u32::MAX),
+        Some("4294967302".to_string())
+    );
+    // Past 64 bits there is nothing to count up to
+    assert_eq!(increment_terminal(&"Z".repeat(14), 1), None);
+    assert_eq!(increment_terminal("18446744073709551615", 1), None);
+}
+
+#[test]
+fn values_without_a_count_are_left_alone() {
+    assert_eq!(increment_terminal("", 1), None);
+    assert_eq!(increment_terminal("  ", 1), None);
+    assert_eq!(increment_terminal("L+", 1), None);
+    assert_eq!(increment_terminal("Ä", 1), None);
+}
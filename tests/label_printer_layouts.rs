@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::PathBuf;
+
+use label_wires::models::connection::Connection;
+use label_wires::utils::csv_exporting_strategy::{ExportToCsvStrategy, ExportWireToCSVStrategy};
+use label_wires::utils::export_options::ExportOptions;
+use label_wires::utils::label_exporting_strategy::{
+    label_exporter_by_name, label_exporter_names, LabelRecord, LabelingMode, WireNumberDisplay,
+};
+
+fn connection(src: [&str; 3], dst: [&str; 3]) -> Connection {
+    Connection::new(
+        src[0].to_string(),
+        src[1].to_string(),
+        src[2].to_string(),
+        dst[0].to_string(),
+        dst[1].to_string(),
+        dst[2].to_string(),
+    )
+}
+
+// Covers an empty terminal block, a field containing delimiters, non-ASCII text and a
+// designation longer than the M-Print PRO field length limit
+fn sample_labels() -> Vec<LabelRecord> {
+    let connections = vec![
+        connection(["K1", "", "A1"], ["X1", "XT", "3"]),
+        connection(["-Q1", "X2", "L1;N"], ["M1", "", "U1"]),
+        connection(["Ölpumpe", "X3", "1"], ["K2", "X3", "14"]),
+        connection(
+            ["ENCODER_FEEDBACK_CABINET_2", "X20", "101"],
+            ["SERVO_DRIVE_AXIS_3", "X21", "7"],
+        ),
+    ];
+    ExportWireToCSVStrategy::default().label_records(&connections)
+}
+
+fn golden_path(name: &str, extension: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{}.{}", name, extension))
+}
+
+fn assert_matches_golden(name: &str) {
+    let exporter = label_exporter_by_name(name).unwrap();
+    let rendered = exporter.render(&sample_labels()).unwrap();
+    let expected = fs::read(golden_path(name, exporter.extension())).unwrap();
+    assert_eq!(
+        rendered, expected,
+        "{} output differs from golden file",
+        name
+    );
+}
+
+#[test]
+fn mprint_pro_matches_golden_file() {
+    assert_matches_golden("mprint_pro");
+}
+
+#[test]
+fn clip_project_matches_golden_file() {
+    assert_matches_golden("clip_project");
+}
+
+#[test]
+fn brady_workstation_matches_golden_file() {
+    assert_matches_golden("brady_workstation");
+}
+
+#[test]
+fn generic_csv_matches_golden_file() {
+    assert_matches_golden("generic_csv");
+}
+
+#[test]
+fn clip_and_brady_cut_fields_to_their_length_limit() {
+    let labels = vec![LabelRecord::new(vec!["W".repeat(60), "Ö".repeat(60)])];
+
+    let clip = label_exporter_by_name("clip_project")
+        .unwrap()
+        .render(&labels)
+        .unwrap();
+    let clip: Vec<u16> = clip
+        .chunks(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    assert_eq!(
+        String::from_utf16(&clip).unwrap(),
+        format!(
+            "\u{feff}Line 1\tLine 2\r\n{}\t{}\r\n",
+            "W".repeat(40),
+            "Ö".repeat(40)
+        )
+    );
+
+    let brady = label_exporter_by_name("brady_workstation")
+        .unwrap()
+        .render(&labels)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(brady).unwrap(),
+        format!(
+            "\u{feff}\"Line1\",\"Line2\"\r\n\"{}\",\"{}\"\r\n",
+            "W".repeat(50),
+            "Ö".repeat(50)
+        )
+    );
+}
+
+#[test]
+fn every_exporter_is_selectable_by_name() {
+    for name in label_exporter_names() {
+        assert_eq!(label_exporter_by_name(name).unwrap().name(), name);
+    }
+    assert!(label_exporter_by_name("MPRINT_PRO").is_some());
+    assert!(label_exporter_by_name("unknown").is_none());
+}
+
+#[test]
+fn two_label_modes_emit_a_label_for_each_wire_end() {
+    let connections = vec![connection(["K1", "", "A1"], ["X1", "XT", "3"])];
+    let strategy = ExportWireToCSVStrategy::default();
+    let lines = |mode: LabelingMode| -> Vec<Vec<String>> {
+        strategy
+            .label_records_with(mode, &connections)
+            .into_iter()
+            .map(|label| label.lines)
+            .collect()
+    };
+
+    assert_eq!(lines(LabelingMode::Single), [["K1-A1", "X1-XT-3"]]);
+    assert_eq!(
+        lines(LabelingMode::NearFar),
+        [["K1-A1", "X1-XT-3"], ["X1-XT-3", "K1-A1"]]
+    );
+    assert_eq!(lines(LabelingMode::FarOnly), [["X1-XT-3"], ["K1-A1"]]);
+    assert_eq!(
+        lines(LabelingMode::BothAddresses),
+        [["K1-A1 / X1-XT-3"], ["K1-A1 / X1-XT-3"]]
+    );
+
+    let mut exporter = label_exporter_by_name("generic_csv").unwrap();
+    exporter.set_labeling_mode(LabelingMode::FarOnly);
+    assert_eq!(exporter.connection_labels(&strategy, &connections).len(), 2);
+
+    let csv = ExportWireToCSVStrategy::new(ExportOptions {
+        labeling_mode: LabelingMode::NearFar,
+        ..ExportOptions::default()
+    })
+    .generate_csv_string(&connections);
+    assert_eq!(csv, "K1-A1|X1-XT-3\nX1-XT-3|K1-A1");
+}
+
+#[test]
+fn two_column_layouts_give_the_wire_number_its_own_column() {
+    let mut numbered = connection(["K1", "", "A1"], ["X1", "XT", "3"]);
+    numbered.wire_number = Some("101".to_string());
+    let connections = vec![numbered, connection(["K2", "", "A2"], ["X1", "XT", "4"])];
+    let labels = ExportWireToCSVStrategy::new(ExportOptions {
+        wire_number_display: WireNumberDisplay::NumberAndAddress,
+        ..ExportOptions::default()
+    })
+    .label_records(&connections);
+
+    // Both addresses survive next to the number, and unnumbered wires leave its field empty
+    let generic = label_exporter_by_name("generic_csv")
+        .unwrap()
+        .render(&labels)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(generic).unwrap(),
+        "wire_number;source;destination\n101;K1-A1;X1-XT-3\n;K2-A2;X1-XT-4\n"
+    );
+
+    let brady = label_exporter_by_name("brady_workstation")
+        .unwrap()
+        .render(&labels)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(brady).unwrap(),
+        "\u{feff}\"Number\",\"Line1\",\"Line2\"\r\n\
+         \"101\",\"K1-A1\",\"X1-XT-3\"\r\n\
+         \"\",\"K2-A2\",\"X1-XT-4\"\r\n"
+    );
+
+    // Without any number the layout keeps its own columns
+    let unnumbered = ExportWireToCSVStrategy::default().label_records(&connections[1..]);
+    let generic = label_exporter_by_name("generic_csv")
+        .unwrap()
+        .render(&unnumbered)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(generic).unwrap(),
+        "source;destination\nK2-A2;X1-XT-4\n"
+    );
+}
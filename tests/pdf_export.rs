@@ -0,0 +1,233 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::utils::label_exporting_strategy::{LabelExporter, LabelRecord, LabelingMode};
+use label_wires::utils::pdf_exporting_strategy::{PdfExporter, PdfOptions, SheetTemplate};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn label(lines: &[&str]) -> LabelRecord {
+    LabelRecord::new(lines.iter().map(|line| line.to_string()).collect())
+}
+
+fn labels(count: usize) -> Vec<LabelRecord> {
+    (1..=count)
+        .map(|n| label(&[&format!("K{}-A1", n), "X1-1"]))
+        .collect()
+}
+
+fn exporter(start_offset: usize) -> PdfExporter {
+    PdfExporter::new(PdfOptions {
+        start_offset,
+        ..PdfOptions::default()
+    })
+}
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager holding one K1-A1 → X1-1 connection, using the shipped settings with `changes`
+fn manager(dir: &Path, changes: &[(&str, &str)]) -> ConnectionManager {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    for (key, value) in changes {
+        settings[key] = Value::String(value.to_string());
+    }
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    let mut manager =
+        ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap();
+    manager
+        .add_connection(
+            "K1".to_string(),
+            String::new(),
+            "A1".to_string(),
+            "X1".to_string(),
+            String::new(),
+            "1".to_string(),
+        )
+        .unwrap();
+    manager
+}
+
+fn find(pdf: &[u8], needle: &str) -> Vec<usize> {
+    pdf.windows(needle.len())
+        .enumerate()
+        .filter(|(_, window)| *window == needle.as_bytes())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+// Content streams in page order, as text
+fn content_streams(pdf: &[u8]) -> Vec<String> {
+    let text = String::from_utf8_lossy(pdf);
+    text.split(">>\nstream\n")
+        .skip(1)
+        .filter_map(|part| part.split_once("endstream").map(|(content, _)| content))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn xref_offsets_point_at_their_objects() {
+    let pdf = exporter(0).render_pdf(&labels(70));
+    let text = String::from_utf8_lossy(&pdf);
+
+    let startxref: usize = text
+        .rsplit_once("startxref\n")
+        .unwrap()
+        .1
+        .lines()
+        .next()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(pdf[startxref..].starts_with(b"xref\n0 8\n"));
+
+    let entries: Vec<&str> = text[startxref..].lines().skip(3).take(7).collect();
+    for (i, entry) in entries.iter().enumerate() {
+        assert!(entry.ends_with(" 00000 n "), "{}", entry);
+        let offset: usize = entry[..10].parse().unwrap();
+        assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+    }
+    assert!(text.contains("trailer\n<< /Size 8 /Root 1 0 R >>"));
+    assert!(text.ends_with("%%EOF\n"));
+}
+
+#[test]
+fn stream_lengths_match_their_content() {
+    let pdf = exporter(0).render_pdf(&labels(3));
+
+    for start in find(&pdf, "<< /Length ") {
+        let header = String::from_utf8_lossy(&pdf[start..start + 40]).to_string();
+        let length: usize = header["<< /Length ".len()..]
+            .split_once(' ')
+            .unwrap()
+            .0
+            .parse()
+            .unwrap();
+        let content = start + header.find("stream\n").unwrap() + "stream\n".len();
+        assert!(pdf[content + length..].starts_with(b"endstream"));
+    }
+}
+
+#[test]
+fn start_offset_skips_used_labels_and_pages_at_the_sheet_end() {
+    let per_sheet = SheetTemplate::AVERY_L7651.labels_per_sheet();
+    let labels_on = |pdf: &[u8]| -> Vec<usize> {
+        content_streams(pdf)
+            .iter()
+            .map(|content| content.matches(" re W n").count())
+            .collect()
+    };
+
+    // Two free labels left on the first sheet
+    let pdf = exporter(per_sheet - 2).render_pdf(&labels(3));
+    assert_eq!(labels_on(&pdf), vec![2, 1]);
+    assert!(String::from_utf8_lossy(&pdf).contains("/Count 2"));
+
+    // The first label of the second sheet sits at the top left corner again
+    let (left, top) = SheetTemplate::AVERY_L7651.label_origin(0);
+    let height = SheetTemplate::AVERY_L7651.label_height;
+    let page_height = SheetTemplate::AVERY_L7651.page_height;
+    let points = 72.0 / 25.4;
+    let corner = format!(
+        "{:.2} {:.2} ",
+        left * points,
+        page_height * points - top * points - height * points
+    );
+    assert!(content_streams(&pdf)[1].starts_with(&format!("q\n{}", corner)));
+
+    // An offset past the sheet still prints on the last label of the first sheet
+    assert_eq!(
+        labels_on(&exporter(per_sheet + 5).render_pdf(&labels(2))),
+        vec![1, 1]
+    );
+    // No labels still gives one empty page
+    assert_eq!(labels_on(&exporter(0).render_pdf(&[])), vec![0]);
+}
+
+#[test]
+fn font_size_shrinks_to_fit_the_label() {
+    let pdf = exporter(0);
+    let template = &pdf.options.template;
+    let points = 72.0 / 25.4;
+    let available_width = (template.label_width - 2.0 * pdf.options.padding) * points;
+    let available_height = (template.label_height - 2.0 * pdf.options.padding) * points;
+
+    // Short text is set at the largest size
+    assert_eq!(pdf.font_size_for(&label(&["K1-A1", "X1-1"])), 10.0);
+
+    // `W` is 944/1000 em wide in Helvetica
+    let thirteen = pdf.font_size_for(&label(&[&"W".repeat(13)]));
+    assert!((thirteen - available_width / (13.0 * 0.944)).abs() < 0.001);
+    let twenty_six = pdf.font_size_for(&label(&[&"W".repeat(26)]));
+    assert!((thirteen - 2.0 * twenty_six).abs() < 0.001);
+
+    // Ten lines are limited by the label height
+    let ten_lines = pdf.font_size_for(&label(&["A"; 10]));
+    assert!((ten_lines - available_height / (10.0 * 1.2)).abs() < 0.001);
+
+    // Text that cannot fit stops at the smallest size and is clipped
+    assert_eq!(pdf.font_size_for(&label(&[&"W".repeat(100)])), 4.0);
+}
+
+#[test]
+fn options_are_read_from_the_settings() {
+    let dir = temp_dir();
+    let manager = manager(
+        &dir,
+        &[
+            ("label_exporter", "pdf"),
+            ("labeling_mode", "near_far"),
+            ("pdf_sheet_template", "Avery_5167"),
+            ("pdf_start_offset", "79"),
+            ("pdf_max_font_size", "8"),
+            ("pdf_min_font_size", "6.5"),
+        ],
+    );
+    let options = PdfOptions {
+        template: SheetTemplate::AVERY_5167,
+        start_offset: 79,
+        max_font_size: 8.0,
+        min_font_size: 6.5,
+        labeling_mode: LabelingMode::NearFar,
+        ..PdfOptions::default()
+    };
+
+    let file_path = manager.export_labels(dir.join("labels")).unwrap();
+    let expected = PdfExporter::new(options)
+        .render(&[label(&["K1-A1", "X1-1"]), label(&["X1-1", "K1-A1"])])
+        .unwrap();
+    let pdf = fs::read(file_path).unwrap();
+    assert_eq!(pdf, expected);
+    // The second label of the wire starts a new sheet
+    assert_eq!(find(&pdf, "/Type /Page ").len(), 2);
+
+    for (key, value, message) in [
+        (
+            "pdf_sheet_template",
+            "avery_9999",
+            "Unknown label sheet template: avery_9999",
+        ),
+        ("pdf_start_offset", "65", "Invalid number: 65"),
+        ("pdf_max_font_size", "-1", "Invalid number: -1"),
+        (
+            "pdf_min_font_size",
+            "12",
+            "Minimum font size 12 is larger than the maximum 10",
+        ),
+    ] {
+        let invalid = self::manager(&dir, &[("label_exporter", "pdf"), (key, value)]);
+        assert_eq!(invalid.label_exporter().err().unwrap().to_string(), message);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
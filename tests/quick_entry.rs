@@ -0,0 +1,93 @@
+use label_wires::models::designation::DesignationFormatter;
+use label_wires::models::terminal::TerminalAddress;
+use label_wires::utils::quick_entry::{QuickEntry, QuickEntryError};
+
+fn address(component: &str, terminal_block: &str, terminal: &str) -> TerminalAddress {
+    TerminalAddress::new(component, terminal_block, terminal)
+}
+
+#[test]
+fn sides_are_read_with_the_designation_pattern() {
+    let entry = QuickEntry::parse("K1-A1 > X1-XT-3", &DesignationFormatter::default()).unwrap();
+
+    assert_eq!(entry.source, address("K1", "", "A1"));
+    assert_eq!(entry.destination, address("X1", "XT", "3"));
+    assert!(!entry.reference_designation);
+    assert_eq!(
+        entry.to_line(&DesignationFormatter::default()),
+        "K1-A1 > X1-XT-3"
+    );
+}
+
+#[test]
+fn reference_designations_are_recognised_with_the_default_pattern() {
+    let entry =
+        QuickEntry::parse("=P1+C2-K1:A1 -> X1:3", &DesignationFormatter::default()).unwrap();
+
+    assert_eq!(
+        entry.source,
+        address("K1", "", "A1").with_aspects("P1", "C2")
+    );
+    assert_eq!(entry.destination, address("X1", "", "3"));
+    assert!(entry.reference_designation);
+}
+
+#[test]
+fn patterns_using_iec_prefixes_are_tried_first() {
+    let formatter = DesignationFormatter::new("{=component}{+terminal_block}{:terminal}").unwrap();
+    let entry = QuickEntry::parse("=A1+X1:3 > =K2:4", &formatter).unwrap();
+
+    assert_eq!(entry.source, address("A1", "X1", "3"));
+    assert_eq!(entry.destination, address("K2", "", "4"));
+    assert!(!entry.reference_designation);
+    assert_eq!(entry.to_line(&formatter), "=A1+X1:3 > =K2:4");
+
+    // Sides the pattern cannot read still fall back to reference designations
+    let entry = QuickEntry::parse("-K1:A1 > =K2:4", &formatter).unwrap();
+    assert_eq!(entry.source, address("K1", "", "A1"));
+}
+
+#[test]
+fn dotted_ranges_expand() {
+    let entry = QuickEntry::parse("K1-1..4 > X1-5..8", &DesignationFormatter::default()).unwrap();
+    let connections = entry.connections().unwrap();
+
+    assert_eq!(connections.len(), 4);
+    assert_eq!(connections[3].src_terminal, "4");
+    assert_eq!(connections[3].dst_terminal, "8");
+}
+
+#[test]
+fn dash_ranges_that_read_as_terminal_blocks_are_rejected() {
+    let formatter = DesignationFormatter::default();
+
+    assert_eq!(
+        QuickEntry::parse("X1-1-16 > K2-1-16", &formatter),
+        Err(QuickEntryError::AmbiguousRange(
+            "X1-1-16".to_string(),
+            "X1-1..16".to_string()
+        ))
+    );
+    // One side alone is a terminal block, not a range
+    let entry = QuickEntry::parse("X1-1-16 > K2-3", &formatter).unwrap();
+    assert_eq!(entry.source, address("X1", "1", "16"));
+    assert_eq!(entry.connections().unwrap().len(), 1);
+}
+
+#[test]
+fn malformed_lines_are_reported() {
+    let formatter = DesignationFormatter::default();
+
+    assert_eq!(
+        QuickEntry::parse("K1-A1 X1-3", &formatter),
+        Err(QuickEntryError::MissingSeparator)
+    );
+    assert_eq!(
+        QuickEntry::parse("K1-A1 > X1-3 > X2-4", &formatter),
+        Err(QuickEntryError::TooManySeparators(">".to_string()))
+    );
+    assert_eq!(
+        QuickEntry::parse(" > X1-3", &formatter),
+        Err(QuickEntryError::EmptySide("source"))
+    );
+}
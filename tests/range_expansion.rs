@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use label_wires::models::connection::Connection;
+use label_wires::models::wire_attributes::WireColor;
+use label_wires::utils::range_expansion::{
+    expand_connection, expand_terminals, RangeError, MAX_RANGE_LEN,
+};
+
+fn terminals(spec: &str) -> Vec<String> {
+    expand_terminals(spec).unwrap()
+}
+
+fn numbers(range: std::ops::RangeInclusive<u32>) -> Vec<String> {
+    range.map(|n| n.to_string()).collect()
+}
+
+fn connection(src_terminal: &str, dst_terminal: &str) -> Connection {
+    Connection::new(
+        "X1".to_string(),
+        String::new(),
+        src_terminal.to_string(),
+        "K2".to_string(),
+        String::new(),
+        dst_terminal.to_string(),
+    )
+}
+
+#[test]
+fn dash_and_dot_ranges_are_inclusive() {
+    assert_eq!(terminals("1-16"), numbers(1..=16));
+    assert_eq!(terminals("1..16"), numbers(1..=16));
+    assert_eq!(terminals(" 3 .. 5 "), numbers(3..=5));
+    assert_eq!(terminals("16-13"), vec!["16", "15", "14", "13"]);
+    assert_eq!(terminals("7-7"), vec!["7"]);
+}
+
+#[test]
+fn prefixes_and_zero_padding_are_kept() {
+    assert_eq!(terminals("A1-A4"), vec!["A1", "A2", "A3", "A4"]);
+    assert_eq!(terminals("01..03"), vec!["01", "02", "03"]);
+    assert_eq!(terminals("X08-X10"), vec!["X08", "X09", "X10"]);
+}
+
+#[test]
+fn lists_combine_single_terminals_and_ranges() {
+    assert_eq!(terminals("A1,A2,13,14"), vec!["A1", "A2", "13", "14"]);
+    assert_eq!(
+        terminals("1-4, 9-12"),
+        vec!["1", "2", "3", "4", "9", "10", "11", "12"]
+    );
+    assert_eq!(
+        expand_terminals("1,,2"),
+        Err(RangeError::EmptyEntry("1,,2".to_string()))
+    );
+    assert_eq!(
+        expand_terminals("1-4,"),
+        Err(RangeError::EmptyEntry("1-4,".to_string()))
+    );
+}
+
+#[test]
+fn steps_skip_terminals() {
+    assert_eq!(terminals("1-8/2"), vec!["1", "3", "5", "7"]);
+    assert_eq!(terminals("1..9/4"), vec!["1", "5", "9"]);
+    assert_eq!(terminals("8-1/3"), vec!["8", "5", "2"]);
+    for spec in ["1-8/0", "1-8/x", "1-8/"] {
+        assert_eq!(
+            expand_terminals(spec),
+            Err(RangeError::InvalidStep(spec.to_string()))
+        );
+    }
+}
+
+#[test]
+fn terminals_that_are_not_ranges_come_back_unchanged() {
+    for spec in ["13", "PE", "L1-1", "A-B", "A1-B4", "PE/2"] {
+        assert_eq!(terminals(spec), vec![spec], "{}", spec);
+    }
+}
+
+#[test]
+fn expansions_are_limited_to_max_range_len() {
+    assert_eq!(
+        terminals(&format!("1-{}", MAX_RANGE_LEN)).len(),
+        MAX_RANGE_LEN
+    );
+    assert_eq!(terminals("1-100000/100").len(), MAX_RANGE_LEN);
+
+    let too_long = format!("1-{}", MAX_RANGE_LEN + 1);
+    assert_eq!(
+        expand_terminals(&too_long),
+        Err(RangeError::TooLong(too_long.clone()))
+    );
+    // Rejected before anything is expanded
+    assert_eq!(
+        expand_terminals("0-18446744073709551615"),
+        Err(RangeError::TooLong("0-18446744073709551615".to_string()))
+    );
+    // The limit applies to the whole list as well
+    let list = format!("1-{},A", MAX_RANGE_LEN);
+    assert_eq!(
+        expand_terminals(&list),
+        Err(RangeError::TooLong(list.clone()))
+    );
+}
+
+#[test]
+fn connections_pair_source_and_destination_terminals() {
+    let mut bulk = connection("1-16", "1..16");
+    bulk.color = Some(WireColor::Blue);
+    bulk.wire_number = Some("100".to_string());
+
+    let expanded = expand_connection(&bulk).unwrap();
+
+    assert_eq!(expanded.len(), 16);
+    for (n, wire) in (1..=16).zip(&expanded) {
+        assert_eq!(wire.src_terminal, n.to_string());
+        assert_eq!(wire.dst_terminal, n.to_string());
+        assert_eq!(wire.src_component, "X1");
+        assert_eq!(wire.dst_component, "K2");
+        assert_eq!(wire.color, Some(WireColor::Blue));
+        assert_eq!(wire.wire_number, None);
+    }
+    let uuids: HashSet<_> = expanded.iter().map(|wire| wire.uuid).collect();
+    assert_eq!(uuids.len(), 16);
+    assert!(!uuids.contains(&bulk.uuid));
+
+    // A single terminal on each side is the connection itself
+    let single = connection("13", "A1");
+    let expanded = expand_connection(&single).unwrap();
+    assert_eq!(expanded.len(), 1);
+    assert_eq!(expanded[0].uuid, single.uuid);
+}
+
+#[test]
+fn ranges_of_different_lengths_are_rejected() {
+    assert_eq!(
+        expand_connection(&connection("1-16", "1-8")).map(|wires| wires.len()),
+        Err(RangeError::LengthMismatch {
+            source_len: 16,
+            destination_len: 8,
+        })
+    );
+    assert_eq!(
+        expand_connection(&connection("1-4", "PE")).map(|wires| wires.len()),
+        Err(RangeError::LengthMismatch {
+            source_len: 4,
+            destination_len: 1,
+        })
+    );
+    assert_eq!(
+        expand_connection(&connection("A1,A2", "1-2/0")).map(|wires| wires.len()),
+        Err(RangeError::InvalidStep("1-2/0".to_string()))
+    );
+}
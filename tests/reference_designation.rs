@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::designation::DesignationFormatter;
+use label_wires::models::reference_designation::{
+    format_reference_designation, parse_reference_designation, ReferenceDesignationError,
+};
+use label_wires::models::terminal::TerminalAddress;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager in `dir` using a copy of the shipped settings
+fn manager(dir: &Path) -> ConnectionManager {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let settings_file = dir.join("settings.json");
+    fs::copy(shipped, &settings_file).unwrap();
+    ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap()
+}
+
+fn address(
+    function: &str,
+    location: &str,
+    component: &str,
+    block: &str,
+    terminal: &str,
+) -> TerminalAddress {
+    TerminalAddress::new(component, block, terminal).with_aspects(function, location)
+}
+
+#[test]
+fn every_aspect_is_read() {
+    assert_eq!(
+        parse_reference_designation("=PUMP1+CAB2-K1:A1"),
+        Ok(address("PUMP1", "CAB2", "K1", "", "A1"))
+    );
+    assert_eq!(
+        parse_reference_designation("+CAB2-X1-X2:3"),
+        Ok(address("", "CAB2", "X1", "X2", "3"))
+    );
+    assert_eq!(
+        parse_reference_designation("K1:A1"),
+        Ok(address("", "", "K1", "", "A1"))
+    );
+}
+
+#[test]
+fn repeated_levels_are_kept_together() {
+    let parsed = parse_reference_designation("=PLANT=PUMP1+HALL+CAB2-A1-K1-X1:4").unwrap();
+
+    assert_eq!(
+        parsed,
+        address("PLANT=PUMP1", "HALL+CAB2", "A1-K1", "X1", "4")
+    );
+    assert_eq!(
+        format_reference_designation(&parsed),
+        "=PLANT=PUMP1+HALL+CAB2-A1-K1-X1:4"
+    );
+}
+
+#[test]
+fn prefixes_inside_a_terminal_belong_to_it() {
+    assert_eq!(
+        parse_reference_designation("-X1:L1-1"),
+        Ok(address("", "", "X1", "", "L1-1"))
+    );
+    assert_eq!(
+        parse_reference_designation("-X1:PE+=2"),
+        Ok(address("", "", "X1", "", "PE+=2"))
+    );
+}
+
+#[test]
+fn dashes_without_a_terminal_differ_from_the_default_pattern() {
+    // Only `:` starts a terminal in a reference designation, while the default pattern reads
+    // the last part as the terminal
+    assert_eq!(
+        parse_reference_designation("K1-A1"),
+        Ok(address("", "", "K1", "A1", ""))
+    );
+    assert_eq!(
+        DesignationFormatter::default().parse("K1-A1"),
+        Ok(address("", "", "K1", "", "A1"))
+    );
+}
+
+#[test]
+fn malformed_designations_are_rejected() {
+    assert_eq!(
+        parse_reference_designation("  "),
+        Err(ReferenceDesignationError::Empty)
+    );
+    assert_eq!(
+        parse_reference_designation("=PUMP1+-K1"),
+        Err(ReferenceDesignationError::EmptyAspect {
+            aspect: "location",
+            designation: "=PUMP1+-K1".to_string(),
+        })
+    );
+    assert_eq!(
+        parse_reference_designation("-K1:"),
+        Err(ReferenceDesignationError::EmptyAspect {
+            aspect: "terminal",
+            designation: "-K1:".to_string(),
+        })
+    );
+    assert_eq!(
+        parse_reference_designation("-K1:A1:A2"),
+        Err(ReferenceDesignationError::MultipleTerminals(
+            "-K1:A1:A2".to_string()
+        ))
+    );
+}
+
+#[test]
+fn the_connection_list_filters_and_groups_by_aspect() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    for (source, destination) in [
+        (
+            address("PUMP1", "CAB1", "K1", "", "1"),
+            address("PUMP1", "CAB2", "X1", "", "1"),
+        ),
+        (
+            address("PUMP2", "CAB2", "K2", "", "1"),
+            address("PUMP2", "CAB2", "X1", "", "2"),
+        ),
+        (
+            address("", "", "K3", "", "1"),
+            address("", "", "X3", "", "1"),
+        ),
+    ] {
+        let mut connection = Connection::new(
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        );
+        connection.set_source_terminal(source);
+        connection.set_destination_terminal(destination);
+        manager.insert_connection(connection).unwrap();
+    }
+    let components = |connections: &[&Connection]| -> Vec<String> {
+        connections
+            .iter()
+            .map(|c| c.src_component.clone())
+            .collect()
+    };
+
+    let in_cab2 = manager.filter_connections("+CAB2");
+    assert_eq!(in_cab2.keys().collect::<Vec<_>>(), vec!["CAB2"]);
+    assert_eq!(components(&in_cab2["CAB2"]), vec!["K1", "K2"]);
+
+    let in_pump2 = manager.filter_connections(" =PUMP2 ");
+    assert_eq!(components(&in_pump2["PUMP2"]), vec!["K2"]);
+
+    let by_location = manager.filter_connections("+");
+    assert_eq!(
+        by_location.keys().collect::<Vec<_>>(),
+        vec!["", "CAB1", "CAB2"]
+    );
+    assert_eq!(components(&by_location["CAB1"]), vec!["K1"]);
+    assert_eq!(components(&by_location["CAB2"]), vec!["K1", "K2"]);
+
+    let by_function = manager.filter_connections("=");
+    assert_eq!(
+        by_function.keys().collect::<Vec<_>>(),
+        vec!["", "PUMP1", "PUMP2"]
+    );
+
+    let unfiltered = manager.filter_connections("");
+    assert_eq!(components(&unfiltered[""]), vec!["K1", "K2", "K3"]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
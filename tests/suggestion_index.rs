@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::PathBuf;
+
+use label_wires::models::catalog::ComponentCatalog;
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::suggestion_index::SuggestionIndex;
+use label_wires::models::terminal::TerminalAddress;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// `source` and `destination` as `component:block:terminal`
+fn wire(source: &str, destination: &str) -> Connection {
+    let address = |designation: &str| {
+        let parts: Vec<&str> = designation.split(':').collect();
+        TerminalAddress::new(parts[0], parts[1], parts[2])
+    };
+    let mut connection = Connection::new(
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+    );
+    connection.set_source_terminal(address(source));
+    connection.set_destination_terminal(address(destination));
+    connection
+}
+
+fn catalog() -> ComponentCatalog {
+    ComponentCatalog::from_csv_str(
+        "component,terminal_block,terminals\nK1,,A1;A2;13;14\nX1,XT,1-4\nX9,XS,PE\n",
+    )
+    .unwrap()
+}
+
+#[test]
+fn prefix_matches_rank_before_substring_and_fuzzy_matches() {
+    let index = SuggestionIndex::new(
+        &[
+            wire("KA1::1", "XK1::1"),
+            wire("K10::1", "K1::1"),
+            wire("K1::2", "K1::3"),
+        ],
+        None,
+    );
+
+    // Within a tier, more used names come first
+    assert_eq!(
+        index.suggest_components("k1", 10),
+        vec!["K1", "K10", "XK1", "KA1"]
+    );
+    assert_eq!(index.suggest_components("K1", 2), vec!["K1", "K10"]);
+    assert!(index.suggest_components("Q", 10).is_empty());
+    // An empty query lists every name by use
+    assert_eq!(index.suggest_components("", 1), vec!["K1"]);
+}
+
+#[test]
+fn equally_used_names_rank_shorter_first() {
+    let index = SuggestionIndex::new(&[wire("K20::1", "K2::1")], None);
+
+    assert_eq!(index.suggest_components("K2", 10), vec!["K2", "K20"]);
+}
+
+#[test]
+fn blocks_and_terminals_are_scoped_to_the_chosen_component() {
+    let index = SuggestionIndex::new(
+        &[wire("K1::A1", "X1:XT:1"), wire("K2:KB:5", "X2:XU:7")],
+        Some(&catalog()),
+    );
+
+    assert_eq!(index.suggest_terminal_blocks("X1", "", 10), vec!["XT"]);
+    assert_eq!(index.suggest_terminal_blocks("X2", "", 10), vec!["XU"]);
+    // Catalog blocks count even though no wire uses them yet
+    assert_eq!(index.suggest_terminal_blocks("X9", "", 10), vec!["XS"]);
+    // Components without blocks fall back to every block
+    assert_eq!(
+        index.suggest_terminal_blocks("K1", "X", 10),
+        vec!["XT", "XU", "XS"]
+    );
+
+    assert_eq!(
+        index.suggest_terminals("X1", "XT", "", 10),
+        vec!["1", "2", "3", "4"]
+    );
+    assert_eq!(
+        index.suggest_terminals("K1", "", "1", 10),
+        vec!["13", "14", "A1"]
+    );
+    assert_eq!(index.suggest_terminals("K2", "KB", "", 10), vec!["5"]);
+    assert_eq!(index.suggest_terminals("Q1", "", "7", 10), vec!["7"]);
+}
+
+#[test]
+fn catalog_names_rank_after_used_names() {
+    let index = SuggestionIndex::new(&[wire("X2::1", "K3::1")], Some(&catalog()));
+
+    assert_eq!(
+        index.suggest_components("", 10),
+        vec!["K3", "X2", "K1", "X1", "X9"]
+    );
+}
+
+#[test]
+fn adding_and_removing_wires_matches_a_rebuild() {
+    let first = wire("K1::A1", "X1:XT:1");
+    let second = wire("K2:KB:5", "X1:XT:2");
+    let mut index = SuggestionIndex::new(std::slice::from_ref(&first), None);
+
+    index.add_connection(&second);
+    let rebuilt = SuggestionIndex::new(&[first.clone(), second.clone()], None);
+    assert_eq!(
+        index.suggest_components("", 10),
+        rebuilt.suggest_components("", 10)
+    );
+    assert_eq!(index.suggest_components("", 10), vec!["X1", "K1", "K2"]);
+    assert_eq!(index.suggest_terminal_blocks("K2", "", 10), vec!["KB"]);
+
+    index.remove_connection(&second);
+    assert_eq!(index.suggest_components("", 10), vec!["K1", "X1"]);
+    assert_eq!(index.suggest_terminals("X1", "XT", "", 10), vec!["1"]);
+    // With its only block gone, K2 falls back to every block again
+    assert_eq!(index.suggest_terminal_blocks("K2", "", 10), vec!["XT"]);
+
+    index.remove_connection(&first);
+    assert!(index.suggest_components("", 10).is_empty());
+}
+
+#[test]
+fn the_manager_keeps_its_index_up_to_date() {
+    let dir = temp_dir();
+    let settings_file = dir.join("settings.json");
+    fs::copy(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/config/settings.json"
+        ),
+        &settings_file,
+    )
+    .unwrap();
+    let mut manager =
+        ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap();
+
+    let connection = wire("K7::A1", "X7::1");
+    let uuid = connection.uuid;
+    manager.insert_connection(connection).unwrap();
+    assert_eq!(
+        manager.suggestions().suggest_components("7", 10),
+        vec!["K7", "X7"]
+    );
+
+    let mut edited = wire("K8::A1", "X7::1");
+    edited.uuid = uuid;
+    manager.replace_connection(edited.clone()).unwrap();
+    assert_eq!(
+        manager.suggestions().suggest_components("7", 10),
+        vec!["X7"]
+    );
+
+    manager.delete_connection(&edited).unwrap();
+    assert!(manager.suggestions().suggest_components("", 10).is_empty());
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use label_wires::models::catalog::{CatalogPolicy, ComponentCatalog};
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::terminal::TerminalAddress;
+use label_wires::models::terminal_capacity::{
+    TerminalCapacityOverride, TerminalCapacityValidator, DEFAULT_MAX_WIRES_PER_TERMINAL,
+};
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// X1-XT terminals take three wires each, K1 has no capacity in the catalog
+fn catalog() -> ComponentCatalog {
+    ComponentCatalog::from_csv_str(
+        "component,terminal_block,terminals,description,max_wires_per_terminal\n\
+         X1,XT,1-4,Feed-through terminals,3\n\
+         K1,,A1;A2;13;14,,\n",
+    )
+    .unwrap()
+}
+
+fn reopen(dir: &Path) -> ConnectionManager {
+    let project = dir.join("connections.json");
+    let mut manager =
+        ConnectionManager::new(Some(project.clone()), project, dir.join("settings.json")).unwrap();
+    manager.set_catalog(Some(catalog()), CatalogPolicy::Reject);
+    manager
+}
+
+fn manager(dir: &Path) -> ConnectionManager {
+    fs::copy(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/config/settings.json"
+        ),
+        dir.join("settings.json"),
+    )
+    .unwrap();
+    let mut manager = ConnectionManager::new(
+        None,
+        dir.join("connections.json"),
+        dir.join("settings.json"),
+    )
+    .unwrap();
+    manager.set_catalog(Some(catalog()), CatalogPolicy::Reject);
+    manager
+}
+
+// Adds a wire between two `component:block:terminal` designations
+fn wire(manager: &mut ConnectionManager, source: &str, destination: &str) {
+    let [src_component, src_block, src_terminal]: [String; 3] = source
+        .split(':')
+        .map(str::to_string)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let [dst_component, dst_block, dst_terminal]: [String; 3] = destination
+        .split(':')
+        .map(str::to_string)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    manager
+        .add_connection(
+            src_component,
+            src_block,
+            src_terminal,
+            dst_component,
+            dst_block,
+            dst_terminal,
+        )
+        .unwrap();
+}
+
+// Three wires on K1-A1 and X1-XT-1 each, landing on either end of the connections
+fn add_wires(manager: &mut ConnectionManager) {
+    wire(manager, "K1::A1", "X1:XT:1");
+    wire(manager, "X1:XT:2", "K1::A1");
+    wire(manager, "K1::A1", "X1:XT:3");
+    wire(manager, "X1:XT:1", "K1::A2");
+    wire(manager, "K1::13", "X1:XT:1");
+}
+
+#[test]
+fn overrides_take_precedence_over_the_catalog_default() {
+    let catalog = catalog();
+    let feed_through = TerminalAddress::new("X1", "XT", "1");
+    let overrides = vec![TerminalCapacityOverride {
+        terminal: TerminalAddress::new("X1", "XT", "2"),
+        max_wires: 1,
+    }];
+    let validator = TerminalCapacityValidator::new(Some(&catalog), &overrides);
+
+    assert_eq!(validator.capacity_of(&feed_through), 3);
+    assert_eq!(
+        validator.capacity_of(&TerminalAddress::new("X1", "XT", "2")),
+        1
+    );
+    assert_eq!(
+        validator.capacity_of(&TerminalAddress::new("K1", "", "A1")),
+        DEFAULT_MAX_WIRES_PER_TERMINAL
+    );
+    assert_eq!(
+        TerminalCapacityValidator::new(None, &overrides).capacity_of(&feed_through),
+        DEFAULT_MAX_WIRES_PER_TERMINAL
+    );
+    assert_eq!(
+        TerminalCapacityValidator::new(None, &[])
+            .with_default_capacity(4)
+            .capacity_of(&feed_through),
+        4
+    );
+}
+
+#[test]
+fn wires_are_counted_on_both_ends() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    add_wires(&mut manager);
+
+    // Three wires fit the catalog's X1-XT-1 but not the default capacity of K1-A1
+    let overloads = manager.terminal_overloads();
+    assert_eq!(overloads.len(), 1);
+    assert_eq!(overloads[0].terminal, TerminalAddress::new("K1", "", "A1"));
+    assert_eq!(overloads[0].count, 3);
+    assert_eq!(overloads[0].capacity, DEFAULT_MAX_WIRES_PER_TERMINAL);
+    assert_eq!(
+        overloads[0].to_string(),
+        "Terminal K1-A1 has 3 wires but accepts at most 2"
+    );
+
+    wire(&mut manager, "K1::14", "X1:XT:1");
+    let overloaded: Vec<String> = manager
+        .terminal_overloads()
+        .iter()
+        .map(|overload| overload.terminal.to_string())
+        .collect();
+    assert_eq!(overloaded, vec!["K1-A1", "X1-XT-1"]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn overrides_are_saved_with_the_project() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    add_wires(&mut manager);
+    wire(&mut manager, "K1::14", "X1:XT:1");
+    let relay = TerminalAddress::new("K1", "", "A1");
+    let feed_through = TerminalAddress::new("X1", "XT", "1");
+
+    manager
+        .set_terminal_capacity(relay.clone(), Some(4))
+        .unwrap();
+    manager
+        .set_terminal_capacity(feed_through.clone(), Some(2))
+        .unwrap();
+    let overloads = manager.terminal_overloads();
+    assert_eq!(overloads.len(), 1);
+    assert_eq!(overloads[0].terminal, feed_through);
+    assert_eq!(overloads[0].capacity, 2);
+
+    let mut manager = reopen(&dir);
+    assert_eq!(manager.terminal_overloads(), overloads);
+
+    // Clearing the override falls back to the catalog default
+    manager.set_terminal_capacity(feed_through, None).unwrap();
+    let overloads = reopen(&dir).terminal_overloads();
+    assert_eq!(overloads.len(), 1);
+    assert_eq!(overloads[0].capacity, 3);
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,230 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use label_wires::command::command_manager::CommandManager;
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::wire_attributes::{
+    parse_length_mm, CrossSection, WireAttributeError, WireColor,
+};
+use label_wires::utils::csv_exporting_strategy::ExportFormat;
+use label_wires::utils::csv_importing_strategy::ImportFormat;
+use label_wires::utils::file_handler::FileHandler;
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager in `dir` whose CSV files have a header and every attribute column
+fn manager(dir: &Path) -> ConnectionManager {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    settings["csv_include_header"] = Value::String("true".to_string());
+    settings["csv_columns"] = Value::String(
+        "source,destination,cross_section,color,length_mm,wire_type,signal_name".to_string(),
+    );
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap()
+}
+
+fn wire(
+    terminal: &str,
+    cross_section: CrossSection,
+    color: WireColor,
+    length_mm: u32,
+) -> Connection {
+    let mut connection = Connection::new(
+        "K1".to_string(),
+        String::new(),
+        terminal.to_string(),
+        "X1".to_string(),
+        String::new(),
+        terminal.to_string(),
+    );
+    connection.cross_section = Some(cross_section);
+    connection.color = Some(color);
+    connection.length_mm = Some(length_mm);
+    connection.wire_type = Some("H07V-K".to_string());
+    connection.signal_name = Some("0V".to_string());
+    connection
+}
+
+fn attributes(connection: &Connection) -> [String; 5] {
+    connection.attribute_columns()
+}
+
+#[test]
+fn cross_sections_read_metric_and_awg_spellings() {
+    for (text, expected) in [
+        ("1.5", CrossSection::SquareMillimetres(1.5)),
+        ("1,5", CrossSection::SquareMillimetres(1.5)),
+        ("1.5mm2", CrossSection::SquareMillimetres(1.5)),
+        (" 2.5 mm² ", CrossSection::SquareMillimetres(2.5)),
+        ("18 AWG", CrossSection::Awg(18)),
+        ("awg18", CrossSection::Awg(18)),
+        ("AWG 22", CrossSection::Awg(22)),
+    ] {
+        assert_eq!(text.parse::<CrossSection>(), Ok(expected), "{}", text);
+    }
+    for text in ["", "0", "-1.5", "thick", "AWG", "1.5 AWG"] {
+        assert_eq!(
+            text.parse::<CrossSection>(),
+            Err(WireAttributeError::InvalidCrossSection(text.to_string()))
+        );
+    }
+}
+
+#[test]
+fn cross_sections_display_and_serialize_the_same_way() {
+    assert_eq!(CrossSection::SquareMillimetres(1.5).to_string(), "1.5 mm²");
+    assert_eq!(CrossSection::SquareMillimetres(1.0).to_string(), "1 mm²");
+    assert_eq!(CrossSection::Awg(18).to_string(), "18 AWG");
+
+    for cross_section in [CrossSection::SquareMillimetres(0.75), CrossSection::Awg(14)] {
+        let json = serde_json::to_string(&cross_section).unwrap();
+        assert_eq!(json, format!("\"{}\"", cross_section));
+        assert_eq!(
+            serde_json::from_str::<CrossSection>(&json).unwrap(),
+            cross_section
+        );
+        assert_eq!(
+            cross_section.to_string().parse::<CrossSection>(),
+            Ok(cross_section)
+        );
+    }
+    assert!(serde_json::from_str::<CrossSection>("\"thick\"").is_err());
+}
+
+#[test]
+fn colors_read_iec_codes_and_names() {
+    for (text, expected) in [
+        ("BU", WireColor::Blue),
+        ("blue", WireColor::Blue),
+        ("Gray", WireColor::Grey),
+        ("purple", WireColor::Violet),
+        ("GNYE", WireColor::GreenYellow),
+        ("gn-ye", WireColor::GreenYellow),
+        ("green/yellow", WireColor::GreenYellow),
+        (" Silver ", WireColor::Other("Silver".to_string())),
+    ] {
+        assert_eq!(text.parse::<WireColor>(), Ok(expected), "{}", text);
+    }
+    assert_eq!(
+        " ".parse::<WireColor>(),
+        Err(WireAttributeError::InvalidColor(" ".to_string()))
+    );
+}
+
+#[test]
+fn colors_display_and_serialize_as_their_code() {
+    assert_eq!(WireColor::GreenYellow.to_string(), "GNYE");
+    assert_eq!(WireColor::Other("Silver".to_string()).to_string(), "Silver");
+
+    for color in [
+        WireColor::Black,
+        WireColor::Brown,
+        WireColor::Turquoise,
+        WireColor::GreenYellow,
+        WireColor::Other("Silver".to_string()),
+    ] {
+        let json = serde_json::to_string(&color).unwrap();
+        assert_eq!(json, format!("\"{}\"", color.code()));
+        assert_eq!(serde_json::from_str::<WireColor>(&json).unwrap(), color);
+    }
+    assert_eq!(
+        serde_json::from_str::<WireColor>("\"red\"").unwrap(),
+        WireColor::Red
+    );
+}
+
+#[test]
+fn lengths_are_whole_millimetres() {
+    assert_eq!(parse_length_mm("1500"), Ok(1500));
+    assert_eq!(parse_length_mm(" 200mm "), Ok(200));
+    assert_eq!(parse_length_mm("200 mm"), Ok(200));
+    for text in ["", "long", "1.5", "-3", "2 m"] {
+        assert_eq!(
+            parse_length_mm(text),
+            Err(WireAttributeError::InvalidLength(text.to_string()))
+        );
+    }
+}
+
+#[test]
+fn attribute_columns_are_exported_and_imported() {
+    let dir = temp_dir();
+    let mut source = manager(&dir);
+    source
+        .insert_connection(wire(
+            "1",
+            CrossSection::SquareMillimetres(1.5),
+            WireColor::Blue,
+            1500,
+        ))
+        .unwrap();
+    source
+        .insert_connection(wire(
+            "2",
+            CrossSection::Awg(18),
+            WireColor::GreenYellow,
+            200,
+        ))
+        .unwrap();
+
+    let file_path = dir.join("export.csv");
+    source
+        .export_to_csv(ExportFormat::Wire, file_path.clone())
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "source|destination|cross_section|color|length_mm|wire_type|signal_name\n\
+         K1-1|X1-1|1.5 mm²|BU|1500|H07V-K|0V\n\
+         K1-2|X1-2|18 AWG|GNYE|200|H07V-K|0V\n"
+    );
+
+    let target_dir = dir.join("target");
+    fs::create_dir_all(&target_dir).unwrap();
+    let target = Arc::new(Mutex::new(manager(&target_dir)));
+    let options = target.lock().unwrap().import_options().unwrap();
+    let report = ImportFormat::Wire
+        .strategy(options)
+        .import_from_csv(file_path, &target, &mut CommandManager::new())
+        .unwrap();
+
+    assert_eq!(report.errors, vec![]);
+    let imported: Vec<_> = report.imported.iter().map(attributes).collect();
+    let exported: Vec<_> = source.connections.iter().map(attributes).collect();
+    assert_eq!(imported, exported);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn attributes_survive_saving_and_loading() {
+    let dir = temp_dir();
+    let mut manager = manager(&dir);
+    let original = wire("3", CrossSection::Awg(22), WireColor::Violet, 750);
+    manager.insert_connection(original.clone()).unwrap();
+
+    let loaded = FileHandler::new(Some(dir.join("connections.json")))
+        .load_wires()
+        .unwrap();
+
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].cross_section, original.cross_section);
+    assert_eq!(loaded[0].color, original.color);
+    assert_eq!(loaded[0].length_mm, original.length_mm);
+    assert_eq!(loaded[0].wire_type, original.wire_type);
+    assert_eq!(loaded[0].signal_name, original.signal_name);
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use label_wires::command::command::NumberWiresCommand;
+use label_wires::command::command_manager::CommandManager;
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::models::connectivity::ConnectivityGraph;
+use label_wires::models::wire_numbering::{
+    WireNumberTemplate, WireNumberingEngine, WireNumberingError, WireNumberingScheme,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager in `dir` using the shipped settings with `changes` applied
+fn manager(dir: &Path, changes: &[(&str, &str)]) -> ConnectionManager {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    for (key, value) in changes {
+        settings[key] = Value::String(value.to_string());
+    }
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap()
+}
+
+fn wire(source: [&str; 3], destination: [&str; 3]) -> Connection {
+    let [component, block, terminal] = source.map(String::from);
+    let [dst_component, dst_block, dst_terminal] = destination.map(String::from);
+    Connection::new(
+        component,
+        block,
+        terminal,
+        dst_component,
+        dst_block,
+        dst_terminal,
+    )
+}
+
+fn engine(scheme: WireNumberingScheme, template: &str) -> WireNumberingEngine {
+    WireNumberingEngine::new(scheme, WireNumberTemplate::new(template).unwrap())
+}
+
+fn numbers(connections: &[Connection]) -> Vec<&str> {
+    connections
+        .iter()
+        .map(|c| c.wire_number.as_deref().unwrap_or_default())
+        .collect()
+}
+
+#[test]
+fn templates_parse_and_reject_bad_placeholders() {
+    let mut connections = vec![wire(["K1", "", "1"], ["X1", "", "1"])];
+    let graph = ConnectivityGraph::from_connections(&connections);
+    engine(WireNumberingScheme::Sequential, "W{n:4}").renumber(&mut connections, &graph);
+    assert_eq!(numbers(&connections), vec!["W0001"]);
+
+    assert_eq!(
+        WireNumberTemplate::new("W"),
+        Err(WireNumberingError::MissingCounter("W".to_string()))
+    );
+    assert_eq!(
+        WireNumberTemplate::new("{sheet}{n}"),
+        Err(WireNumberingError::UnknownPlaceholder("sheet".to_string()))
+    );
+    assert_eq!(
+        WireNumberTemplate::new("{n:x}"),
+        Err(WireNumberingError::UnknownPlaceholder("n:x".to_string()))
+    );
+    assert_eq!(
+        WireNumberTemplate::new("W{n"),
+        Err(WireNumberingError::UnclosedBrace("W{n".to_string()))
+    );
+    assert_eq!(
+        "per_potential".parse(),
+        Ok(WireNumberingScheme::PerPotential)
+    );
+    assert!("by_colour".parse::<WireNumberingScheme>().is_err());
+}
+
+#[test]
+fn adding_wires_keeps_existing_numbers() {
+    let numbering = engine(WireNumberingScheme::Sequential, "{n}");
+    let mut connections = vec![
+        wire(["K1", "", "1"], ["X1", "", "1"]),
+        wire(["K1", "", "2"], ["X1", "", "2"]),
+        wire(["K1", "", "3"], ["X1", "", "3"]),
+    ];
+    let graph = ConnectivityGraph::from_connections(&connections);
+    numbering.assign_missing(&mut connections, &graph);
+    assert_eq!(numbers(&connections), vec!["1", "2", "3"]);
+
+    // A wire removed from the middle leaves a gap that a new wire fills
+    connections.remove(1);
+    connections.insert(0, wire(["K2", "", "1"], ["X2", "", "1"]));
+    connections.push(wire(["K2", "", "2"], ["X2", "", "2"]));
+    let graph = ConnectivityGraph::from_connections(&connections);
+    numbering.assign_missing(&mut connections, &graph);
+    assert_eq!(numbers(&connections), vec!["2", "1", "3", "4"]);
+
+    numbering.renumber(&mut connections, &graph);
+    assert_eq!(numbers(&connections), vec!["1", "2", "3", "4"]);
+}
+
+#[test]
+fn wires_of_one_potential_share_a_number() {
+    let numbering = engine(WireNumberingScheme::PerPotential, "{potential}-{n}");
+    let mut connections = vec![
+        wire(["K1", "", "1"], ["X1", "", "1"]),
+        wire(["M1", "", "U1"], ["K2", "", "1"]),
+        wire(["X1", "", "1"], ["K3", "", "1"]),
+        wire(["M1", "", "V1"], ["K2", "", "3"]),
+    ];
+    connections[2].signal_name = Some("24V".to_string());
+    connections[3].signal_name = Some("24V".to_string());
+    let graph = ConnectivityGraph::from_connections(&connections);
+
+    numbering.assign_missing(&mut connections, &graph);
+
+    // The first and third wire meet at X1:1, so they take the signal name found on the third
+    assert_eq!(numbers(&connections), vec!["24V-1", "-1", "24V-1", "24V-2"]);
+}
+
+#[test]
+fn page_and_column_scopes_count_separately() {
+    let numbering = engine(WireNumberingScheme::PerPageAndColumn, "{page}.{column}{n}");
+    let mut connections: Vec<Connection> = [(12, 3), (12, 3), (12, 4), (13, 3)]
+        .iter()
+        .enumerate()
+        .map(|(i, (page, column))| {
+            let terminal = (i + 1).to_string();
+            let mut connection = wire(["K1", "", &terminal], ["X1", "", &terminal]);
+            connection.page = Some(*page);
+            connection.column = Some(*column);
+            connection
+        })
+        .collect();
+    let graph = ConnectivityGraph::from_connections(&connections);
+
+    numbering.assign_missing(&mut connections, &graph);
+
+    assert_eq!(
+        numbers(&connections),
+        vec!["12.31", "12.32", "12.41", "13.31"]
+    );
+}
+
+#[test]
+fn numbering_is_one_undo_step() {
+    let dir = temp_dir();
+    let mut project = manager(
+        &dir,
+        &[
+            ("wire_numbering_scheme", "sequential"),
+            ("wire_number_template", "W{n:2}"),
+        ],
+    );
+    for terminal in ["1", "2", "3"] {
+        project
+            .add_connection(
+                "K1".to_string(),
+                String::new(),
+                terminal.to_string(),
+                "X1".to_string(),
+                String::new(),
+                terminal.to_string(),
+            )
+            .unwrap();
+    }
+    project.connections[1].wire_number = Some("W01".to_string());
+    let project = Arc::new(Mutex::new(project));
+    let mut commands = CommandManager::new();
+
+    commands
+        .execute_command(Box::new(NumberWiresCommand::number_new(project.clone())))
+        .unwrap();
+    assert_eq!(
+        numbers(&project.lock().unwrap().connections),
+        vec!["W02", "W01", "W03"]
+    );
+
+    commands
+        .execute_command(Box::new(NumberWiresCommand::renumber(project.clone())))
+        .unwrap();
+    assert_eq!(
+        numbers(&project.lock().unwrap().connections),
+        vec!["W01", "W02", "W03"]
+    );
+
+    commands.undo().unwrap();
+    assert_eq!(
+        numbers(&project.lock().unwrap().connections),
+        vec!["W02", "W01", "W03"]
+    );
+    commands.undo().unwrap();
+    assert_eq!(
+        numbers(&project.lock().unwrap().connections),
+        vec!["", "W01", ""]
+    );
+    commands.redo().unwrap();
+    assert_eq!(
+        numbers(&project.lock().unwrap().connections),
+        vec!["W02", "W01", "W03"]
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,230 @@
+use std::fs;
+use std::io::Read;
+use std::net::{SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use label_wires::models::connection::Connection;
+use label_wires::models::connection_manager::ConnectionManager;
+use label_wires::utils::csv_exporting_strategy::{ExportToCsvStrategy, ExportWireToCSVStrategy};
+use label_wires::utils::label_exporting_strategy::{LabelRecord, LabelingMode};
+use label_wires::utils::zpl_exporting_strategy::{
+    printer_addresses, ZplExporter, ZplOptions, ZplOrientation,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+fn sample_labels() -> Vec<LabelRecord> {
+    let connections = vec![
+        Connection::new(
+            "K1".to_string(),
+            "".to_string(),
+            "A1".to_string(),
+            "X1".to_string(),
+            "XT".to_string(),
+            "3".to_string(),
+        ),
+        Connection::new(
+            "K2".to_string(),
+            "X2".to_string(),
+            "14".to_string(),
+            "M1".to_string(),
+            "".to_string(),
+            "U1".to_string(),
+        ),
+    ];
+    ExportWireToCSVStrategy::default().label_records(&connections)
+}
+
+fn temp_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("label_wires_test_{}", Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// A manager holding one K1-A1 → X1-1 connection, using the shipped settings with `changes`
+fn manager(dir: &Path, changes: &[(&str, &str)]) -> ConnectionManager {
+    let shipped = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/resources/config/settings.json"
+    );
+    let mut settings: Value = serde_json::from_str(&fs::read_to_string(shipped).unwrap()).unwrap();
+    for (key, value) in changes {
+        settings[key] = Value::String(value.to_string());
+    }
+    let settings_file = dir.join("settings.json");
+    fs::write(&settings_file, settings.to_string()).unwrap();
+    let mut manager =
+        ConnectionManager::new(None, dir.join("connections.json"), settings_file).unwrap();
+    manager
+        .add_connection(
+            "K1".to_string(),
+            String::new(),
+            "A1".to_string(),
+            "X1".to_string(),
+            String::new(),
+            "1".to_string(),
+        )
+        .unwrap();
+    manager
+}
+
+#[test]
+fn renders_one_label_program_per_connection() {
+    let zpl = ZplExporter::default().render_zpl(&sample_labels());
+
+    assert_eq!(zpl.matches("^XA").count(), 2);
+    assert_eq!(zpl.matches("^XZ").count(), 2);
+    assert!(zpl.contains("^FO10,10^A0N,30,30^FDK1-A1^FS"));
+    assert!(zpl.contains("^FO10,47^A0N,30,30^FDX1-XT-3^FS"));
+    // 50 x 12 mm at 300 dpi
+    assert!(zpl.contains("^PW591\n^LL142\n"));
+}
+
+#[test]
+fn options_control_orientation_repeat_and_both_ends() {
+    let exporter = ZplExporter::new(ZplOptions {
+        orientation: ZplOrientation::Rotated,
+        repeat: 3,
+        both_ends: true,
+        ..ZplOptions::default()
+    });
+    let zpl = exporter.render_label(&sample_labels()[1]);
+
+    assert_eq!(zpl.matches("^FDK2-X2-14^FS").count(), 2);
+    assert!(zpl.contains("^FO10,10^A0R,30,30^FDK2-X2-14^FS"));
+    assert!(zpl.contains("^FO47,10^A0R,30,30^FDM1-U1^FS"));
+    assert!(zpl.contains("^FO10,81^A0R,30,30^FDK2-X2-14^FS"));
+    assert!(zpl.contains("^PQ3\n"));
+}
+
+#[test]
+fn escapes_zpl_control_characters() {
+    let label = LabelRecord::new(vec!["A^B~C_D".to_string()]);
+    let zpl = ZplExporter::default().render_label(&label);
+    assert!(zpl.contains("^FH^FDA_5EB_7EC_5FD^FS"));
+}
+
+#[test]
+fn streams_labels_to_a_raw_tcp_port() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let printer = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        received
+    });
+
+    let exporter = ZplExporter::default();
+    let labels = sample_labels();
+    exporter.send_to_printer(&address, &labels).unwrap();
+
+    assert_eq!(printer.join().unwrap(), exporter.render_zpl(&labels));
+}
+
+#[test]
+fn printer_addresses_accept_ipv6_and_default_the_port() {
+    let resolve = |address: &str| -> Vec<SocketAddr> { printer_addresses(address).unwrap() };
+    let socket = |address: &str| -> Vec<SocketAddr> { vec![address.parse().unwrap()] };
+
+    assert_eq!(resolve("192.0.2.7"), socket("192.0.2.7:9100"));
+    assert_eq!(resolve(" 192.0.2.7:6101 "), socket("192.0.2.7:6101"));
+    assert_eq!(resolve("fe80::1"), socket("[fe80::1]:9100"));
+    assert_eq!(resolve("[fe80::1]"), socket("[fe80::1]:9100"));
+    assert_eq!(resolve("[2001:db8::7]:6101"), socket("[2001:db8::7]:6101"));
+    assert!(resolve("localhost:6101")
+        .iter()
+        .all(|address| address.port() == 6101 && address.ip().is_loopback()));
+    assert!(printer_addresses("printer:raw").is_err());
+}
+
+#[test]
+fn options_are_read_from_the_settings() {
+    let dir = temp_dir();
+    let manager = manager(
+        &dir,
+        &[
+            ("label_exporter", "zpl"),
+            ("labeling_mode", "near_far"),
+            ("zpl_label_width_mm", "25.4"),
+            ("zpl_label_height_mm", "10"),
+            ("zpl_dpi", "203"),
+            ("zpl_font", "d"),
+            ("zpl_font_height", "18"),
+            ("zpl_font_width", "10"),
+            ("zpl_orientation", "bottom"),
+            ("zpl_repeat", "2"),
+            ("zpl_both_ends", "true"),
+        ],
+    );
+
+    assert_eq!(
+        manager.zpl_exporter().unwrap().options,
+        ZplOptions {
+            label_width_mm: 25.4,
+            label_height_mm: 10.0,
+            dpi: 203,
+            font: 'D',
+            font_height: 18,
+            font_width: 10,
+            orientation: ZplOrientation::Bottom,
+            repeat: 2,
+            both_ends: true,
+            labeling_mode: LabelingMode::NearFar,
+        }
+    );
+    let file_path = manager.export_labels(dir.join("labels")).unwrap();
+    let zpl = fs::read_to_string(file_path).unwrap();
+    assert_eq!(zpl.matches("^XA").count(), 2);
+    assert!(zpl.contains("^PW203\n^LL80\n"));
+    assert!(zpl.contains("^FO10,10^ADB,18,10^FDK1-A1^FS"));
+    assert!(zpl.contains("^PQ2\n"));
+
+    for (key, value, message) in [
+        ("zpl_dpi", "0", "Invalid number: 0"),
+        ("zpl_repeat", "twice", "Invalid number: twice"),
+        ("zpl_font", "0B", "Invalid ZPL font: 0B"),
+        (
+            "zpl_orientation",
+            "sideways",
+            "Invalid ZPL orientation: sideways",
+        ),
+    ] {
+        let invalid = self::manager(&dir, &[(key, value)]);
+        assert_eq!(invalid.zpl_exporter().unwrap_err().to_string(), message);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn labels_are_printed_at_the_configured_address() {
+    let dir = temp_dir();
+    let unset = manager(&dir, &[]);
+    assert_eq!(
+        unset.print_labels().unwrap_err().to_string(),
+        "No label printer address is set"
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let printer = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = String::new();
+        stream.read_to_string(&mut received).unwrap();
+        received
+    });
+    let manager = manager(
+        &dir,
+        &[("zpl_printer_address", &address), ("zpl_repeat", "4")],
+    );
+
+    assert_eq!(manager.print_labels().unwrap(), 1);
+    let received = printer.join().unwrap();
+    assert!(received.contains("^FDK1-A1^FS"));
+    assert!(received.contains("^FDX1-1^FS"));
+    assert!(received.contains("^PQ4\n"));
+
+    fs::remove_dir_all(dir).unwrap();
+}